@@ -155,6 +155,7 @@ mod tests {
             }),
             actor: "square.0".into(),
             shot_number: 1,
+            estimated: false,
         }
     }
 
@@ -10,21 +10,30 @@ pub(crate) mod proto;
 
 use proto::*;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fmt;
-use std::io::Read;
+use std::io::{Read, Write};
 use std::net::{SocketAddr, TcpStream};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use super::{Actor, ReconfigureOutcome};
+use super::{Actor, ActorThread, ReconfigureOutcome};
 use crate::bus::{BusReceiver, BusSender, PollError};
 use crate::state::SystemState;
 use flighthook::{
-    ActorStatus, FlighthookEvent, FlighthookMessage, Handedness, Severity, ShotAccumulator,
-    ShotDetectionMode, ShotKey,
+    ActorStatus, DedupOutcome, DedupSection, DeviceModeSection, FlighthookEvent, FlighthookMessage,
+    Handedness, RoutingSection, Severity, ShotAccumulator, ShotData, ShotDeduplicator, ShotKey,
+    ShotRouter, now_ms,
 };
 
+/// Longest the bridge waits on the bus before checking GSPro's socket again.
+#[cfg(not(test))]
+const RESPONSE_POLL: Duration = Duration::from_millis(50);
+/// Long in tests, so a shot that only went out on the next poll would show
+/// up as seconds of latency.
+#[cfg(test)]
+const RESPONSE_POLL: Duration = Duration::from_secs(2);
+
 /// Bridge-internal error type.
 pub(crate) enum BridgeError {
     Io(std::io::Error),
@@ -40,31 +49,35 @@ impl fmt::Display for BridgeError {
     }
 }
 
-/// Per-mode launch monitor routing configuration.
-#[derive(Debug, Clone, Default)]
-pub struct GsProRouting {
-    pub full_monitor: Option<String>,
-    pub chipping_monitor: Option<String>,
-    pub putting_monitor: Option<String>,
-}
-
 /// GSPro bridge actor. Connects to GSPro on port 921 (TCP, JSON),
-/// forwards shot data, and handles heartbeats.
+/// forwards shot data, and handles heartbeats. Shots are filtered by the
+/// section's routing rules (`GsProSection::effective_routing`), each in its
+/// monitor's own detection mode, then duplicates of one swing are dropped per
+/// `[dedup]`.
 pub struct GsProActor {
     pub addr: SocketAddr,
-    pub routing: GsProRouting,
+    pub routing: RoutingSection,
+    pub dedup: DedupSection,
+    /// Launch monitors' `mode` settings (`FlighthookConfig::device_modes`).
+    pub device_modes: HashMap<String, DeviceModeSection>,
 }
 
 impl Actor for GsProActor {
-    fn start(&self, _state: Arc<SystemState>, sender: BusSender, receiver: BusReceiver) {
+    fn start(
+        &self,
+        _state: Arc<SystemState>,
+        sender: BusSender,
+        receiver: BusReceiver,
+    ) -> ActorThread {
         let addr = self.addr;
         let routing = self.routing.clone();
+        let dedup = self.dedup.clone();
+        let device_modes = self.device_modes.clone();
         let thread_name = format!("gspro:{}", sender.actor_id());
 
         std::thread::Builder::new()
             .name(thread_name)
-            .spawn(move || run(addr, routing, sender, receiver))
-            .expect("failed to spawn gspro thread");
+            .spawn(move || run(addr, routing, dedup, device_modes, sender, receiver))
     }
 
     fn reconfigure(&self, state: &Arc<SystemState>, sender: &BusSender) -> ReconfigureOutcome {
@@ -89,10 +102,10 @@ impl Actor for GsProActor {
             return ReconfigureOutcome::RestartRequired;
         }
 
-        // Check routing changes
-        if section.full_monitor != self.routing.full_monitor
-            || section.chipping_monitor != self.routing.chipping_monitor
-            || section.putting_monitor != self.routing.putting_monitor
+        // Check routing changes, including edits to a named rule set
+        if section.effective_routing(&snap.routing) != self.routing
+            || snap.dedup != self.dedup
+            || snap.device_modes() != self.device_modes
         {
             return ReconfigureOutcome::RestartRequired;
         }
@@ -102,10 +115,23 @@ impl Actor for GsProActor {
 }
 
 /// Main bridge loop. Reconnects forever until the bus closes.
-fn run(addr: SocketAddr, routing: GsProRouting, sender: BusSender, mut receiver: BusReceiver) {
+fn run(
+    addr: SocketAddr,
+    routing: RoutingSection,
+    dedup: DedupSection,
+    device_modes: HashMap<String, DeviceModeSection>,
+    sender: BusSender,
+    mut receiver: BusReceiver,
+) {
     let mut backoff = Duration::from_secs(1);
     let max_backoff = Duration::from_secs(15);
     let mut ever_connected = false;
+    // Outlives the connection, so swings held when GSPro drops go out on
+    // the next one.
+    let mut deduplicator = ShotDeduplicator::new(dedup);
+    // Swings whose window closed but which GSPro has not taken yet; kept
+    // across connections like the deduplicator.
+    let mut unsent = VecDeque::new();
 
     loop {
         if receiver.is_shutdown() {
@@ -123,7 +149,16 @@ fn run(addr: SocketAddr, routing: GsProRouting, sender: BusSender, mut receiver:
             telemetry: HashMap::new(),
         }));
 
-        match connect_and_run(addr, &routing, &sender, &mut receiver, &mut ever_connected) {
+        match connect_and_run(
+            addr,
+            &routing,
+            &mut deduplicator,
+            &mut unsent,
+            &device_modes,
+            &sender,
+            &mut receiver,
+            &mut ever_connected,
+        ) {
             Ok(()) => {
                 tracing::info!("gspro bridge: shutting down");
                 return;
@@ -147,54 +182,25 @@ fn run(addr: SocketAddr, routing: GsProRouting, sender: BusSender, mut receiver:
                     status: backoff_status,
                     telemetry: HashMap::new(),
                 }));
-                std::thread::sleep(backoff);
+                let deadline = Instant::now() + backoff;
+                while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+                    if receiver.recv_timeout(left).is_err() {
+                        break;
+                    }
+                }
                 backoff = (backoff + Duration::from_secs(1)).min(max_backoff);
             }
         }
     }
 }
 
-/// Compute readiness from routing config, current mode, and per-monitor state.
-///
-/// Returns a single `ready` bool. `DeviceTelemetry.ready` is the unified
-/// readiness signal (all device conditions met). GSPro's
-/// `launch_monitor_is_ready` and `launch_monitor_ball_detected` are both
-/// set from this single value.
-fn readiness(
-    routing: &GsProRouting,
-    mode: ShotDetectionMode,
-    states: &HashMap<String, bool>,
-) -> bool {
-    let target = match mode {
-        ShotDetectionMode::Full => &routing.full_monitor,
-        ShotDetectionMode::Chipping => &routing.chipping_monitor,
-        ShotDetectionMode::Putting => &routing.putting_monitor,
-    };
-    match target {
-        Some(id) => *states.get(id.as_str()).unwrap_or(&false),
-        None => {
-            // "Any" — ready if any monitor is ready
-            states.values().any(|&r| r)
-        }
-    }
-}
-
-/// Check if a shot actor matches the routing target for the current mode.
-fn shot_matches_routing(routing: &GsProRouting, mode: ShotDetectionMode, actor: &str) -> bool {
-    let target = match mode {
-        ShotDetectionMode::Full => &routing.full_monitor,
-        ShotDetectionMode::Chipping => &routing.chipping_monitor,
-        ShotDetectionMode::Putting => &routing.putting_monitor,
-    };
-    match target {
-        Some(id) => actor == id,
-        None => true, // "Any" — accept from all
-    }
-}
-
+#[allow(clippy::too_many_arguments)]
 fn connect_and_run(
     addr: SocketAddr,
-    routing: &GsProRouting,
+    routing: &RoutingSection,
+    deduplicator: &mut ShotDeduplicator<QueuedShot>,
+    unsent: &mut VecDeque<DedupOutcome<QueuedShot>>,
+    device_modes: &HashMap<String, DeviceModeSection>,
     sender: &BusSender,
     receiver: &mut BusReceiver,
     ever_connected: &mut bool,
@@ -204,9 +210,6 @@ fn connect_and_run(
     let mut stream =
         TcpStream::connect_timeout(&addr, Duration::from_secs(5)).map_err(BridgeError::Io)?;
     stream.set_nodelay(true).map_err(BridgeError::Io)?;
-    stream
-        .set_read_timeout(Some(Duration::from_millis(50)))
-        .map_err(BridgeError::Io)?;
 
     *ever_connected = true;
     tracing::info!("gspro bridge: connected to {addr}");
@@ -215,12 +218,11 @@ fn connect_and_run(
         telemetry: HashMap::new(),
     }));
 
-    let mut current_mode = ShotDetectionMode::Full;
+    let mut router = ShotRouter::new(routing.clone()).with_device_modes(device_modes.clone());
     let mut current_handed = Handedness::Right;
     // Backdate so the first heartbeat fires after ~1s instead of waiting the full 10s.
     let mut last_heartbeat = Instant::now() - Duration::from_secs(9);
     let mut read_buf = vec![0u8; 4096];
-    let mut monitor_state: HashMap<String, bool> = HashMap::new();
     let mut prev_readiness = false;
     // Per-actor shot accumulators, keyed by (actor, shot_key)
     let mut accumulators: HashMap<(String, ShotKey), ShotAccumulator> = HashMap::new();
@@ -232,8 +234,8 @@ fn connect_and_run(
         let mut activity = false;
 
         // 1. Read any data from GSPro
-        match stream.read(&mut read_buf) {
-            Ok(0) => {
+        match read_available(&mut stream, &mut read_buf) {
+            Ok(Some(0)) => {
                 tracing::warn!("gspro <- connection closed");
                 sender.send(FlighthookMessage::new(FlighthookEvent::Alert {
                     severity: Severity::Warn,
@@ -244,7 +246,7 @@ fn connect_and_run(
                     "connection closed by GSPro",
                 )));
             }
-            Ok(n) => {
+            Ok(Some(n)) => {
                 let raw_text = String::from_utf8_lossy(&read_buf[..n]);
                 let decoded = parse_response_debug(&read_buf[..n]);
                 tracing::info!(
@@ -255,9 +257,7 @@ fn connect_and_run(
                 handle_response(&read_buf[..n], sender);
                 activity = true;
             }
-            Err(ref e)
-                if e.kind() == std::io::ErrorKind::TimedOut
-                    || e.kind() == std::io::ErrorKind::WouldBlock => {}
+            Ok(None) => {}
             Err(e) => {
                 tracing::warn!("gspro <- read error: {e}");
                 sender.send(FlighthookMessage::new(FlighthookEvent::Alert {
@@ -268,103 +268,110 @@ fn connect_and_run(
             }
         }
 
-        // 2. Drain bus events — collect any queued shot to send
-        let mut shot_to_send = None;
+        // 2. Drain bus events — routed shots go to the deduplicator
         let mut readiness_changed = false;
         loop {
             match receiver.poll() {
                 Err(PollError::Shutdown) => return Err(BridgeError::Shutdown),
                 Ok(None) => break,
-                Ok(Some(msg)) => match msg.event {
-                    FlighthookEvent::ShotTrigger { ref key } => {
-                        let acc = ShotAccumulator::new(msg.actor.clone(), key.clone());
-                        accumulators.insert((msg.actor.clone(), key.clone()), acc);
-                    }
-                    FlighthookEvent::BallFlight { ref key, ref ball } => {
-                        if let Some(acc) = accumulators.get_mut(&(msg.actor.clone(), key.clone())) {
-                            acc.set_ball(*ball.clone());
+                Ok(Some(msg)) => {
+                    router.observe(&msg, now_ms());
+                    match msg.event {
+                        FlighthookEvent::ShotTrigger { ref key } => {
+                            let acc = ShotAccumulator::new(msg.actor.clone(), key.clone())
+                                .triggered_at(msg.timestamp_ms);
+                            accumulators.insert((msg.actor.clone(), key.clone()), acc);
                         }
-                    }
-                    FlighthookEvent::FaceImpact {
-                        ref key,
-                        ref impact,
-                    } => {
-                        if let Some(acc) = accumulators.get_mut(&(msg.actor.clone(), key.clone())) {
-                            acc.set_impact(*impact.clone());
+                        FlighthookEvent::BallFlight {
+                            ref key,
+                            ref ball,
+                            estimated,
+                        } => {
+                            if let Some(acc) =
+                                accumulators.get_mut(&(msg.actor.clone(), key.clone()))
+                            {
+                                acc.set_ball(*ball.clone());
+                                acc.set_estimated(estimated);
+                            }
                         }
-                    }
-                    FlighthookEvent::ClubPath { ref key, ref club } => {
-                        if let Some(acc) = accumulators.get_mut(&(msg.actor.clone(), key.clone())) {
-                            acc.set_club(*club.clone());
+                        FlighthookEvent::FaceImpact {
+                            ref key,
+                            ref impact,
+                        } => {
+                            if let Some(acc) =
+                                accumulators.get_mut(&(msg.actor.clone(), key.clone()))
+                            {
+                                acc.set_impact(*impact.clone());
+                            }
                         }
-                    }
-                    FlighthookEvent::ShotFinished { ref key } => {
-                        if let Some(acc) = accumulators.remove(&(msg.actor.clone(), key.clone())) {
-                            if !shot_matches_routing(routing, current_mode, &msg.actor) {
-                                tracing::debug!(
-                                    "gspro bridge: skipping shot #{} from '{}' (routed to {:?} for mode {current_mode:?})",
+                        FlighthookEvent::ClubPath { ref key, ref club } => {
+                            if let Some(acc) =
+                                accumulators.get_mut(&(msg.actor.clone(), key.clone()))
+                            {
+                                acc.set_club(*club.clone());
+                            }
+                        }
+                        FlighthookEvent::ShotFinished { ref key } => {
+                            let Some(acc) = accumulators.remove(&(msg.actor.clone(), key.clone()))
+                            else {
+                                continue;
+                            };
+                            let triggered_ms = acc.triggered_ms;
+                            let Some(shot) = acc.finish() else {
+                                continue;
+                            };
+                            match router.route(&msg.actor, &shot, now_ms()) {
+                                None => tracing::debug!(
+                                    "gspro bridge: skipping shot #{} from '{}' (no routing rule matches in {} mode)",
                                     key.shot_number,
                                     msg.actor,
-                                    match current_mode {
-                                        ShotDetectionMode::Full => &routing.full_monitor,
-                                        ShotDetectionMode::Chipping => &routing.chipping_monitor,
-                                        ShotDetectionMode::Putting => &routing.putting_monitor,
-                                    },
-                                );
-                            } else if let Some(shot) = acc.finish() {
-                                shot_to_send = Some(Box::new(shot));
+                                    router.mode(),
+                                ),
+                                Some(rule) => {
+                                    tracing::debug!(
+                                        "gspro bridge: shot #{} from '{}' matches routing rule {rule}",
+                                        key.shot_number,
+                                        msg.actor,
+                                    );
+                                    let now = now_ms();
+                                    let queued = QueuedShot {
+                                        shot: Box::new(shot),
+                                        key: key.clone(),
+                                        source: msg.actor.clone(),
+                                        triggered_ms,
+                                        finished_ms: msg.timestamp_ms,
+                                    };
+                                    let at = triggered_ms.or(msg.timestamp_ms).unwrap_or(now);
+                                    deduplicator.offer(queued, at, now);
+                                }
                             }
                         }
-                    }
-                    FlighthookEvent::DeviceTelemetry {
-                        telemetry: Some(ref tel),
-                        ..
-                    } if tel.contains_key("ready") => {
-                        let ready = tel.get("ready").is_some_and(|v| v == "true");
-                        monitor_state.insert(msg.actor.clone(), ready);
-                        readiness_changed = true;
-                    }
-                    FlighthookEvent::SetDetectionMode { mode, handed } => {
-                        if let Some(&m) = mode.as_ref() {
-                            current_mode = m;
-                            readiness_changed = true;
-                        }
-                        if let Some(&h) = handed.as_ref() {
-                            current_handed = h;
+                        FlighthookEvent::DeviceTelemetry {
+                            telemetry: Some(ref tel),
+                            ..
+                        } if tel.contains_key("ready") => readiness_changed = true,
+                        FlighthookEvent::SetDetectionMode { mode, handed } => {
+                            readiness_changed |= mode.is_some();
+                            if let Some(&h) = handed.as_ref() {
+                                current_handed = h;
+                            }
                         }
-                    }
-                    FlighthookEvent::ActorStatus { status, .. } => {
-                        if matches!(
-                            status,
-                            ActorStatus::Disconnected | ActorStatus::Reconnecting
-                        ) && monitor_state.contains_key(&msg.actor)
-                        {
-                            monitor_state.insert(msg.actor.clone(), false);
+                        FlighthookEvent::ClubInfo { .. } | FlighthookEvent::ActorStatus { .. } => {
                             readiness_changed = true;
                         }
+                        _ => {}
                     }
-                    _ => {}
-                },
+                }
             }
         }
 
-        // 3. Send shot if queued
-        if let Some(shot) = shot_to_send {
-            let msg = mapper::map_shot(&shot, current_handed);
-            log_outbound(&msg);
-            if let Ok(json_str) = serde_json::to_string(&msg) {
-                tracing::info!(
-                    target: "audit",
-                    "{name} sent shot {json_str} | {msg:?}",
-                );
-            }
-            send_message(&mut stream, &msg)?;
-            activity = true;
-        }
+        // 3. Send the best shot of each swing whose dedup window has closed
+        unsent.extend(deduplicator.due(now_ms()));
+        activity |= flush_due(unsent, &mut stream, current_handed, sender)?;
 
         // 4. Immediate heartbeat on readiness change
         if readiness_changed {
-            let new_readiness = readiness(routing, current_mode, &monitor_state);
+            let new_readiness = router.ready(now_ms());
             if new_readiness != prev_readiness {
                 prev_readiness = new_readiness;
                 let msg = api::GsProMessage::heartbeat_with_readiness(new_readiness, new_readiness);
@@ -384,7 +391,7 @@ fn connect_and_run(
         // 5. Periodic heartbeat if due
         if last_heartbeat.elapsed() >= Duration::from_secs(10) {
             last_heartbeat = Instant::now();
-            let ready = readiness(routing, current_mode, &monitor_state);
+            let ready = router.ready(now_ms());
             prev_readiness = ready;
             let msg = api::GsProMessage::heartbeat_with_readiness(ready, ready);
             tracing::debug!("gspro -> heartbeat (ready={ready})");
@@ -398,8 +405,471 @@ fn connect_and_run(
             activity = true;
         }
 
+        // Shots go out as soon as they are on the bus (or their dedup window
+        // closes); GSPro's responses are picked up at least every RESPONSE_POLL.
         if !activity {
-            std::thread::sleep(Duration::from_millis(100));
+            let until_due = deduplicator
+                .next_due_ms()
+                .map(|due| Duration::from_millis(due.saturating_sub(now_ms())));
+            receiver.wait(until_due.map_or(RESPONSE_POLL, |d| d.min(RESPONSE_POLL)));
+        }
+    }
+}
+
+/// Send the kept shot of each due swing, oldest first. A swing leaves
+/// `unsent` only once GSPro has its shot, so one that fails goes out on the
+/// next connection. Returns whether anything was sent.
+fn flush_due(
+    unsent: &mut VecDeque<DedupOutcome<QueuedShot>>,
+    stream: &mut impl Write,
+    handed: Handedness,
+    sender: &BusSender,
+) -> Result<bool, BridgeError> {
+    let name = sender.actor_id();
+    let mut sent = false;
+    while let Some(outcome) = unsent.pop_front() {
+        // A late outcome's kept shot already went out with its swing.
+        if !outcome.late {
+            let msg = mapper::map_shot(&outcome.kept.shot, handed);
+            log_outbound(&msg);
+            if let Ok(json_str) = serde_json::to_string(&msg) {
+                tracing::info!(
+                    target: "audit",
+                    "{name} sent shot {json_str} | {msg:?}",
+                );
+            }
+            if let Err(e) = send_message(stream, &msg) {
+                unsent.push_front(outcome);
+                return Err(e);
+            }
+            sent = true;
+        }
+        let queued = outcome.kept;
+        for dropped in outcome.dropped {
+            tracing::debug!(
+                "gspro bridge: suppressing shot #{} from '{}' (duplicate of #{} from '{}')",
+                dropped.key.shot_number,
+                dropped.source,
+                queued.key.shot_number,
+                queued.source,
+            );
+            sender.send(FlighthookMessage::new(FlighthookEvent::ShotSuppressed {
+                key: dropped.key,
+                source: dropped.source,
+                kept: queued.key.clone(),
+                kept_source: queued.source.clone(),
+            }));
+        }
+        if !outcome.late {
+            report_delivery(sender, queued, now_ms());
+        }
+    }
+    Ok(sent)
+}
+
+/// A finished shot waiting to go out, with what is needed to report its
+/// latency once it has.
+#[derive(Clone)]
+struct QueuedShot {
+    shot: Box<ShotData>,
+    key: ShotKey,
+    source: String,
+    triggered_ms: Option<u64>,
+    finished_ms: Option<u64>,
+}
+
+impl AsRef<ShotData> for QueuedShot {
+    fn as_ref(&self) -> &ShotData {
+        &self.shot
+    }
+}
+
+/// Announce a shot handed to GSPro at `sent_ms`: a `ShotDelivered` for the
+/// shot history, and the latencies as telemetry.
+fn report_delivery(sender: &BusSender, queued: QueuedShot, sent_ms: u64) {
+    let since = |t: Option<u64>| t.map(|t| sent_ms.saturating_sub(t));
+    let mut telemetry = HashMap::new();
+    if let Some(ms) = since(queued.triggered_ms) {
+        telemetry.insert("last_shot_latency_ms".into(), ms.to_string());
+    }
+    if let Some(ms) = since(queued.finished_ms) {
+        telemetry.insert("last_delivery_latency_ms".into(), ms.to_string());
+        sender.send(FlighthookMessage::new(FlighthookEvent::ShotDelivered {
+            key: queued.key,
+            source: queued.source,
+            latency_ms: ms,
+        }));
+    }
+    if !telemetry.is_empty() {
+        sender.send(FlighthookMessage::new(FlighthookEvent::ActorStatus {
+            status: ActorStatus::Connected,
+            telemetry,
+        }));
+    }
+}
+
+/// Read whatever GSPro has sent without blocking: `Ok(None)` if nothing is
+/// waiting. Writes stay blocking.
+fn read_available(stream: &mut TcpStream, buf: &mut [u8]) -> std::io::Result<Option<usize>> {
+    stream.set_nonblocking(true)?;
+    let read = stream.read(buf);
+    stream.set_nonblocking(false)?;
+    match read {
+        Ok(n) => Ok(Some(n)),
+        Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    use flighthook::{BallFlight, GsProSection, Velocity};
+
+    use crate::bus::{Bus, ShutdownFlag};
+
+    /// Read from a fake GSPro until a shot (not a heartbeat) arrives.
+    fn read_shot(stream: &mut TcpStream, buf: &mut Vec<u8>) -> u32 {
+        let mut chunk = [0u8; 4096];
+        loop {
+            let mut parsed = 0;
+            let mut shot = None;
+            let mut messages =
+                serde_json::Deserializer::from_slice(buf).into_iter::<api::GsProMessage>();
+            while let Some(Ok(msg)) = messages.next() {
+                parsed = messages.byte_offset();
+                if !msg.shot_data_options.is_heart_beat {
+                    shot = Some(msg.shot_number);
+                    break;
+                }
+            }
+            buf.drain(..parsed);
+            if let Some(n) = shot {
+                return n;
+            }
+            let n = stream.read(&mut chunk).expect("fake GSPro read");
+            assert!(n > 0, "bridge closed the connection");
+            buf.extend_from_slice(&chunk[..n]);
+        }
+    }
+
+    /// The bridge wakes on the bus message rather than its poll timer:
+    /// every shot reaches GSPro well inside one `RESPONSE_POLL`.
+    #[test]
+    fn shot_reaches_gspro_without_waiting_for_the_poll() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let bus_tx = Bus::new(256);
+        let mut watcher = bus_tx.subscribe();
+        let shutdown = Arc::new(ShutdownFlag::new());
+        let sender = BusSender::new("gspro.0".into(), bus_tx.clone(), Arc::clone(&shutdown));
+        let receiver = sender.subscribe();
+        let any_monitor = GsProSection::default().effective_routing(&HashMap::new());
+        let bridge = std::thread::spawn(move || {
+            run(
+                addr,
+                any_monitor,
+                DedupSection::default(),
+                HashMap::new(),
+                sender,
+                receiver,
+            )
+        });
+
+        let (mut gspro, _) = listener.accept().unwrap();
+        gspro
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let mut buf = Vec::new();
+        for shot_number in 1..=20 {
+            // Irregular gaps, so shots land at every point of the bridge's loop.
+            std::thread::sleep(Duration::from_millis(
+                20 + u64::from(shot_number) * 53 % 150,
+            ));
+            let key = ShotKey {
+                shot_id: format!("shot-{shot_number}"),
+                shot_number,
+            };
+            let send = |event| {
+                bus_tx.send(FlighthookMessage::new(event).actor("mock_monitor.0"));
+            };
+            let triggered = Instant::now();
+            send(FlighthookEvent::ShotTrigger { key: key.clone() });
+            send(FlighthookEvent::BallFlight {
+                key: key.clone(),
+                estimated: false,
+                ball: Box::new(BallFlight {
+                    launch_speed: Some(Velocity::MetersPerSecond(60.0)),
+                    ..Default::default()
+                }),
+            });
+            send(FlighthookEvent::ShotFinished { key });
+            assert_eq!(read_shot(&mut gspro, &mut buf), shot_number);
+            let latency = triggered.elapsed();
+            assert!(
+                latency < RESPONSE_POLL / 2,
+                "shot {shot_number}: {latency:?}"
+            );
+        }
+
+        shutdown.set();
+        bridge.join().unwrap();
+
+        let mut delivered = Vec::new();
+        while let Ok(Some(msg)) = watcher.poll() {
+            if let FlighthookEvent::ShotDelivered { key, source, .. } = msg.event {
+                assert_eq!(msg.actor, "gspro.0");
+                assert_eq!(source, "mock_monitor.0");
+                delivered.push(key.shot_number);
+            }
+        }
+        assert_eq!(delivered, (1..=20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn shot_published_over_websocket_is_routed_by_client_name() {
+        use crate::actors::web::ws::{client_actor_id, parse_client_frame};
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let bus_tx = Bus::new(256);
+        let shutdown = Arc::new(ShutdownFlag::new());
+        let sender = BusSender::new("gspro.0".into(), bus_tx.clone(), Arc::clone(&shutdown));
+        let receiver = sender.subscribe();
+        let routing = GsProSection {
+            full_monitor: Some("ws.uneekor-bridge".into()),
+            ..GsProSection::default()
+        }
+        .effective_routing(&HashMap::new());
+        let bridge = std::thread::spawn(move || {
+            run(
+                addr,
+                routing,
+                DedupSection::default(),
+                HashMap::new(),
+                sender,
+                receiver,
+            )
+        });
+
+        let (mut gspro, _) = listener.accept().unwrap();
+        gspro
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        // A built-in monitor's shot is not routed; the client's is.
+        let client = client_actor_id("Uneekor Bridge");
+        for (actor, shot_number) in [("mevo.0", 1), (client.as_str(), 2)] {
+            let key = format!(
+                r#"{{ "shot_id": "{actor}-{shot_number}", "shot_number": {shot_number} }}"#
+            );
+            for event in [
+                format!(r#"{{ "kind": "shot_trigger", "key": {key} }}"#),
+                format!(
+                    r#"{{ "kind": "ball_flight", "key": {key}, "ball": {{ "launch_speed": "60mps" }} }}"#
+                ),
+                format!(r#"{{ "kind": "shot_finished", "key": {key} }}"#),
+            ] {
+                let frame = format!(r#"{{ "event": {event} }}"#);
+                bus_tx.send(parse_client_frame(&frame, actor).unwrap());
+            }
+        }
+        let mut buf = Vec::new();
+        assert_eq!(read_shot(&mut gspro, &mut buf), 2);
+
+        shutdown.set();
+        bridge.join().unwrap();
+    }
+
+    #[test]
+    fn one_swing_from_two_monitors_is_sent_once() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let bus_tx = Bus::new(256);
+        let mut watcher = bus_tx.subscribe();
+        let shutdown = Arc::new(ShutdownFlag::new());
+        let sender = BusSender::new("gspro.0".into(), bus_tx.clone(), Arc::clone(&shutdown));
+        let receiver = sender.subscribe();
+        let any_monitor = GsProSection::default().effective_routing(&HashMap::new());
+        let dedup = DedupSection {
+            window_ms: Some(200),
+            priority: vec!["r10.0".into()],
+            ..Default::default()
+        };
+        let bridge = std::thread::spawn(move || {
+            run(addr, any_monitor, dedup, HashMap::new(), sender, receiver)
+        });
+
+        let (mut gspro, _) = listener.accept().unwrap();
+        gspro
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        for (actor, shot_number) in [("mevo.0", 7), ("r10.0", 3)] {
+            let key = ShotKey {
+                shot_id: format!("{actor}-{shot_number}"),
+                shot_number,
+            };
+            let send = |event| {
+                bus_tx.send(FlighthookMessage::new(event).actor(actor));
+            };
+            send(FlighthookEvent::ShotTrigger { key: key.clone() });
+            send(FlighthookEvent::BallFlight {
+                key: key.clone(),
+                ball: Box::new(BallFlight::default()),
+                estimated: false,
+            });
+            send(FlighthookEvent::ShotFinished { key });
+        }
+        let mut buf = Vec::new();
+        assert_eq!(read_shot(&mut gspro, &mut buf), 3);
+
+        shutdown.set();
+        bridge.join().unwrap();
+
+        let mut delivered = Vec::new();
+        let mut suppressed = Vec::new();
+        while let Ok(Some(msg)) = watcher.poll() {
+            match msg.event {
+                FlighthookEvent::ShotDelivered { source, .. } => delivered.push(source),
+                FlighthookEvent::ShotSuppressed {
+                    source,
+                    kept_source,
+                    ..
+                } => suppressed.push((source, kept_source)),
+                _ => {}
+            }
+        }
+        assert_eq!(delivered, ["r10.0"]);
+        assert_eq!(suppressed, [("mevo.0".to_string(), "r10.0".to_string())]);
+    }
+
+    #[test]
+    fn held_swing_survives_a_gspro_reconnect() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let bus_tx = Bus::new(256);
+        let shutdown = Arc::new(ShutdownFlag::new());
+        let sender = BusSender::new("gspro.0".into(), bus_tx.clone(), Arc::clone(&shutdown));
+        let receiver = sender.subscribe();
+        let any_monitor = GsProSection::default().effective_routing(&HashMap::new());
+        let dedup = DedupSection {
+            window_ms: Some(1_000),
+            ..Default::default()
+        };
+        let bridge = std::thread::spawn(move || {
+            run(addr, any_monitor, dedup, HashMap::new(), sender, receiver)
+        });
+
+        let (gspro, _) = listener.accept().unwrap();
+        let key = ShotKey {
+            shot_id: "mevo.0-4".into(),
+            shot_number: 4,
+        };
+        let send = |event| {
+            bus_tx.send(FlighthookMessage::new(event).actor("mevo.0"));
+        };
+        send(FlighthookEvent::ShotTrigger { key: key.clone() });
+        send(FlighthookEvent::BallFlight {
+            key: key.clone(),
+            ball: Box::new(BallFlight::default()),
+            estimated: false,
+        });
+        send(FlighthookEvent::ShotFinished { key });
+        // GSPro goes away while the swing is still held.
+        std::thread::sleep(Duration::from_millis(100));
+        drop(gspro);
+
+        let (mut gspro, _) = listener.accept().unwrap();
+        gspro
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        assert_eq!(read_shot(&mut gspro, &mut Vec::new()), 4);
+
+        shutdown.set();
+        bridge.join().unwrap();
+    }
+
+    /// Takes `messages` whole messages, then fails every write as a socket
+    /// GSPro closed would.
+    struct ClosesAfter {
+        messages: usize,
+    }
+
+    impl Write for ClosesAfter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if self.messages == 0 {
+                return Err(std::io::ErrorKind::BrokenPipe.into());
+            }
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            self.messages = self.messages.saturating_sub(1);
+            Ok(())
+        }
+    }
+
+    fn due_swing(shot_number: u32) -> DedupOutcome<QueuedShot> {
+        let key = ShotKey {
+            shot_id: format!("mevo.0-{shot_number}"),
+            shot_number,
+        };
+        let mut acc = ShotAccumulator::new("mevo.0".into(), key.clone());
+        acc.set_ball(BallFlight {
+            launch_speed: Some(Velocity::MetersPerSecond(60.0)),
+            ..Default::default()
+        });
+        DedupOutcome {
+            kept: QueuedShot {
+                shot: Box::new(acc.finish().unwrap()),
+                key,
+                source: "mevo.0".into(),
+                triggered_ms: None,
+                finished_ms: Some(now_ms()),
+            },
+            dropped: Vec::new(),
+            late: false,
+        }
+    }
+
+    #[test]
+    fn shots_due_when_gspro_drops_mid_flush_are_kept() {
+        let bus = Bus::new(64);
+        let mut watcher = bus.subscribe();
+        let sender = BusSender::new("gspro.0".into(), bus, Arc::new(ShutdownFlag::new()));
+        let mut unsent = VecDeque::from([due_swing(1), due_swing(2)]);
+
+        // GSPro goes away after taking the first shot.
+        let closed = flush_due(
+            &mut unsent,
+            &mut ClosesAfter { messages: 1 },
+            Handedness::Right,
+            &sender,
+        );
+        assert!(matches!(closed, Err(BridgeError::Io(_))));
+        let left: Vec<_> = unsent.iter().map(|o| o.kept.key.shot_number).collect();
+        assert_eq!(left, [2]);
+
+        // The next connection gets the rest.
+        let mut reconnected = Vec::new();
+        assert!(matches!(
+            flush_due(&mut unsent, &mut reconnected, Handedness::Right, &sender),
+            Ok(true)
+        ));
+        assert!(unsent.is_empty());
+        let sent: Vec<_> = serde_json::Deserializer::from_slice(&reconnected)
+            .into_iter::<api::GsProMessage>()
+            .map(|msg| msg.unwrap().shot_number)
+            .collect();
+        assert_eq!(sent, [2]);
+
+        let mut delivered = Vec::new();
+        while let Ok(Some(msg)) = watcher.poll() {
+            if let FlighthookEvent::ShotDelivered { key, .. } = msg.event {
+                delivered.push(key.shot_number);
+            }
         }
+        assert_eq!(delivered, [1, 2]);
     }
 }
@@ -11,14 +11,14 @@ use ironsight::seq::ShotDatum;
 use ironsight::{BinaryClient, BinaryConnection, BinaryEvent, ConnError, Message};
 use tracing::{debug, info, warn};
 
-use super::{Actor, ReconfigureOutcome};
+use super::{Actor, ActorThread, ReconfigureOutcome};
 use crate::bus::{BusReceiver, BusSender, PollError};
 use crate::state::SystemState;
 use settings::{cam_config, fusion_cam_config};
 
 use flighthook::{
-    ActorStatus, BallFlight, CameraMode, ClubData, Distance, FlighthookEvent, FlighthookMessage,
-    Severity, ShotDetectionMode, ShotKey, Velocity,
+    ActorStatus, BallFlight, CameraMode, ClubData, DeviceModeSection, Distance, FlighthookEvent,
+    FlighthookMessage, Severity, ShotDetectionMode, ShotKey, Velocity,
 };
 
 /// No events for this long → treat as disconnected.
@@ -43,16 +43,25 @@ const CAMERA_WARMUP: Duration = Duration::from_secs(15);
 /// arms, and processes shots in a reconnecting event loop.
 pub struct MevoActor {
     pub addr: SocketAddr,
+    /// The device's mode at start: the global mode through `device_mode`.
     pub initial_mode: ShotDetectionMode,
+    /// Pin or mapping applied to every global mode change.
+    pub device_mode: DeviceModeSection,
     pub session_config: SessionConfig,
     pub use_estimated: bool,
     pub camera_mode: CameraMode,
 }
 
 impl Actor for MevoActor {
-    fn start(&self, _state: Arc<SystemState>, sender: BusSender, receiver: BusReceiver) {
+    fn start(
+        &self,
+        _state: Arc<SystemState>,
+        sender: BusSender,
+        receiver: BusReceiver,
+    ) -> ActorThread {
         let addr = self.addr;
         let initial_mode = self.initial_mode;
+        let device_mode = self.device_mode;
         let session_config = self.session_config.clone();
         let use_estimated = self.use_estimated;
         let camera_mode = self.camera_mode;
@@ -64,6 +73,7 @@ impl Actor for MevoActor {
                 run(
                     addr,
                     initial_mode,
+                    device_mode,
                     session_config,
                     use_estimated,
                     camera_mode,
@@ -71,7 +81,6 @@ impl Actor for MevoActor {
                     receiver,
                 );
             })
-            .expect("failed to spawn mevo thread");
     }
 
     fn reconfigure(&self, state: &Arc<SystemState>, sender: &BusSender) -> ReconfigureOutcome {
@@ -115,6 +124,11 @@ impl Actor for MevoActor {
             return ReconfigureOutcome::RestartRequired;
         }
 
+        // Mode pin or mapping changed -> restart to re-arm in the new mode
+        if section.mode != self.device_mode {
+            return ReconfigureOutcome::RestartRequired;
+        }
+
         ReconfigureOutcome::Applied
     }
 }
@@ -169,9 +183,11 @@ fn try_emit_device_telemetry(
 // Run loop
 // ---------------------------------------------------------------------------
 
+#[allow(clippy::too_many_arguments)]
 fn run(
     addr: SocketAddr,
     initial_mode: ShotDetectionMode,
+    device_mode: DeviceModeSection,
     initial_session_config: SessionConfig,
     use_estimated: bool,
     camera_mode: CameraMode,
@@ -196,6 +212,7 @@ fn run(
         match connect_and_run(
             &addr,
             initial_mode,
+            device_mode,
             use_estimated,
             camera_mode,
             &sender,
@@ -234,12 +251,11 @@ fn run(
                 info!("{} in {}s", verb.to_lowercase(), backoff.as_secs());
 
                 let deadline = Instant::now() + backoff;
-                while Instant::now() < deadline {
-                    if receiver.poll().is_err() {
+                while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+                    if receiver.recv_timeout(left).is_err() {
                         emit_device_status(&sender, ActorStatus::Disconnected, HashMap::new());
                         return;
                     }
-                    std::thread::sleep(Duration::from_millis(250));
                 }
                 backoff = (backoff + Duration::from_secs(1)).min(MAX_BACKOFF);
             }
@@ -257,6 +273,7 @@ fn run(
 fn connect_and_run(
     addr: &SocketAddr,
     initial_mode: ShotDetectionMode,
+    device_mode: DeviceModeSection,
     use_estimated: bool,
     camera_mode: CameraMode,
     sender: &BusSender,
@@ -344,10 +361,13 @@ fn connect_and_run(
                 }
                 Ok(None) => break,
                 Ok(Some(msg)) => {
+                    // Global mode changes arrive through the section's pin
+                    // or mapping; a pinned device never re-arms for them.
                     if let FlighthookEvent::SetDetectionMode {
-                        mode: Some(new_mode),
+                        mode: Some(global_mode),
                         ..
                     } = msg.event
+                        && let new_mode = device_mode.effective(global_mode)
                         && !same_mode(current_mode, new_mode)
                     {
                         if client.is_armed() {
@@ -531,6 +551,7 @@ fn connect_and_run(
                                     let msg = FlighthookMessage::new(FlighthookEvent::BallFlight {
                                         key: key.clone(),
                                         ball: Box::new(ball),
+                                        estimated: false,
                                     });
                                     sender.send(stamp_device(msg, device_id));
                                 }
@@ -575,6 +596,7 @@ fn connect_and_run(
                                             FlighthookMessage::new(FlighthookEvent::BallFlight {
                                                 key: key.clone(),
                                                 ball: Box::new(ball),
+                                                estimated: true,
                                             });
                                         sender.send(stamp_device(msg, device_id));
                                     } else {
@@ -683,7 +705,7 @@ fn connect_and_run(
             Ok(None) => {
                 idle_count += 1;
                 if idle_count >= 3 {
-                    std::thread::sleep(Duration::from_millis(50));
+                    receiver.wait(Duration::from_millis(50));
                 }
                 if idle_count == 1 || idle_count.is_multiple_of(200) {
                     debug!(
@@ -11,12 +11,12 @@ use std::time::{Duration, Instant};
 
 use tracing::info;
 
-use crate::actors::Actor;
+use crate::actors::{Actor, ActorThread, ReconfigureOutcome};
 use crate::bus::{BusReceiver, BusSender, PollError};
 use crate::state::SystemState;
 use flighthook::{
-    ActorStatus, BallFlight, ClubData, Distance, FlighthookEvent, FlighthookMessage,
-    ShotDetectionMode, ShotKey, Velocity,
+    ActorStatus, BallFlight, ClubData, DeviceModeSection, Distance, FlighthookEvent,
+    FlighthookMessage, ShotDetectionMode, ShotKey, Velocity,
 };
 
 const SHOT_INTERVAL: Duration = Duration::from_secs(30);
@@ -38,19 +38,38 @@ enum Phase {
 
 /// Mock launch monitor actor. Generates random shots at a fixed interval.
 pub struct MockLaunchActor {
+    /// The device's mode at start: the global mode through `device_mode`.
     pub initial_mode: ShotDetectionMode,
+    /// Pin or mapping applied to every global mode change.
+    pub device_mode: DeviceModeSection,
 }
 
 impl Actor for MockLaunchActor {
-    fn start(&self, _state: Arc<SystemState>, sender: BusSender, receiver: BusReceiver) {
+    fn start(
+        &self,
+        _state: Arc<SystemState>,
+        sender: BusSender,
+        receiver: BusReceiver,
+    ) -> ActorThread {
         let initial_mode = self.initial_mode;
+        let device_mode = self.device_mode;
         let device_id = format!("mock-{}", sender.actor_id());
         let thread_name = format!("device:{}", sender.actor_id());
 
         std::thread::Builder::new()
             .name(thread_name)
-            .spawn(move || run(initial_mode, device_id, sender, receiver))
-            .expect("failed to spawn mock launch thread");
+            .spawn(move || run(initial_mode, device_mode, device_id, sender, receiver))
+    }
+
+    fn reconfigure(&self, state: &Arc<SystemState>, sender: &BusSender) -> ReconfigureOutcome {
+        let Some((_, index)) = sender.actor_id().split_once('.') else {
+            return ReconfigureOutcome::Applied;
+        };
+        match state.system.snapshot().mock_monitor.get(index) {
+            Some(section) if section.mode == self.device_mode => ReconfigureOutcome::Applied,
+            // Section removed, or its mode pin or mapping changed
+            _ => ReconfigureOutcome::RestartRequired,
+        }
     }
 }
 
@@ -80,6 +99,7 @@ fn telemetry(
 
 fn run(
     initial_mode: ShotDetectionMode,
+    device_mode: DeviceModeSection,
     device_id: String,
     sender: BusSender,
     mut receiver: BusReceiver,
@@ -120,8 +140,10 @@ fn run(
                 Ok(None) => break,
                 Ok(Some(msg)) => {
                     if let FlighthookEvent::SetDetectionMode {
-                        mode: Some(mode), ..
+                        mode: Some(global_mode),
+                        ..
                     } = msg.event
+                        && let mode = device_mode.effective(global_mode)
                         && std::mem::discriminant(&current_mode) != std::mem::discriminant(&mode)
                     {
                         info!("mock: mode change: {current_mode:?} -> {mode:?}");
@@ -218,6 +240,7 @@ fn run(
                     FlighthookMessage::new(FlighthookEvent::BallFlight {
                         key: key.clone(),
                         ball: Box::new(ball),
+                        estimated: false,
                     })
                     .device(device_id.as_str()),
                 );
@@ -250,7 +273,7 @@ fn run(
             _ => {}
         }
 
-        std::thread::sleep(Duration::from_millis(100));
+        receiver.wait(Duration::from_millis(100));
     }
 }
 
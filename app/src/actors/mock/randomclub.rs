@@ -9,7 +9,7 @@ use std::time::Duration;
 
 use std::collections::HashMap;
 
-use super::super::Actor;
+use super::super::{Actor, ActorThread};
 use crate::bus::{BusReceiver, BusSender, PollError};
 use crate::state::SystemState;
 use flighthook::{ActorStatus, Club, ClubInfo, FlighthookEvent, FlighthookMessage, Handedness};
@@ -20,13 +20,17 @@ const HANDEDNESS: &[Handedness] = &[Handedness::Right, Handedness::Left];
 pub struct RandomClubActor;
 
 impl Actor for RandomClubActor {
-    fn start(&self, _state: Arc<SystemState>, sender: BusSender, receiver: BusReceiver) {
+    fn start(
+        &self,
+        _state: Arc<SystemState>,
+        sender: BusSender,
+        receiver: BusReceiver,
+    ) -> ActorThread {
         let thread_name = format!("mock:{}", sender.actor_id());
 
         std::thread::Builder::new()
             .name(thread_name)
             .spawn(move || run(sender, receiver))
-            .expect("failed to spawn random_club thread");
     }
 }
 
@@ -75,14 +79,12 @@ fn run(sender: BusSender, mut receiver: BusReceiver) {
     }));
 
     loop {
-        match receiver.poll() {
+        match receiver.recv_timeout(Duration::from_secs(1)) {
             Err(PollError::Shutdown) => {
                 tracing::info!("random_club '{name}': shutting down");
                 return;
             }
-            Ok(None) => {
-                std::thread::sleep(Duration::from_millis(100));
-            }
+            Ok(None) => {}
             Ok(Some(msg)) => {
                 // Cycle club/handedness on each completed shot
                 if let FlighthookEvent::ShotFinished { .. } = &msg.event {
@@ -4,19 +4,22 @@ pub mod gspro;
 pub mod mevo;
 pub mod mock;
 pub mod openconnect;
+pub mod plugin;
+pub mod process;
 pub mod r10;
+pub mod script;
 pub mod square;
+pub mod supervisor;
 pub mod system;
+#[cfg(test)]
+mod test_support;
 pub mod web;
 
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use std::sync::atomic::AtomicBool;
 
-use tokio::sync::broadcast;
-
-use crate::bus::{BusReceiver, BusSender};
+use crate::bus::{Bus, BusReceiver, BusSender, ShutdownFlag};
 use crate::state::SystemState;
 use crate::state::config::{FlighthookConfig, global_id};
 use flighthook::Club;
@@ -33,11 +36,21 @@ pub enum ReconfigureOutcome {
     RestartRequired,
 }
 
+/// The thread an actor's `start()` spawned, or the error spawning it.
+pub type ActorThread = std::io::Result<std::thread::JoinHandle<()>>;
+
 /// Common trait for self-managed actors. Each actor struct holds its own config;
 /// `start()` clones what it needs and spawns a thread.
 pub trait Actor: Send + Sync {
-    /// Spawn the actor's run loop.
-    fn start(&self, state: Arc<SystemState>, sender: BusSender, receiver: BusReceiver);
+    /// Spawn the actor's run loop. The returned thread is watched by the
+    /// supervisor: if it ends without the actor being stopped, the actor has
+    /// crashed.
+    fn start(
+        &self,
+        state: Arc<SystemState>,
+        sender: BusSender,
+        receiver: BusReceiver,
+    ) -> ActorThread;
 
     /// Request the actor to stop. Default: no-op (actors check the shutdown
     /// flag via `BusReceiver::is_shutdown()`).
@@ -65,8 +78,9 @@ pub struct ResolvedActor {
 /// Build a flat list of all actors from the persisted config.
 ///
 /// Iterates all config sections (mevo, mock_monitor, gspro, random_club,
-/// webserver) and constructs the appropriate concrete actor for each.
-/// Invalid addresses are logged and skipped.
+/// plugin, script, process, webserver) and constructs the appropriate concrete actor for each.
+/// Invalid addresses are logged and skipped; `disabled` sections are skipped
+/// silently.
 ///
 /// `current_mode` is the active detection mode from game state. Falls back
 /// to `Full` when no mode has been set yet (e.g. first startup).
@@ -81,6 +95,9 @@ pub fn resolve_actors(
 
     // Mevo devices
     for (index, section) in &config.mevo {
+        if section.disabled {
+            continue;
+        }
         let id = global_id("mevo", index);
         let addr_str = section.address.as_deref().unwrap_or("192.168.2.1:5100");
         match addr_str.parse::<SocketAddr>() {
@@ -93,7 +110,8 @@ pub fn resolve_actors(
                     name: section.name.clone(),
                     actor: Box::new(mevo::MevoActor {
                         addr,
-                        initial_mode: mode,
+                        initial_mode: section.mode.effective(mode),
+                        device_mode: section.mode,
                         session_config,
                         use_estimated,
                         camera_mode,
@@ -108,12 +126,15 @@ pub fn resolve_actors(
 
     // R10 devices
     for (index, section) in &config.r10 {
+        if section.disabled {
+            continue;
+        }
         let id = global_id("r10", index);
         actors.push(ResolvedActor {
             id,
             name: section.name.clone(),
             actor: Box::new(r10::R10Actor {
-                initial_mode: mode,
+                initial_mode: section.mode.effective(mode),
                 // The R10 protocol carries tee distance in yards.
                 #[allow(clippy::cast_possible_truncation)]
                 tee_range_yards: section.range.map(|d| d.as_yards() as f32),
@@ -123,6 +144,9 @@ pub fn resolve_actors(
 
     // Square Golf devices
     for (index, section) in &config.square {
+        if section.disabled {
+            continue;
+        }
         let id = global_id("square", index);
         let club = section
             .club
@@ -139,22 +163,33 @@ pub fn resolve_actors(
                 discard_non_putting_zero_spin: section
                     .discard_non_putting_zero_spin
                     .unwrap_or(true),
+                initial_mode: mode,
+                device_mode: section.mode,
             }),
         });
     }
 
     // Mock monitors
     for (index, section) in &config.mock_monitor {
+        if section.disabled {
+            continue;
+        }
         let id = global_id("mock_monitor", index);
         actors.push(ResolvedActor {
             id,
             name: section.name.clone(),
-            actor: Box::new(mock::launch::MockLaunchActor { initial_mode: mode }),
+            actor: Box::new(mock::launch::MockLaunchActor {
+                initial_mode: section.mode.effective(mode),
+                device_mode: section.mode,
+            }),
         });
     }
 
     // OpenConnect servers (inbound launch monitors: Uneekor, Foresight, ...)
     for (index, section) in &config.openconnect_server {
+        if section.disabled {
+            continue;
+        }
         let id = global_id("openconnect_server", index);
         let bind_str = section.bind.as_deref().unwrap_or("0.0.0.0:921");
         match bind_str.parse::<SocketAddr>() {
@@ -173,19 +208,23 @@ pub fn resolve_actors(
 
     // GSPro integrations
     for (index, section) in &config.gspro {
+        if section.disabled {
+            continue;
+        }
         let id = global_id("gspro", index);
         let addr_str = section.address.as_deref().unwrap_or("127.0.0.1:921");
         match addr_str.parse::<SocketAddr>() {
             Ok(addr) => {
-                let routing = gspro::GsProRouting {
-                    full_monitor: section.full_monitor.clone(),
-                    chipping_monitor: section.chipping_monitor.clone(),
-                    putting_monitor: section.putting_monitor.clone(),
-                };
+                let routing = section.effective_routing(&config.routing);
                 actors.push(ResolvedActor {
                     id,
                     name: section.name.clone(),
-                    actor: Box::new(gspro::GsProActor { addr, routing }),
+                    actor: Box::new(gspro::GsProActor {
+                        addr,
+                        routing,
+                        dedup: config.dedup.clone(),
+                        device_modes: config.device_modes(),
+                    }),
                 });
             }
             Err(e) => {
@@ -196,6 +235,9 @@ pub fn resolve_actors(
 
     // Random club integrations
     for (index, section) in &config.random_club {
+        if section.disabled {
+            continue;
+        }
         let id = global_id("random_club", index);
         actors.push(ResolvedActor {
             id,
@@ -204,15 +246,66 @@ pub fn resolve_actors(
         });
     }
 
+    // WebAssembly plugins
+    for (index, section) in &config.plugin {
+        if section.disabled {
+            continue;
+        }
+        let id = global_id("plugin", index);
+        actors.push(ResolvedActor {
+            id,
+            name: section.name.clone(),
+            actor: Box::new(plugin::PluginActor {
+                section: section.clone(),
+            }),
+        });
+    }
+
+    // Rhai scripts
+    for (index, section) in &config.script {
+        if section.disabled {
+            continue;
+        }
+        let id = global_id("script", index);
+        actors.push(ResolvedActor {
+            id,
+            name: section.name.clone(),
+            actor: Box::new(script::ScriptActor {
+                section: section.clone(),
+            }),
+        });
+    }
+
+    // External processes
+    for (index, section) in &config.process {
+        if section.disabled {
+            continue;
+        }
+        let id = global_id("process", index);
+        actors.push(ResolvedActor {
+            id,
+            name: section.name.clone(),
+            actor: Box::new(process::ProcessActor {
+                section: section.clone(),
+            }),
+        });
+    }
+
     // Webservers
     for (index, ws) in &config.webserver {
+        if ws.disabled {
+            continue;
+        }
         let id = global_id("webserver", index);
         match ws.bind.parse::<SocketAddr>() {
             Ok(addr) => {
                 actors.push(ResolvedActor {
                     id,
                     name: ws.name.clone(),
-                    actor: Box::new(web::WebActor::new(addr)),
+                    actor: Box::new(web::WebActor::new(
+                        addr,
+                        web::tls::TlsSettings::from_section(ws),
+                    )),
                 });
             }
             Err(e) => {
@@ -225,17 +318,14 @@ pub fn resolve_actors(
 }
 
 /// Start a resolved actor: create bus wrappers, call start(), register in state.
-pub fn start_actor(
-    id: String,
-    actor: Box<dyn Actor>,
-    state: &Arc<SystemState>,
-    bus_tx: &broadcast::Sender<flighthook::FlighthookMessage>,
-) {
-    let shutdown = Arc::new(AtomicBool::new(false));
-    let sender = BusSender::new(id.clone(), bus_tx.clone(), Arc::clone(&shutdown));
+pub fn start_actor(id: String, actor: Box<dyn Actor>, state: &Arc<SystemState>, bus_tx: &Bus) {
+    let sender = BusSender::new(id.clone(), bus_tx.clone(), Arc::new(ShutdownFlag::new()));
     let receiver = sender.subscribe();
-    actor.start(Arc::clone(state), sender, receiver);
-    state.register_actor(id, actor, shutdown);
+    let thread = actor.start(Arc::clone(state), sender.clone(), receiver);
+    if let Err(e) = &thread {
+        tracing::error!("actor '{id}': failed to spawn thread: {e}");
+    }
+    state.register_actor(id, actor, thread, &sender);
 }
 
 /// Build a map of actor IDs to display names from config (for UI display).
@@ -259,6 +349,15 @@ pub fn actor_names(config: &FlighthookConfig) -> HashMap<String, String> {
     for (index, section) in &config.random_club {
         names.insert(global_id("random_club", index), section.name.clone());
     }
+    for (index, section) in &config.plugin {
+        names.insert(global_id("plugin", index), section.name.clone());
+    }
+    for (index, section) in &config.script {
+        names.insert(global_id("script", index), section.name.clone());
+    }
+    for (index, section) in &config.process {
+        names.insert(global_id("process", index), section.name.clone());
+    }
     for (index, ws) in &config.webserver {
         names.insert(global_id("webserver", index), ws.name.clone());
     }
@@ -29,10 +29,10 @@ use std::collections::HashMap;
 use std::io::{Read, Write};
 use std::net::{SocketAddr, TcpListener, TcpStream};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use super::gspro::api;
-use super::{Actor, ReconfigureOutcome};
+use super::{Actor, ActorThread, ReconfigureOutcome};
 use crate::bus::{BusReceiver, BusSender};
 use crate::state::SystemState;
 use flighthook::{ActorStatus, FlighthookEvent, FlighthookMessage, RawPayload, Severity, ShotKey};
@@ -49,14 +49,18 @@ pub struct OpenConnectServerActor {
 }
 
 impl Actor for OpenConnectServerActor {
-    fn start(&self, _state: Arc<SystemState>, sender: BusSender, receiver: BusReceiver) {
+    fn start(
+        &self,
+        _state: Arc<SystemState>,
+        sender: BusSender,
+        receiver: BusReceiver,
+    ) -> ActorThread {
         let bind = self.bind;
         let thread_name = format!("device:{}", sender.actor_id());
 
         std::thread::Builder::new()
             .name(thread_name)
             .spawn(move || run(bind, sender, receiver))
-            .expect("failed to spawn openconnect thread");
     }
 
     fn reconfigure(&self, state: &Arc<SystemState>, sender: &BusSender) -> ReconfigureOutcome {
@@ -106,7 +110,12 @@ fn run(bind: SocketAddr, sender: BusSender, mut receiver: BusReceiver) {
                     severity: Severity::Error,
                     message: format!("OpenConnect server could not bind {bind}: {e}"),
                 }));
-                std::thread::sleep(backoff);
+                let deadline = Instant::now() + backoff;
+                while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+                    if receiver.recv_timeout(left).is_err() {
+                        break;
+                    }
+                }
                 backoff = (backoff + Duration::from_secs(1)).min(max_backoff);
                 continue;
             }
@@ -142,7 +151,8 @@ fn run(bind: SocketAddr, sender: BusSender, mut receiver: BusReceiver) {
                     }));
                 }
                 Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                    std::thread::sleep(Duration::from_millis(100));
+                    // Nothing to act on while idle, but keep the queue drained.
+                    let _ = receiver.recv_timeout(Duration::from_millis(100));
                 }
                 Err(e) => {
                     tracing::warn!("openconnect server: accept failed: {e}");
@@ -382,6 +392,7 @@ fn emit_shot(msg: &api::GsProMessage, payload: &RawPayload, sender: &BusSender)
         FlighthookMessage::new(FlighthookEvent::BallFlight {
             key: key.clone(),
             ball: Box::new(ball),
+            estimated: false,
         })
         .raw(payload.clone())
         .device(device),
@@ -0,0 +1,168 @@
+//! wasmtime glue: loads a plugin module with its limits and host functions.
+//!
+//! Data crosses the boundary as UTF-8 JSON in the module's linear memory,
+//! passed as a `(ptr, len)` pair:
+//!
+//! - The module exports `memory`, `flighthook_alloc(len) -> ptr` (a buffer
+//!   the host writes into), `flighthook_on_message(ptr, len)` and, optionally,
+//!   `flighthook_init(ptr, len)`.
+//! - The host provides `flighthook.emit(ptr, len) -> i32` and
+//!   `flighthook.log(level, ptr, len)`. Nothing else is imported: no WASI,
+//!   no files, no network.
+
+use std::path::Path;
+
+use anyhow::Context;
+use wasmtime::{
+    Caller, Config, Engine, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder,
+    TypedFunc,
+};
+
+use crate::bus::{self, BusSender, Publisher};
+use flighthook::FlighthookMessage;
+
+/// Fuel for one call into the module, roughly one unit per instruction. A
+/// call that runs out traps instead of hanging the actor.
+const FUEL_PER_CALL: u64 = 100_000_000;
+
+/// Largest the module's linear memory may grow.
+const MAX_MEMORY_BYTES: usize = 64 << 20;
+
+/// Store data: what the host functions need.
+struct HostState {
+    sender: BusSender,
+    limits: StoreLimits,
+}
+
+/// An instantiated plugin module.
+pub struct Plugin {
+    store: Store<HostState>,
+    memory: Memory,
+    alloc: TypedFunc<i32, i32>,
+    on_message: TypedFunc<(i32, i32), ()>,
+    init: Option<TypedFunc<(i32, i32), ()>>,
+}
+
+impl Plugin {
+    /// Compile and instantiate the module at `path` (`.wasm` or `.wat`).
+    /// Messages it emits go out through `sender`.
+    pub fn load(path: &Path, sender: BusSender) -> anyhow::Result<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config)?;
+        let module = Module::from_file(&engine, path)
+            .with_context(|| format!("loading {}", path.display()))?;
+
+        let mut linker = Linker::new(&engine);
+        linker.func_wrap("flighthook", "emit", emit)?;
+        linker.func_wrap("flighthook", "log", log)?;
+
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(MAX_MEMORY_BYTES)
+            .instances(1)
+            .build();
+        let mut store = Store::new(&engine, HostState { sender, limits });
+        store.limiter(|state| &mut state.limits);
+        // The module's start function, if any, runs on this.
+        store.set_fuel(FUEL_PER_CALL)?;
+        let instance = linker.instantiate(&mut store, &module)?;
+
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .context("module does not export `memory`")?;
+        let alloc = instance.get_typed_func(&mut store, "flighthook_alloc")?;
+        let on_message = instance.get_typed_func(&mut store, "flighthook_on_message")?;
+        let init = match instance.get_func(&mut store, "flighthook_init") {
+            Some(func) => Some(func.typed(&store)?),
+            None => None,
+        };
+        Ok(Self {
+            store,
+            memory,
+            alloc,
+            on_message,
+            init,
+        })
+    }
+
+    /// Hand the section's `settings` to `flighthook_init`, if exported.
+    pub fn init(
+        &mut self,
+        settings: &serde_json::Map<String, serde_json::Value>,
+    ) -> anyhow::Result<()> {
+        let Some(init) = self.init.clone() else {
+            return Ok(());
+        };
+        self.call(&init, &serde_json::to_vec(settings)?)
+    }
+
+    /// Deliver one bus message to `flighthook_on_message`.
+    pub fn on_message(&mut self, msg: &FlighthookMessage) -> anyhow::Result<()> {
+        let on_message = self.on_message.clone();
+        self.call(&on_message, &serde_json::to_vec(msg)?)
+    }
+
+    /// Copy `data` into a module buffer and call `func(ptr, len)` on it.
+    fn call(&mut self, func: &TypedFunc<(i32, i32), ()>, data: &[u8]) -> anyhow::Result<()> {
+        self.store.set_fuel(FUEL_PER_CALL)?;
+        let len = i32::try_from(data.len()).context("message too large")?;
+        let ptr = self.alloc.call(&mut self.store, len)?;
+        let offset =
+            usize::try_from(ptr).context("flighthook_alloc returned a negative pointer")?;
+        self.memory.write(&mut self.store, offset, data)?;
+        func.call(&mut self.store, (ptr, len))
+    }
+}
+
+/// Copy `len` bytes at `ptr` out of the calling module's memory.
+fn guest_bytes(caller: &mut Caller<'_, HostState>, ptr: i32, len: i32) -> Option<Vec<u8>> {
+    let memory = caller.get_export("memory")?.into_memory()?;
+    let start = usize::try_from(ptr).ok()?;
+    let end = start.checked_add(usize::try_from(len).ok()?)?;
+    memory.data(&caller).get(start..end).map(<[u8]>::to_vec)
+}
+
+/// `emit(ptr, len) -> i32`: publish a `FlighthookMessage` (JSON envelope) as
+/// this plugin. Returns 0, or -1 when it does not parse or is not allowed.
+fn emit(mut caller: Caller<'_, HostState>, ptr: i32, len: i32) -> i32 {
+    let bytes = guest_bytes(&mut caller, ptr, len);
+    let sender = &caller.data().sender;
+    let Some(bytes) = bytes else {
+        tracing::warn!("plugin '{}': emit out of bounds", sender.actor_id());
+        return -1;
+    };
+    match serde_json::from_slice::<FlighthookMessage>(&bytes) {
+        Ok(msg) if bus::may_publish(Publisher::Plugin, &msg.event) => {
+            sender.send(msg);
+            0
+        }
+        Ok(msg) => {
+            tracing::warn!(
+                "plugin '{}': may not emit {}",
+                sender.actor_id(),
+                msg.event.kind()
+            );
+            -1
+        }
+        Err(e) => {
+            tracing::warn!("plugin '{}': invalid message: {e}", sender.actor_id());
+            -1
+        }
+    }
+}
+
+/// `log(level, ptr, len)`: write a UTF-8 line to the app log. Levels: 0
+/// error, 1 warn, 2 info, anything else debug.
+fn log(mut caller: Caller<'_, HostState>, level: i32, ptr: i32, len: i32) {
+    let Some(bytes) = guest_bytes(&mut caller, ptr, len) else {
+        return;
+    };
+    let text = String::from_utf8_lossy(&bytes);
+    let id = caller.data().sender.actor_id();
+    match level {
+        0 => tracing::error!("plugin '{id}': {text}"),
+        1 => tracing::warn!("plugin '{id}': {text}"),
+        2 => tracing::info!("plugin '{id}': {text}"),
+        _ => tracing::debug!("plugin '{id}': {text}"),
+    }
+}
@@ -0,0 +1,202 @@
+//! WebAssembly plugin actor — custom shot processors and integrations.
+//!
+//! Each `[plugin.<idx>]` section loads one module (see `host` for the ABI)
+//! and feeds it the bus messages of the configured kinds, as JSON. Whatever
+//! the module emits goes on the bus as this actor, so a plugin can filter
+//! shots and re-emit them (and be a GSPro routing target), score them, or
+//! report them somewhere else. A module that fails to load, or traps (e.g.
+//! runs out of fuel), ends the actor; the supervisor restarts it with
+//! backoff.
+
+mod host;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::{Actor, ActorThread, ReconfigureOutcome};
+use crate::bus::{self, BusReceiver, BusSender, PollError};
+use crate::state::SystemState;
+use flighthook::{ActorStatus, FlighthookEvent, FlighthookMessage, PluginSection};
+
+/// Plugin actor. Holds the section it was built from.
+pub struct PluginActor {
+    pub section: PluginSection,
+}
+
+impl Actor for PluginActor {
+    fn start(
+        &self,
+        _state: Arc<SystemState>,
+        sender: BusSender,
+        receiver: BusReceiver,
+    ) -> ActorThread {
+        let section = self.section.clone();
+        let thread_name = format!("plugin:{}", sender.actor_id());
+
+        std::thread::Builder::new()
+            .name(thread_name)
+            .spawn(move || run(section, sender, receiver))
+    }
+
+    fn reconfigure(&self, state: &Arc<SystemState>, sender: &BusSender) -> ReconfigureOutcome {
+        let Some((_, index)) = sender.actor_id().split_once('.') else {
+            return ReconfigureOutcome::Applied;
+        };
+        let snap = state.system.snapshot();
+        let Some(section) = snap.plugin.get(index) else {
+            return ReconfigureOutcome::RestartRequired; // section removed
+        };
+        // The module keeps its own state, so any change means a fresh one.
+        if section.path != self.section.path
+            || section.kinds != self.section.kinds
+            || section.settings != self.section.settings
+        {
+            return ReconfigureOutcome::RestartRequired;
+        }
+        ReconfigureOutcome::Applied
+    }
+}
+
+/// Report why the plugin is not running. The supervisor raises the alert
+/// when the thread ends.
+fn report_error(sender: &BusSender, error: String) {
+    tracing::error!("plugin '{}': {error}", sender.actor_id());
+    sender.send(FlighthookMessage::new(FlighthookEvent::ActorStatus {
+        status: ActorStatus::Disconnected,
+        telemetry: HashMap::from([("error".into(), error)]),
+    }));
+}
+
+fn run(section: PluginSection, sender: BusSender, mut receiver: BusReceiver) {
+    let id = sender.actor_id().to_string();
+    let path = PathBuf::from(&section.path);
+
+    let mut plugin = match host::Plugin::load(&path, sender.clone()) {
+        Ok(plugin) => plugin,
+        Err(e) => return report_error(&sender, format!("{e:#}")),
+    };
+    if let Err(e) = plugin.init(&section.settings) {
+        return report_error(&sender, format!("flighthook_init failed: {e:#}"));
+    }
+    tracing::info!("plugin '{id}': loaded {}", path.display());
+    sender.send(FlighthookMessage::new(FlighthookEvent::ActorStatus {
+        status: ActorStatus::Connected,
+        telemetry: HashMap::from([("module".into(), section.path.clone())]),
+    }));
+
+    loop {
+        match receiver.recv_timeout(Duration::from_secs(1)) {
+            Err(PollError::Shutdown) => {
+                tracing::info!("plugin '{id}': shutting down");
+                return;
+            }
+            Ok(None) => {}
+            Ok(Some(msg)) => {
+                // Never feed a plugin its own output.
+                if msg.actor == id || !bus::subscribed(&section.kinds, &msg.event) {
+                    continue;
+                }
+                if let Err(e) = plugin.on_message(&msg) {
+                    return report_error(&sender, format!("trapped: {e:#}"));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use flighthook::ShotKey;
+
+    use crate::actors::test_support::{TestActor, expect_from, source_file, spawn_actor_for_test};
+
+    /// Writes `wat` to a temp file and runs it as `plugin.0`.
+    fn start(wat: &str, kinds: &[&str]) -> TestActor {
+        let file = source_file(".wat", wat);
+        let section = PluginSection {
+            path: file.path().to_string_lossy().into_owned(),
+            kinds: kinds.iter().map(|k| k.to_string()).collect(),
+            ..PluginSection::default()
+        };
+        spawn_actor_for_test("plugin.0", move |sender, receiver| {
+            run(section, sender, receiver)
+        })
+        .with_source(file)
+    }
+
+    #[test]
+    fn plugin_receives_subscribed_kinds_and_emits_as_itself() {
+        let echo = r#"(module
+            (import "flighthook" "emit" (func $emit (param i32 i32) (result i32)))
+            (memory (export "memory") 1)
+            (func (export "flighthook_alloc") (param i32) (result i32) (i32.const 1024))
+            (func (export "flighthook_on_message") (param $ptr i32) (param $len i32)
+                (drop (call $emit (local.get $ptr) (local.get $len)))))"#;
+        let mut actor = start(echo, &["shot_trigger"]);
+        expect_from(&mut actor.watcher, "plugin.0", |e| {
+            matches!(
+                e,
+                FlighthookEvent::ActorStatus {
+                    status: ActorStatus::Connected,
+                    ..
+                }
+            )
+        });
+
+        let key = ShotKey {
+            shot_id: "abc".into(),
+            shot_number: 7,
+        };
+        let from_monitor = |event| FlighthookMessage::new(event).actor("mock_monitor.0");
+        actor.bus.send(from_monitor(FlighthookEvent::ShotFinished {
+            key: key.clone(),
+        }));
+        actor.bus.send(from_monitor(FlighthookEvent::ShotTrigger {
+            key: key.clone(),
+        }));
+        let echoed = expect_from(&mut actor.watcher, "plugin.0", |e| {
+            matches!(
+                e,
+                FlighthookEvent::ShotTrigger { .. } | FlighthookEvent::ShotFinished { .. }
+            )
+        });
+        assert!(matches!(echoed.event, FlighthookEvent::ShotTrigger { key: k } if k == key));
+
+        actor.shutdown.set();
+        actor.thread.join().unwrap();
+    }
+
+    #[test]
+    fn runaway_plugin_runs_out_of_fuel_and_stops() {
+        let spin = r#"(module
+            (memory (export "memory") 1)
+            (func (export "flighthook_alloc") (param i32) (result i32) (i32.const 0))
+            (func (export "flighthook_on_message") (param i32 i32) (loop $spin (br $spin))))"#;
+        let mut actor = start(spin, &[]);
+        actor
+            .bus
+            .send(FlighthookMessage::new(FlighthookEvent::Alert {
+                severity: flighthook::Severity::Warn,
+                message: "tick".into(),
+            }));
+        actor.thread.join().unwrap();
+
+        let status = expect_from(&mut actor.watcher, "plugin.0", |e| {
+            matches!(
+                e,
+                FlighthookEvent::ActorStatus {
+                    status: ActorStatus::Disconnected,
+                    ..
+                }
+            )
+        });
+        let FlighthookEvent::ActorStatus { telemetry, .. } = status.event else {
+            unreachable!()
+        };
+        assert!(telemetry["error"].contains("fuel"), "{telemetry:?}");
+    }
+}
@@ -0,0 +1,354 @@
+//! External process actor — drivers and integrations in any language.
+//!
+//! Each `[process.<idx>]` section runs one command. Bus messages of the
+//! configured kinds are written to its stdin as NDJSON `FlighthookMessage`s;
+//! each line it prints on stdout is parsed the same way and published as
+//! this actor. Its stderr goes to the log. When the process exits it is
+//! started again with linear backoff, reporting `reconnecting` in between.
+//! See `docs/PROCESSES.md`.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::Arc;
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::time::{Duration, Instant};
+
+use super::{Actor, ActorThread, ReconfigureOutcome};
+use crate::bus::{self, BusReceiver, BusSender, PollError, Publisher};
+use crate::state::SystemState;
+use flighthook::{ActorStatus, FlighthookEvent, FlighthookMessage, ProcessSection, Severity};
+
+/// Longest the actor waits on the bus before checking whether the process
+/// is still running.
+const EXIT_POLL: Duration = Duration::from_millis(100);
+
+/// Messages queued for a process that is slow to read its stdin. Beyond
+/// this they are dropped, so one stuck process cannot stall the actor.
+const STDIN_QUEUE: usize = 256;
+
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(15);
+
+/// Process actor. Holds the section it was built from.
+pub struct ProcessActor {
+    pub section: ProcessSection,
+}
+
+impl Actor for ProcessActor {
+    fn start(
+        &self,
+        _state: Arc<SystemState>,
+        sender: BusSender,
+        receiver: BusReceiver,
+    ) -> ActorThread {
+        let section = self.section.clone();
+        let thread_name = format!("process:{}", sender.actor_id());
+
+        std::thread::Builder::new()
+            .name(thread_name)
+            .spawn(move || run(section, sender, receiver))
+    }
+
+    fn reconfigure(&self, state: &Arc<SystemState>, sender: &BusSender) -> ReconfigureOutcome {
+        let Some((_, index)) = sender.actor_id().split_once('.') else {
+            return ReconfigureOutcome::Applied;
+        };
+        let snap = state.system.snapshot();
+        let Some(section) = snap.process.get(index) else {
+            return ReconfigureOutcome::RestartRequired; // section removed
+        };
+        if section.command != self.section.command
+            || section.args != self.section.args
+            || section.kinds != self.section.kinds
+        {
+            return ReconfigureOutcome::RestartRequired;
+        }
+        ReconfigureOutcome::Applied
+    }
+}
+
+/// How one run of the process ended.
+enum Exit {
+    /// The actor is shutting down; the process has been killed.
+    Shutdown,
+    /// The process could not be started, or exited on its own.
+    Failed(String),
+}
+
+/// Main loop. Starts the process again after each exit until shutdown.
+fn run(section: ProcessSection, sender: BusSender, mut receiver: BusReceiver) {
+    let id = sender.actor_id().to_string();
+    let mut backoff = MIN_BACKOFF;
+    let mut ever_started = false;
+
+    loop {
+        if receiver.is_shutdown() {
+            tracing::info!("process '{id}': shutting down");
+            return;
+        }
+        let status = if ever_started {
+            ActorStatus::Reconnecting
+        } else {
+            ActorStatus::Starting
+        };
+        sender.send(FlighthookMessage::new(FlighthookEvent::ActorStatus {
+            status,
+            telemetry: HashMap::new(),
+        }));
+
+        let started = Instant::now();
+        match run_once(&section, &sender, &mut receiver, &mut ever_started) {
+            Exit::Shutdown => {
+                tracing::info!("process '{id}': shutting down");
+                return;
+            }
+            Exit::Failed(error) => {
+                // A process that stayed up a while is not crash-looping.
+                if started.elapsed() >= MAX_BACKOFF {
+                    backoff = MIN_BACKOFF;
+                }
+                tracing::warn!("process '{id}': {error}, restarting in {backoff:?}");
+                sender.send(FlighthookMessage::new(FlighthookEvent::Alert {
+                    severity: Severity::Error,
+                    message: format!("process '{id}': {error}"),
+                }));
+                let status = if ever_started {
+                    ActorStatus::Reconnecting
+                } else {
+                    ActorStatus::Starting
+                };
+                sender.send(FlighthookMessage::new(FlighthookEvent::ActorStatus {
+                    status,
+                    telemetry: HashMap::from([("error".into(), error)]),
+                }));
+                let deadline = Instant::now() + backoff;
+                while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+                    if receiver.recv_timeout(left).is_err() {
+                        break;
+                    }
+                }
+                backoff = (backoff + Duration::from_secs(1)).min(MAX_BACKOFF);
+            }
+        }
+    }
+}
+
+/// Start the process and pump messages both ways until it exits or the
+/// actor shuts down.
+fn run_once(
+    section: &ProcessSection,
+    sender: &BusSender,
+    receiver: &mut BusReceiver,
+    ever_started: &mut bool,
+) -> Exit {
+    let id = sender.actor_id().to_string();
+    let mut child = match Command::new(&section.command)
+        .args(&section.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => return Exit::Failed(format!("cannot start '{}': {e}", section.command)),
+    };
+    *ever_started = true;
+    tracing::info!(
+        "process '{id}': started '{}' (pid {})",
+        section.command,
+        child.id()
+    );
+
+    let stdin = spawn_writer(&id, child.stdin.take().expect("stdin is piped"));
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let stderr = child.stderr.take().expect("stderr is piped");
+    let out_sender = sender.clone();
+    std::thread::spawn(move || read_stdout(stdout, out_sender));
+    let err_id = id.clone();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+            tracing::info!("process '{err_id}': {line}");
+        }
+    });
+
+    sender.send(FlighthookMessage::new(FlighthookEvent::ActorStatus {
+        status: ActorStatus::Connected,
+        telemetry: HashMap::from([("pid".into(), child.id().to_string())]),
+    }));
+
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return Exit::Failed(format!("exited ({status})")),
+            Ok(None) => {}
+            Err(e) => {
+                kill(&mut child);
+                return Exit::Failed(format!("lost track of the process: {e}"));
+            }
+        }
+        match receiver.recv_timeout(EXIT_POLL) {
+            Err(PollError::Shutdown) => {
+                kill(&mut child);
+                return Exit::Shutdown;
+            }
+            Ok(None) => {}
+            Ok(Some(msg)) => {
+                // Never feed a process its own output.
+                if msg.actor == id || !bus::subscribed(&section.kinds, &msg.event) {
+                    continue;
+                }
+                let mut line = match serde_json::to_string(&msg) {
+                    Ok(line) => line,
+                    Err(e) => {
+                        tracing::warn!("process '{id}': cannot serialize message: {e}");
+                        continue;
+                    }
+                };
+                line.push('\n');
+                match stdin.try_send(line) {
+                    Ok(()) => {}
+                    Err(TrySendError::Full(_)) => {
+                        tracing::warn!("process '{id}': not reading stdin, dropped a message");
+                    }
+                    // The writer ends when the pipe breaks; `try_wait` above
+                    // picks up the exit.
+                    Err(TrySendError::Disconnected(_)) => {}
+                }
+            }
+        }
+    }
+}
+
+/// Write queued lines to the process's stdin on a thread of their own.
+fn spawn_writer(id: &str, mut stdin: ChildStdin) -> SyncSender<String> {
+    let (tx, rx) = mpsc::sync_channel::<String>(STDIN_QUEUE);
+    let id = id.to_string();
+    std::thread::spawn(move || {
+        for line in rx {
+            if let Err(e) = stdin
+                .write_all(line.as_bytes())
+                .and_then(|()| stdin.flush())
+            {
+                tracing::debug!("process '{id}': stdin closed: {e}");
+                return;
+            }
+        }
+    });
+    tx
+}
+
+/// Publish each NDJSON message the process prints, until its stdout closes.
+fn read_stdout(stdout: std::process::ChildStdout, sender: BusSender) {
+    let id = sender.actor_id().to_string();
+    for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let msg = match serde_json::from_str::<FlighthookMessage>(line) {
+            Ok(msg) => msg,
+            Err(e) => {
+                tracing::warn!("process '{id}': ignoring invalid message ({e}): {line}");
+                continue;
+            }
+        };
+        if !bus::may_publish(Publisher::Process, &msg.event) {
+            tracing::warn!("process '{id}': may not emit {}", msg.event.kind());
+            continue;
+        }
+        sender.send(msg);
+    }
+}
+
+fn kill(child: &mut Child) {
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    use flighthook::ShotKey;
+
+    use crate::actors::test_support::{TestActor, expect_from, spawn_actor_for_test};
+
+    /// Runs `command args` as `process.0`.
+    fn start(command: &str, args: &[&str], kinds: &[&str]) -> TestActor {
+        let section = ProcessSection {
+            command: command.into(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            kinds: kinds.iter().map(|k| k.to_string()).collect(),
+            ..ProcessSection::default()
+        };
+        spawn_actor_for_test("process.0", move |sender, receiver| {
+            run(section, sender, receiver)
+        })
+    }
+
+    fn is_status(event: &FlighthookEvent, want: ActorStatus) -> bool {
+        matches!(event, FlighthookEvent::ActorStatus { status, .. } if *status == want)
+    }
+
+    #[test]
+    fn process_gets_subscribed_kinds_and_its_output_goes_on_the_bus() {
+        // `cat` echoes each message back, so it comes out as process.0.
+        let mut actor = start("cat", &[], &["shot_trigger"]);
+        expect_from(&mut actor.watcher, "process.0", |e| {
+            is_status(e, ActorStatus::Connected)
+        });
+
+        let key = ShotKey {
+            shot_id: "abc".into(),
+            shot_number: 3,
+        };
+        let from_monitor = |event| FlighthookMessage::new(event).actor("mock_monitor.0");
+        actor.bus.send(from_monitor(FlighthookEvent::ShotFinished {
+            key: key.clone(),
+        }));
+        actor.bus.send(from_monitor(FlighthookEvent::ShotTrigger {
+            key: key.clone(),
+        }));
+        let echoed = expect_from(&mut actor.watcher, "process.0", |e| {
+            matches!(
+                e,
+                FlighthookEvent::ShotTrigger { .. } | FlighthookEvent::ShotFinished { .. }
+            )
+        });
+        assert!(matches!(echoed.event, FlighthookEvent::ShotTrigger { key: k } if k == key));
+
+        actor.shutdown.set();
+        actor.thread.join().unwrap();
+    }
+
+    #[test]
+    fn exited_process_is_reported_and_restarted() {
+        let alert = r#"{"event":{"kind":"alert","severity":"warn","message":"hello"}}"#;
+        let script = format!("echo '{alert}'; echo 'not json'; exit 3");
+        let mut actor = start("sh", &["-c", &script], &[]);
+
+        expect_from(
+            &mut actor.watcher,
+            "process.0",
+            |e| matches!(e, FlighthookEvent::Alert { message, .. } if message == "hello"),
+        );
+        let status = expect_from(
+            &mut actor.watcher,
+            "process.0",
+            |e| matches!(e, FlighthookEvent::ActorStatus { telemetry, .. } if telemetry.contains_key("error")),
+        );
+        let FlighthookEvent::ActorStatus { status, telemetry } = status.event else {
+            unreachable!()
+        };
+        assert_eq!(status, ActorStatus::Reconnecting);
+        assert!(telemetry["error"].contains("exit"), "{telemetry:?}");
+
+        // Started again after the backoff.
+        expect_from(&mut actor.watcher, "process.0", |e| {
+            is_status(e, ActorStatus::Connected)
+        });
+
+        actor.shutdown.set();
+        actor.thread.join().unwrap();
+    }
+}
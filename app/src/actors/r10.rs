@@ -6,7 +6,7 @@ use tenover::ble::BleTransport;
 use tenover::{Client, Event};
 use tracing::{debug, info, warn};
 
-use super::{Actor, ReconfigureOutcome};
+use super::{Actor, ActorThread, ReconfigureOutcome};
 use crate::bus::{BusReceiver, BusSender, PollError};
 use crate::state::SystemState;
 
@@ -29,7 +29,12 @@ pub struct R10Actor {
 }
 
 impl Actor for R10Actor {
-    fn start(&self, _state: Arc<SystemState>, sender: BusSender, receiver: BusReceiver) {
+    fn start(
+        &self,
+        _state: Arc<SystemState>,
+        sender: BusSender,
+        receiver: BusReceiver,
+    ) -> ActorThread {
         let initial_mode = self.initial_mode;
         let tee_range_yards = self.tee_range_yards;
         let thread_name = format!("device:{}", sender.actor_id());
@@ -39,7 +44,6 @@ impl Actor for R10Actor {
             .spawn(move || {
                 run(initial_mode, tee_range_yards, sender, receiver);
             })
-            .expect("failed to spawn r10 thread");
     }
 
     fn reconfigure(&self, state: &Arc<SystemState>, sender: &BusSender) -> ReconfigureOutcome {
@@ -184,12 +188,11 @@ fn run(
                 info!("{} in {}s", verb.to_lowercase(), backoff.as_secs());
 
                 let deadline = Instant::now() + backoff;
-                while Instant::now() < deadline {
-                    if receiver.poll().is_err() {
+                while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+                    if receiver.recv_timeout(left).is_err() {
                         emit_device_status(&sender, ActorStatus::Disconnected, HashMap::new());
                         return;
                     }
-                    std::thread::sleep(Duration::from_millis(250));
                 }
                 backoff = (backoff + Duration::from_secs(1)).min(MAX_BACKOFF);
             }
@@ -244,7 +247,8 @@ fn connect_and_run(
                 }
                 Ok(None) => break,
                 Ok(Some(msg)) => {
-                    // R10 doesn't support mode switching — just log
+                    // R10 doesn't support mode switching — just log. A `mode`
+                    // pin still applies to how its shots are routed and recorded.
                     if let FlighthookEvent::SetDetectionMode {
                         mode: Some(new_mode),
                         ..
@@ -348,6 +352,7 @@ fn connect_and_run(
                                 FlighthookMessage::new(FlighthookEvent::BallFlight {
                                     key: key.clone(),
                                     ball: Box::new(bf),
+                                    estimated: false,
                                 })
                                 .device(&addr),
                             );
@@ -478,7 +483,7 @@ fn connect_and_run(
             Ok(None) => {
                 idle_count += 1;
                 if idle_count >= 3 {
-                    std::thread::sleep(Duration::from_millis(5));
+                    receiver.wait(Duration::from_millis(5));
                 }
             }
 
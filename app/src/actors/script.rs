@@ -0,0 +1,320 @@
+//! Rhai scripting actor — one-off bay rules without a Rust actor.
+//!
+//! Each `[script.<idx>]` section compiles one script. Its top level runs once
+//! at start, then `on_start()` if the script defines it. After that
+//! `on_message(msg)` is called for each bus message of the configured kinds,
+//! with `msg` being the JSON envelope as a Rhai map. Inside both functions
+//! `this` is a map that persists between calls, and `emit(...)` publishes a
+//! message as this actor. See `docs/SCRIPTING.md`.
+//!
+//! A script that fails to compile ends the actor (the supervisor restarts
+//! it with backoff). An error while handling a message is logged and
+//! reported as `error` telemetry; the script keeps running.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rhai::{AST, CallFnOptions, Dynamic, Engine, EvalAltResult, Map, Scope};
+
+use super::{Actor, ActorThread, ReconfigureOutcome};
+use crate::bus::{self, BusReceiver, BusSender, PollError, Publisher};
+use crate::state::SystemState;
+use flighthook::{ActorStatus, FlighthookEvent, FlighthookMessage, ScriptSection};
+
+/// Operations one call into the script may run before it is aborted, so a
+/// runaway loop cannot hang the actor.
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// Script actor. Holds the section it was built from.
+pub struct ScriptActor {
+    pub section: ScriptSection,
+}
+
+impl Actor for ScriptActor {
+    fn start(
+        &self,
+        _state: Arc<SystemState>,
+        sender: BusSender,
+        receiver: BusReceiver,
+    ) -> ActorThread {
+        let section = self.section.clone();
+        let thread_name = format!("script:{}", sender.actor_id());
+
+        std::thread::Builder::new()
+            .name(thread_name)
+            .spawn(move || run(section, sender, receiver))
+    }
+
+    fn reconfigure(&self, state: &Arc<SystemState>, sender: &BusSender) -> ReconfigureOutcome {
+        let Some((_, index)) = sender.actor_id().split_once('.') else {
+            return ReconfigureOutcome::Applied;
+        };
+        let snap = state.system.snapshot();
+        let Some(section) = snap.script.get(index) else {
+            return ReconfigureOutcome::RestartRequired; // section removed
+        };
+        if section.path != self.section.path || section.kinds != self.section.kinds {
+            return ReconfigureOutcome::RestartRequired;
+        }
+        ReconfigureOutcome::Applied
+    }
+}
+
+/// A compiled script with its persistent state.
+struct Script {
+    engine: Engine,
+    ast: AST,
+    scope: Scope<'static>,
+    /// `this` inside `on_start` / `on_message`.
+    state: Dynamic,
+}
+
+impl Script {
+    /// Compile the script at `path`, run its top level and `on_start()`.
+    fn load(path: &Path, sender: BusSender) -> Result<Self, Box<EvalAltResult>> {
+        let mut engine = Engine::new();
+        engine
+            .set_max_operations(MAX_OPERATIONS)
+            // Debug builds default to a nesting depth that rejects plain
+            // `else if` chains; use the release limits everywhere.
+            .set_max_expr_depths(64, 32)
+            .set_max_string_size(1 << 20)
+            .set_max_array_size(10_000)
+            .set_max_map_size(10_000);
+        let id = sender.actor_id().to_string();
+        let print_id = id.clone();
+        engine.on_print(move |text| tracing::info!("script '{print_id}': {text}"));
+        engine.on_debug(move |text, _, pos| tracing::debug!("script '{id}': {text} ({pos})"));
+        engine.register_fn("emit", move |value: Map| emit(&sender, value));
+
+        let ast = engine.compile_file(path.to_path_buf())?;
+        let defines = |name: &str, params: usize| {
+            ast.iter_functions()
+                .any(|f| f.name == name && f.params.len() == params)
+        };
+        if !defines("on_message", 1) {
+            return Err("script does not define on_message(msg)".into());
+        }
+        let has_on_start = defines("on_start", 0);
+
+        let mut scope = Scope::new();
+        engine.run_ast_with_scope(&mut scope, &ast)?;
+        let mut script = Self {
+            engine,
+            ast,
+            scope,
+            state: Dynamic::from_map(Map::new()),
+        };
+        if has_on_start {
+            script.call("on_start", ())?;
+        }
+        Ok(script)
+    }
+
+    /// Call `on_message` with the message as a map.
+    fn on_message(&mut self, msg: &FlighthookMessage) -> Result<(), Box<EvalAltResult>> {
+        let msg = rhai::serde::to_dynamic(msg)?;
+        self.call("on_message", (msg,))
+    }
+
+    fn call(&mut self, name: &str, args: impl rhai::FuncArgs) -> Result<(), Box<EvalAltResult>> {
+        let options = CallFnOptions::new()
+            .eval_ast(false)
+            .bind_this_ptr(&mut self.state);
+        self.engine
+            .call_fn_with_options::<Dynamic>(options, &mut self.scope, &self.ast, name, args)
+            .map(drop)
+    }
+}
+
+/// `emit(map)`: publish a message as this script. The map is either an
+/// envelope (`#{ device: ..., event: #{ kind: ... } }`, e.g. a modified
+/// `msg`) or a bare event (`#{ kind: "alert", ... }`).
+fn emit(sender: &BusSender, value: Map) -> Result<(), Box<EvalAltResult>> {
+    let is_envelope = value.contains_key("event");
+    // Through JSON rather than straight from the Dynamic, so integer
+    // literals are accepted for float fields.
+    let json: serde_json::Value = rhai::serde::from_dynamic(&Dynamic::from_map(value))?;
+    let msg = if is_envelope {
+        serde_json::from_value::<FlighthookMessage>(json)
+    } else {
+        serde_json::from_value::<FlighthookEvent>(json).map(FlighthookMessage::new)
+    }
+    .map_err(|e| format!("emit: not a valid message: {e}"))?;
+    if !bus::may_publish(Publisher::Script, &msg.event) {
+        return Err(format!("emit: scripts may not emit {}", msg.event.kind()).into());
+    }
+    sender.send(msg);
+    Ok(())
+}
+
+fn status(sender: &BusSender, status: ActorStatus, key: &str, value: String) {
+    sender.send(FlighthookMessage::new(FlighthookEvent::ActorStatus {
+        status,
+        telemetry: HashMap::from([(key.to_string(), value)]),
+    }));
+}
+
+fn run(section: ScriptSection, sender: BusSender, mut receiver: BusReceiver) {
+    let id = sender.actor_id().to_string();
+
+    let mut script = match Script::load(Path::new(&section.path), sender.clone()) {
+        Ok(script) => script,
+        Err(e) => {
+            let error = format!("{}: {e}", section.path);
+            tracing::error!("script '{id}': {error}");
+            // The supervisor raises the alert when the thread ends.
+            return status(&sender, ActorStatus::Disconnected, "error", error);
+        }
+    };
+    tracing::info!("script '{id}': loaded {}", section.path);
+    status(
+        &sender,
+        ActorStatus::Connected,
+        "script",
+        section.path.clone(),
+    );
+
+    loop {
+        match receiver.recv_timeout(Duration::from_secs(1)) {
+            Err(PollError::Shutdown) => {
+                tracing::info!("script '{id}': shutting down");
+                return;
+            }
+            Ok(None) => {}
+            Ok(Some(msg)) => {
+                // Never feed a script its own output.
+                if msg.actor == id || !bus::subscribed(&section.kinds, &msg.event) {
+                    continue;
+                }
+                if let Err(e) = script.on_message(&msg) {
+                    tracing::warn!("script '{id}': on_message: {e}");
+                    status(&sender, ActorStatus::Connected, "error", e.to_string());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use flighthook::{Club, ClubInfo, ShotKey};
+
+    use crate::actors::test_support::{TestActor, expect_from, source_file, spawn_actor_for_test};
+
+    /// Writes `source` to a temp file and runs it as `script.0`.
+    fn start(source: &str) -> TestActor {
+        let file = source_file(".rhai", source);
+        let section = ScriptSection {
+            path: file.path().to_string_lossy().into_owned(),
+            ..ScriptSection::default()
+        };
+        spawn_actor_for_test("script.0", move |sender, receiver| {
+            run(section, sender, receiver)
+        })
+        .with_source(file)
+    }
+
+    #[test]
+    fn script_keeps_state_between_messages_and_emits_events() {
+        let rules = r#"
+            fn on_start() { this.shots = 0; }
+            fn on_message(msg) {
+                let event = msg.event;
+                if event.kind == "club_info" {
+                    this.club = event.club_info.club;
+                    this.shots = 0;
+                } else if event.kind == "shot_finished" && this.club == "7I" {
+                    this.shots += 1;
+                    if this.shots == 3 {
+                        emit(#{ kind: "club_info", club_info: #{ club: "PW" } });
+                    }
+                } else if event.kind == "device_telemetry"
+                    && parse_int(event.telemetry.battery_pct) < 20
+                {
+                    emit(#{ kind: "alert", severity: "warn", message: "battery low" });
+                }
+            }
+        "#;
+        let mut actor = start(rules);
+        let from_monitor = |event| FlighthookMessage::new(event).actor("mock_monitor.0");
+        actor.bus.send(from_monitor(FlighthookEvent::ClubInfo {
+            club_info: ClubInfo { club: Club::Iron7 },
+        }));
+        for shot_number in 1..=3 {
+            let key = ShotKey {
+                shot_id: format!("shot-{shot_number}"),
+                shot_number,
+            };
+            actor
+                .bus
+                .send(from_monitor(FlighthookEvent::ShotFinished { key }));
+        }
+        let switched = expect_from(&mut actor.watcher, "script.0", |e| {
+            matches!(e, FlighthookEvent::ClubInfo { .. })
+        });
+        assert!(matches!(
+            switched.event,
+            FlighthookEvent::ClubInfo { club_info } if club_info.club == Club::PitchingWedge
+        ));
+
+        actor
+            .bus
+            .send(from_monitor(FlighthookEvent::DeviceTelemetry {
+                manufacturer: None,
+                model: None,
+                firmware: None,
+                telemetry: Some(HashMap::from([("battery_pct".into(), "15".into())])),
+            }));
+        expect_from(&mut actor.watcher, "script.0", |e| {
+            matches!(e, FlighthookEvent::Alert { .. })
+        });
+
+        actor.shutdown.set();
+        actor.thread.join().unwrap();
+    }
+
+    #[test]
+    fn script_errors_are_reported_without_stopping_it() {
+        let source = r#"
+            fn on_message(msg) {
+                if msg.event.kind == "alert" { throw "boom"; }
+                emit(msg);
+            }
+        "#;
+        let mut actor = start(source);
+        actor
+            .bus
+            .send(FlighthookMessage::new(FlighthookEvent::Alert {
+                severity: flighthook::Severity::Warn,
+                message: "first".into(),
+            }));
+        let status = expect_from(
+            &mut actor.watcher,
+            "script.0",
+            |e| matches!(e, FlighthookEvent::ActorStatus { telemetry, .. } if telemetry.contains_key("error")),
+        );
+        let FlighthookEvent::ActorStatus { telemetry, .. } = status.event else {
+            unreachable!()
+        };
+        assert!(telemetry["error"].contains("boom"), "{telemetry:?}");
+
+        let key = ShotKey {
+            shot_id: "abc".into(),
+            shot_number: 1,
+        };
+        actor
+            .bus
+            .send(FlighthookMessage::new(FlighthookEvent::ShotTrigger { key }).actor("mevo.0"));
+        expect_from(&mut actor.watcher, "script.0", |e| {
+            matches!(e, FlighthookEvent::ShotTrigger { .. })
+        });
+
+        actor.shutdown.set();
+        actor.thread.join().unwrap();
+    }
+}
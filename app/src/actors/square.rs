@@ -5,13 +5,13 @@ use std::time::{Duration, Instant};
 use allsquare::{Client, Event, SpinMode, ble};
 use tracing::{debug, info, warn};
 
-use super::{Actor, ReconfigureOutcome};
+use super::{Actor, ActorThread, ReconfigureOutcome};
 use crate::bus::{BusReceiver, BusSender, PollError};
 use crate::state::SystemState;
 
 use flighthook::{
-    ActorStatus, BallFlight, Club, ClubData, FlighthookEvent, FlighthookMessage, Severity, ShotKey,
-    Velocity,
+    ActorStatus, BallFlight, Club, ClubData, DeviceModeSection, FlighthookEvent, FlighthookMessage,
+    Severity, ShotDetectionMode, ShotKey, Velocity,
 };
 
 /// Reconnect backoff bounds (linear: +1s per attempt, capped at 15s).
@@ -29,14 +29,24 @@ pub struct SquareActor {
     pub advanced_spin: bool,
     /// Discard shots that read zero spin, unless the putter is selected.
     pub discard_non_putting_zero_spin: bool,
+    /// Global detection mode at startup.
+    pub initial_mode: ShotDetectionMode,
+    /// Section's `mode` pin/mapping, applied through the selected club.
+    pub device_mode: DeviceModeSection,
 }
 
 impl Actor for SquareActor {
-    fn start(&self, _state: Arc<SystemState>, sender: BusSender, receiver: BusReceiver) {
+    fn start(
+        &self,
+        _state: Arc<SystemState>,
+        sender: BusSender,
+        receiver: BusReceiver,
+    ) -> ActorThread {
         let address = self.address.clone();
         let club = self.club;
         let advanced_spin = self.advanced_spin;
         let discard_zero_spin = self.discard_non_putting_zero_spin;
+        let selection = ClubSelection::new(club, self.initial_mode, self.device_mode);
         let thread_name = format!("device:{}", sender.actor_id());
 
         std::thread::Builder::new()
@@ -44,14 +54,13 @@ impl Actor for SquareActor {
             .spawn(move || {
                 run(
                     address,
-                    club,
+                    selection,
                     advanced_spin,
                     discard_zero_spin,
                     sender,
                     receiver,
                 );
             })
-            .expect("failed to spawn square thread");
     }
 
     fn reconfigure(&self, state: &Arc<SystemState>, sender: &BusSender) -> ReconfigureOutcome {
@@ -64,7 +73,7 @@ impl Actor for SquareActor {
         match snap.square.get(index) {
             // Address, club and spin mode are applied at connect time.
             Some(section) => {
-                if section.address == self.address {
+                if section.address == self.address && section.mode == self.device_mode {
                     ReconfigureOutcome::Applied
                 } else {
                     ReconfigureOutcome::RestartRequired
@@ -123,6 +132,56 @@ fn to_allsquare_club(club: Club) -> allsquare::Club {
     }
 }
 
+/// Club the device is armed with.
+///
+/// Square Golf has no mode control: its detection mode *is* the club. A
+/// section following the global mode simply takes the simulator's club. One
+/// with a `mode` pin or mapping holds the putter while its mode is putting,
+/// and otherwise keeps the last full-swing club when the putter is picked.
+#[derive(Debug, Clone, Copy)]
+struct ClubSelection {
+    /// Last club selected in the simulator (initially the configured one).
+    selected: Club,
+    /// Last club other than the putter, for a device kept out of putting.
+    swing: Club,
+    /// Global detection mode.
+    global: ShotDetectionMode,
+    device_mode: DeviceModeSection,
+}
+
+impl ClubSelection {
+    fn new(club: Club, global: ShotDetectionMode, device_mode: DeviceModeSection) -> Self {
+        Self {
+            selected: club,
+            swing: if club == Club::Putter {
+                Club::Iron7
+            } else {
+                club
+            },
+            global,
+            device_mode,
+        }
+    }
+
+    fn select(&mut self, club: Club) {
+        self.selected = club;
+        if club != Club::Putter {
+            self.swing = club;
+        }
+    }
+
+    fn device_club(&self) -> Club {
+        if self.device_mode.is_default() {
+            return self.selected;
+        }
+        match self.device_mode.effective(self.global) {
+            ShotDetectionMode::Putting => Club::Putter,
+            _ if self.selected == Club::Putter => self.swing,
+            _ => self.selected,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Protocol type -> bus type conversion helpers
 // ---------------------------------------------------------------------------
@@ -189,9 +248,11 @@ fn club_from_square(c: &allsquare::ClubMetrics) -> ClubData {
 // Run loop
 // ---------------------------------------------------------------------------
 
+/// `selection` is latched across reconnects so the device comes back with the
+/// right club.
 fn run(
     address: Option<String>,
-    club: Club,
+    mut selection: ClubSelection,
     advanced_spin: bool,
     discard_zero_spin: bool,
     sender: BusSender,
@@ -200,8 +261,6 @@ fn run(
     let mut backoff = MIN_BACKOFF;
     let mut ever_connected = false;
     let mut device_id: Option<String> = None;
-    // Latched across reconnects so the device comes back with the right club.
-    let mut current_club = club;
 
     loop {
         if receiver.poll().is_err() {
@@ -212,7 +271,7 @@ fn run(
 
         match connect_and_run(
             address.as_deref(),
-            &mut current_club,
+            &mut selection,
             advanced_spin,
             discard_zero_spin,
             &sender,
@@ -251,12 +310,11 @@ fn run(
                 info!("{verb} in {}s", backoff.as_secs());
 
                 let deadline = Instant::now() + backoff;
-                while Instant::now() < deadline {
-                    if receiver.poll().is_err() {
+                while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+                    if receiver.recv_timeout(left).is_err() {
                         emit_device_status(&sender, ActorStatus::Disconnected, HashMap::new());
                         return;
                     }
-                    std::thread::sleep(Duration::from_millis(250));
                 }
                 backoff = (backoff + Duration::from_secs(1)).min(MAX_BACKOFF);
             }
@@ -273,7 +331,7 @@ fn run(
 #[allow(clippy::too_many_lines, clippy::too_many_arguments)]
 fn connect_and_run(
     address: Option<&str>,
-    current_club: &mut Club,
+    selection: &mut ClubSelection,
     advanced_spin: bool,
     discard_zero_spin: bool,
     sender: &BusSender,
@@ -316,32 +374,36 @@ fn connect_and_run(
                     return Ok(());
                 }
                 Ok(None) => break,
-                Ok(Some(msg)) => match msg.event {
-                    // Follow the simulator's club selection. Square Golf uses
-                    // it to classify the shot, so keeping it in sync matters.
-                    FlighthookEvent::ClubInfo { club_info } => {
-                        if club_info.club != *current_club {
-                            *current_club = club_info.club;
-                            let mapped = to_allsquare_club(club_info.club);
-                            info!("club -> {mapped}");
-                            if let Err(e) = client.select_club(mapped) {
-                                warn!("club select failed: {e}");
-                            }
+                Ok(Some(msg)) => {
+                    let before = selection.device_club();
+                    match msg.event {
+                        // Follow the simulator's club selection. Square Golf
+                        // uses it to classify the shot, so keeping it in sync
+                        // matters.
+                        FlighthookEvent::ClubInfo { club_info } => {
+                            selection.select(club_info.club);
                         }
+                        // There is no device-side shot mode: putting and
+                        // chipping differ only by which club is selected, so a
+                        // mode change matters only to a pinned or mapped section.
+                        FlighthookEvent::SetDetectionMode {
+                            mode: Some(new_mode),
+                            ..
+                        } => {
+                            debug!("mode -> {new_mode:?}");
+                            selection.global = new_mode;
+                        }
+                        _ => continue,
                     }
-                    // There is no device-side shot mode: putting and chipping
-                    // differ only by which club is selected.
-                    FlighthookEvent::SetDetectionMode {
-                        mode: Some(new_mode),
-                        ..
-                    } => {
-                        debug!(
-                            "mode change to {new_mode:?} ignored \
-                             — Square Golf has no mode control, select a club"
-                        );
+                    let club = selection.device_club();
+                    if club != before {
+                        let mapped = to_allsquare_club(club);
+                        info!("club -> {mapped}");
+                        if let Err(e) = client.select_club(mapped) {
+                            warn!("club select failed: {e}");
+                        }
                     }
-                    _ => {}
-                },
+                }
             }
         }
 
@@ -400,8 +462,9 @@ fn connect_and_run(
                         );
 
                         // Arm once — the device stays armed across shots.
-                        client.arm(to_allsquare_club(*current_club), spin)?;
-                        info!("armed with {}", to_allsquare_club(*current_club));
+                        let club = to_allsquare_club(selection.device_club());
+                        client.arm(club, spin)?;
+                        info!("armed with {club}");
                     }
 
                     Event::StateChanged(state) => {
@@ -448,7 +511,7 @@ fn connect_and_run(
                         // Drop before the counter advances, so a discarded shot
                         // leaves no gap in the numbering and no half-shot on the
                         // bus — nothing downstream ever learns it happened.
-                        if is_zero_spin_misread(&ball, *current_club, discard_zero_spin) {
+                        if is_zero_spin_misread(&ball, selection.device_club(), discard_zero_spin) {
                             let mph = Velocity::MetersPerSecond(ball.speed).as_mph();
                             discarded_counter += 1;
                             warn!("discarded shot: {mph:.1}mph with zero spin (misread)");
@@ -512,6 +575,7 @@ fn connect_and_run(
                             FlighthookMessage::new(FlighthookEvent::BallFlight {
                                 key: key.clone(),
                                 ball: Box::new(bf),
+                                estimated: false,
                             })
                             .device(&name),
                         );
@@ -568,7 +632,7 @@ fn connect_and_run(
             Ok(None) => {
                 idle_count += 1;
                 if idle_count >= 3 {
-                    std::thread::sleep(Duration::from_millis(5));
+                    receiver.wait(Duration::from_millis(5));
                 }
             }
 
@@ -699,4 +763,30 @@ mod tests {
             false
         ));
     }
+
+    #[test]
+    fn club_follows_the_sim_unless_pinned() {
+        let mut follow =
+            ClubSelection::new(Club::Iron7, ShotDetectionMode::Full, Default::default());
+        follow.select(Club::Putter);
+        assert_eq!(follow.device_club(), Club::Putter);
+
+        let putting = DeviceModeSection {
+            pinned: Some(ShotDetectionMode::Putting),
+            ..DeviceModeSection::default()
+        };
+        let mut green = ClubSelection::new(Club::Iron7, ShotDetectionMode::Full, putting);
+        assert_eq!(green.device_club(), Club::Putter);
+        green.select(Club::Driver);
+        assert_eq!(green.device_club(), Club::Putter);
+
+        let full = DeviceModeSection {
+            pinned: Some(ShotDetectionMode::Full),
+            ..DeviceModeSection::default()
+        };
+        let mut mat = ClubSelection::new(Club::Wood3, ShotDetectionMode::Full, full);
+        mat.select(Club::Putter);
+        mat.global = ShotDetectionMode::Putting;
+        assert_eq!(mat.device_club(), Club::Wood3);
+    }
 }
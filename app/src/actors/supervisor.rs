@@ -0,0 +1,151 @@
+//! Actor supervision — notices actors whose thread died and restarts them.
+//!
+//! Every actor runs on the thread its `Actor::start` spawned. A panic there
+//! (in the actor or in a device crate) ends the thread but leaves the actor
+//! registered, still showing the last status it reported. The supervisor
+//! polls the registry (`SystemState::reap_actors()`), marks dead actors
+//! `Disconnected` with the reason, raises an `Alert`, and restarts them with
+//! exponential backoff per the `[supervisor]` config policy.
+//!
+//! Restarts are sent to the system actor as `RestartActor` commands rather
+//! than done here, so they are serialized with config changes. The
+//! supervisor runs on its own thread, so a dead system actor is still
+//! reported, though it cannot be restarted.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::bus::Bus;
+use crate::state::{ActorExit, SystemState};
+use flighthook::{ActorStatus, ConfigAction, FlighthookEvent, FlighthookMessage, Severity};
+
+/// How often the registry is checked for dead actors.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A run at least this long resets an actor's consecutive-crash count.
+const STABLE_AFTER: Duration = Duration::from_secs(60);
+
+/// Actor ID on the restart commands the supervisor sends.
+const SUPERVISOR_ACTOR: &str = "supervisor";
+
+/// Consecutive crashes of one actor, and when it is due for a restart.
+#[derive(Default)]
+struct Crashes {
+    count: u32,
+    restart_at: Option<Instant>,
+}
+
+/// Spawn the supervisor thread. It runs until `shutdown` is set.
+pub fn spawn(
+    state: Arc<SystemState>,
+    bus_tx: Bus,
+    shutdown: Arc<AtomicBool>,
+) -> std::io::Result<JoinHandle<()>> {
+    std::thread::Builder::new()
+        .name("supervisor".into())
+        .spawn(move || run(state, bus_tx, shutdown))
+}
+
+fn run(state: Arc<SystemState>, bus_tx: Bus, shutdown: Arc<AtomicBool>) {
+    let mut crashes: HashMap<String, Crashes> = HashMap::new();
+    while !shutdown.load(Ordering::Relaxed) {
+        for exit in state.reap_actors() {
+            on_exit(&state, &bus_tx, &mut crashes, exit);
+        }
+
+        let now = Instant::now();
+        for (id, c) in crashes.iter_mut() {
+            if c.restart_at.is_none_or(|t| t > now) {
+                continue;
+            }
+            c.restart_at = None;
+            // Restarted or removed by a config change in the meantime.
+            if !state.is_dead(id) {
+                continue;
+            }
+            tracing::info!("supervisor: restarting '{id}' (crash {})", c.count);
+            bus_tx.send(
+                FlighthookMessage::new(FlighthookEvent::ConfigCommand {
+                    request_id: None,
+                    action: Box::new(ConfigAction::RestartActor { id: id.clone() }),
+                })
+                .actor(SUPERVISOR_ACTOR),
+            );
+        }
+
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Report a dead actor and schedule its restart, if the policy allows one.
+fn on_exit(
+    state: &SystemState,
+    bus_tx: &Bus,
+    crashes: &mut HashMap<String, Crashes>,
+    exit: ActorExit,
+) {
+    let id = exit.id;
+    if id == "system" {
+        // Owns the game state writer, so it cannot be recreated.
+        tracing::error!(
+            "supervisor: system actor {}; config changes and game state updates have stopped",
+            exit.reason
+        );
+        alert(
+            bus_tx,
+            &id,
+            Severity::Critical,
+            format!("System actor {}. Restart flighthook.", exit.reason),
+        );
+        return;
+    }
+
+    // A crashed actor never reported its own failure, so record it. One that
+    // returned on its own has already said why (e.g. a webserver that could
+    // not bind); keep that error.
+    let mut telemetry = HashMap::new();
+    if exit.crashed {
+        telemetry.insert("error".to_string(), exit.reason.clone());
+    }
+    bus_tx.send(
+        FlighthookMessage::new(FlighthookEvent::ActorStatus {
+            status: ActorStatus::Disconnected,
+            telemetry,
+        })
+        .actor(id.as_str()),
+    );
+
+    let c = crashes.entry(id.clone()).or_default();
+    if exit.uptime >= STABLE_AFTER {
+        c.count = 0;
+    }
+    c.count += 1;
+
+    let policy = state.system.snapshot().supervisor;
+    let message = match policy.restart_delay(c.count) {
+        Some(delay) => {
+            c.restart_at = Some(Instant::now() + delay);
+            format!(
+                "Actor '{id}' {}; restarting in {:.1}s",
+                exit.reason,
+                delay.as_secs_f64()
+            )
+        }
+        None => {
+            c.restart_at = None;
+            format!(
+                "Actor '{id}' {}; not restarting ([supervisor] policy, {} consecutive crash(es))",
+                exit.reason, c.count
+            )
+        }
+    };
+    tracing::error!("supervisor: {message}");
+    alert(bus_tx, &id, Severity::Error, message);
+}
+
+fn alert(bus_tx: &Bus, actor: &str, severity: Severity, message: String) {
+    bus_tx.send(FlighthookMessage::new(FlighthookEvent::Alert { severity, message }).actor(actor));
+}
@@ -6,25 +6,39 @@
 //! Also processes `ConfigCommand` events for config mutations (from the
 //! REST API). This runs independently of the web server, so `SystemState`
 //! is always consistent even in headless mode.
+//!
+//! Edits to the config file made outside flighthook are picked up here too:
+//! the file is polled and a changed, valid config is replayed on the bus as a
+//! `ReplaceAll` command, so it takes the same path as a save from the UI.
 
 use std::collections::HashMap;
-use std::sync::atomic::AtomicBool;
 use std::sync::mpsc as std_mpsc;
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
-
-use tokio::sync::broadcast;
+use std::time::{Duration, Instant};
 
-use crate::actors::{Actor, ReconfigureOutcome, ResolvedActor, resolve_actors, start_actor};
-use crate::bus::{BusReceiver, BusSender, PollError};
+use crate::actors::{
+    Actor, ActorThread, ReconfigureOutcome, ResolvedActor, resolve_actors, start_actor,
+};
+use crate::bus::{Bus, BusReceiver, BusSender, PollError, ShutdownFlag};
+use crate::state::config::FileChange;
 use crate::state::{GameStateWriter, SystemState};
-use flighthook::{FlighthookEvent, FlighthookMessage};
+use flighthook::{
+    ConfigAction, FlighthookEvent, FlighthookMessage, PostSettingsResponse, Severity,
+};
+
+/// How often the config file is checked for outside edits.
+const CONFIG_FILE_POLL: Duration = Duration::from_secs(1);
+
+/// Actor ID on commands replayed from an edited config file. Shows up as the
+/// `source` of the resulting config history entry.
+const CONFIG_FILE_ACTOR: &str = "config_file";
 
 // ---------------------------------------------------------------------------
 // Config reload
 // ---------------------------------------------------------------------------
 
 /// Result of applying a config reload.
+#[derive(Default)]
 pub(crate) struct ConfigReloadOutcome {
     pub restarted: Vec<String>,
     pub stopped: Vec<String>,
@@ -41,12 +55,14 @@ pub(crate) struct ConfigReloadOutcome {
 /// Other actors are left untouched (no stop/start/reconfigure).
 pub(crate) fn apply_config_reload(
     state: &Arc<SystemState>,
-    bus_tx: &broadcast::Sender<FlighthookMessage>,
+    bus_tx: &Bus,
     scope: Option<&str>,
 ) -> ConfigReloadOutcome {
     let snap = state.system.snapshot();
     let current_mode = state.game.snapshot().mode;
-    let resolved = resolve_actors(&snap, current_mode);
+    let mut resolved = resolve_actors(&snap, current_mode);
+    // Actors stopped via the lifecycle API stay stopped.
+    resolved.retain(|ra| !state.is_held(&ra.id));
 
     // Pre-build into a map so we can pull actors out for restart/new cases
     let mut resolved_map: HashMap<String, ResolvedActor> =
@@ -64,7 +80,7 @@ pub(crate) fn apply_config_reload(
     if let Some(target) = scope {
         // Scoped reload: only reconfigure the target actor
         if current_ids.contains(&target.to_string()) && expected_ids.contains(&target.to_string()) {
-            let shutdown = Arc::new(AtomicBool::new(false));
+            let shutdown = Arc::new(ShutdownFlag::new());
             let sender = BusSender::new(target.to_string(), bus_tx.clone(), shutdown);
             let reconf = state.reconfigure_actor(target, state, &sender);
 
@@ -120,7 +136,7 @@ pub(crate) fn apply_config_reload(
                 continue;
             }
 
-            let shutdown = Arc::new(AtomicBool::new(false));
+            let shutdown = Arc::new(ShutdownFlag::new());
             let sender = BusSender::new(id.clone(), bus_tx.clone(), shutdown);
             let reconf = state.reconfigure_actor(id, state, &sender);
 
@@ -155,6 +171,43 @@ pub(crate) fn apply_config_reload(
     result
 }
 
+/// Stop and recreate one actor from its current config section, whatever
+/// `reconfigure()` would say. Starts it if it was not running.
+fn restart_actor(state: &Arc<SystemState>, bus_tx: &Bus, id: &str) -> ConfigReloadOutcome {
+    let mut result = ConfigReloadOutcome {
+        restarted: Vec::new(),
+        stopped: Vec::new(),
+        started: Vec::new(),
+    };
+    state.release_actor(id);
+
+    let was_running = state.actor_ids().iter().any(|a| a == id);
+    if was_running {
+        tracing::info!("lifecycle: restarting '{id}'");
+        state.stop_actor(id);
+        state.remove_actor(id);
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+
+    let snap = state.system.snapshot();
+    let current_mode = state.game.snapshot().mode;
+    if let Some(ra) = resolve_actors(&snap, current_mode)
+        .into_iter()
+        .find(|ra| ra.id == id)
+    {
+        start_actor(ra.id, ra.actor, state, bus_tx);
+        if was_running {
+            result.restarted.push(id.to_string());
+        } else {
+            result.started.push(id.to_string());
+        }
+    } else if was_running {
+        // Section vanished or became invalid/disabled meanwhile.
+        result.stopped.push(id.to_string());
+    }
+    result
+}
+
 // ---------------------------------------------------------------------------
 // System actor
 // ---------------------------------------------------------------------------
@@ -167,7 +220,7 @@ pub(crate) fn apply_config_reload(
 pub struct SystemActor {
     writer: Mutex<Option<GameStateWriter>>,
     state: Mutex<Option<Arc<SystemState>>>,
-    bus_tx: Mutex<Option<broadcast::Sender<FlighthookMessage>>>,
+    bus_tx: Mutex<Option<Bus>>,
     ready_tx: Mutex<Option<std_mpsc::SyncSender<()>>>,
 }
 
@@ -175,7 +228,7 @@ impl SystemActor {
     pub fn new(
         writer: GameStateWriter,
         state: Arc<SystemState>,
-        bus_tx: broadcast::Sender<FlighthookMessage>,
+        bus_tx: Bus,
     ) -> (Self, std_mpsc::Receiver<()>) {
         let (ready_tx, ready_rx) = std_mpsc::sync_channel(0);
         let actor = Self {
@@ -189,7 +242,12 @@ impl SystemActor {
 }
 
 impl Actor for SystemActor {
-    fn start(&self, _state: Arc<SystemState>, sender: BusSender, receiver: BusReceiver) {
+    fn start(
+        &self,
+        _state: Arc<SystemState>,
+        sender: BusSender,
+        receiver: BusReceiver,
+    ) -> ActorThread {
         let writer = self
             .writer
             .lock()
@@ -218,14 +276,13 @@ impl Actor for SystemActor {
         std::thread::Builder::new()
             .name("system".into())
             .spawn(move || run(writer, sys_state, bus_tx, sender, receiver, ready_tx))
-            .expect("failed to spawn system thread");
     }
 }
 
 fn run(
     writer: GameStateWriter,
     state: Arc<SystemState>,
-    bus_tx: broadcast::Sender<FlighthookMessage>,
+    bus_tx: Bus,
     sender: BusSender,
     mut receiver: BusReceiver,
     ready_tx: std_mpsc::SyncSender<()>,
@@ -234,12 +291,16 @@ fn run(
     let _ = ready_tx.send(());
     drop(ready_tx);
 
+    let mut last_file_check = Instant::now();
     loop {
-        match receiver.poll() {
+        if last_file_check.elapsed() >= CONFIG_FILE_POLL {
+            last_file_check = Instant::now();
+            check_config_file(&state, &bus_tx, &sender);
+        }
+        let until_file_check = CONFIG_FILE_POLL.saturating_sub(last_file_check.elapsed());
+        match receiver.recv_timeout(until_file_check) {
             Err(PollError::Shutdown) => return,
-            Ok(None) => {
-                std::thread::sleep(Duration::from_millis(50));
-            }
+            Ok(None) => {}
             Ok(Some(msg)) => match &msg.event {
                 FlighthookEvent::PlayerInfo { player_info } => {
                     writer.set_player_info(player_info.clone());
@@ -263,7 +324,7 @@ fn run(
                     }
                 }
                 FlighthookEvent::ConfigCommand { .. } => {
-                    handle_config_command(&msg.event, &state, &bus_tx, &sender);
+                    handle_config_command(&msg, &state, &bus_tx, &sender);
                 }
                 _ => {}
             },
@@ -271,127 +332,133 @@ fn run(
     }
 }
 
+/// Reload the config file if it was edited outside flighthook (by hand or by
+/// config management). An edit that does not parse, or that introduces
+/// validation errors, raises an `Alert` and leaves the running config alone.
+fn check_config_file(state: &Arc<SystemState>, bus_tx: &Bus, sender: &BusSender) {
+    let config = match state.system.poll_file() {
+        None => return,
+        Some(FileChange::Invalid(e)) => {
+            tracing::error!("config file not reloaded: {e}");
+            sender.send(FlighthookMessage::new(FlighthookEvent::Alert {
+                severity: Severity::Error,
+                message: format!("Config file not reloaded: {e}"),
+            }));
+            return;
+        }
+        Some(FileChange::Changed(config)) => config,
+    };
+
+    let current = state.system.snapshot();
+    if *config == current {
+        return;
+    }
+    let validation = config.validate_from(&current);
+    if !validation.is_valid() {
+        let errors: Vec<String> = validation
+            .errors
+            .iter()
+            .map(|e| {
+                format!(
+                    "{}: {}",
+                    e.section.as_deref().unwrap_or("config"),
+                    e.message
+                )
+            })
+            .collect();
+        tracing::error!("config file not reloaded: {}", errors.join("; "));
+        sender.send(FlighthookMessage::new(FlighthookEvent::Alert {
+            severity: Severity::Error,
+            message: format!("Config file not reloaded: {}", errors.join("; ")),
+        }));
+        return;
+    }
+    for w in &validation.warnings {
+        let section = w.section.as_deref().unwrap_or("config");
+        tracing::warn!("config file: {section}: {}", w.message);
+    }
+
+    tracing::info!("config file changed on disk, reloading");
+    bus_tx.send(
+        FlighthookMessage::new(FlighthookEvent::ConfigCommand {
+            request_id: None,
+            action: Box::new(ConfigAction::ReplaceAll { config }),
+        })
+        .actor(CONFIG_FILE_ACTOR),
+    );
+}
+
 fn handle_config_command(
-    event: &FlighthookEvent,
+    msg: &FlighthookMessage,
     state: &Arc<SystemState>,
-    bus_tx: &broadcast::Sender<FlighthookMessage>,
+    bus_tx: &Bus,
     sender: &BusSender,
 ) {
-    use flighthook::ConfigAction;
-
     let FlighthookEvent::ConfigCommand {
         ref request_id,
         ref action,
-    } = *event
+    } = msg.event
     else {
         return;
     };
 
+    // Kept for the change history, recorded once the outcome is known.
+    let previous = state.system.snapshot();
+
     // Determine scope for actor reconciliation
     let scope: Option<String>;
 
     match action.as_ref() {
-        ConfigAction::ReplaceAll { config } => {
-            state.system.replace((**config).clone());
-            scope = None;
-        }
-        ConfigAction::UpsertWebserver { index, section } => {
-            let idx = index.clone();
-            state.system.update(|p| {
-                p.webserver.insert(idx, section.clone());
-            });
-            scope = Some(format!("webserver.{index}"));
-        }
-        ConfigAction::UpsertMevo { index, section } => {
-            let idx = index.clone();
-            state.system.update(|p| {
-                p.mevo.insert(idx, section.clone());
-            });
-            scope = Some(format!("mevo.{index}"));
-        }
-        ConfigAction::UpsertR10 { index, section } => {
-            let idx = index.clone();
-            state.system.update(|p| {
-                p.r10.insert(idx, section.clone());
-            });
-            scope = Some(format!("r10.{index}"));
+        ConfigAction::RestartActor { id } => {
+            let result = restart_actor(state, bus_tx, id);
+            send_outcome(sender, request_id, result);
+            return;
         }
-        ConfigAction::UpsertSquare { index, section } => {
-            let idx = index.clone();
-            state.system.update(|p| {
-                p.square.insert(idx, section.clone());
-            });
-            scope = Some(format!("square.{index}"));
+        ConfigAction::StopActor { id } => {
+            tracing::info!("lifecycle: stopping '{id}'");
+            state.hold_actor(id);
+            scope = Some(id.clone());
         }
-        ConfigAction::UpsertOpenConnectServer { index, section } => {
-            let idx = index.clone();
-            state.system.update(|p| {
-                p.openconnect_server.insert(idx, section.clone());
-            });
-            scope = Some(format!("openconnect_server.{index}"));
+        ConfigAction::StartActor { id } => {
+            tracing::info!("lifecycle: starting '{id}'");
+            state.release_actor(id);
+            scope = Some(id.clone());
         }
-        ConfigAction::UpsertGsPro { index, section } => {
-            let idx = index.clone();
-            state.system.update(|p| {
-                p.gspro.insert(idx, section.clone());
-            });
-            scope = Some(format!("gspro.{index}"));
-        }
-        ConfigAction::UpsertMockMonitor { index, section } => {
-            let idx = index.clone();
-            state.system.update(|p| {
-                p.mock_monitor.insert(idx, section.clone());
-            });
-            scope = Some(format!("mock_monitor.{index}"));
-        }
-        ConfigAction::UpsertRandomClub { index, section } => {
-            let idx = index.clone();
-            state.system.update(|p| {
-                p.random_club.insert(idx, section.clone());
-            });
-            scope = Some(format!("random_club.{index}"));
-        }
-        ConfigAction::Remove { id } => {
-            if let Some((prefix, index)) = id.split_once('.') {
-                let idx = index.to_string();
-                state.system.update(|p| match prefix {
-                    "mevo" => {
-                        p.mevo.remove(&idx);
-                    }
-                    "r10" => {
-                        p.r10.remove(&idx);
-                    }
-                    "square" => {
-                        p.square.remove(&idx);
-                    }
-                    "openconnect_server" => {
-                        p.openconnect_server.remove(&idx);
-                    }
-                    "gspro" => {
-                        p.gspro.remove(&idx);
-                    }
-                    "mock_monitor" => {
-                        p.mock_monitor.remove(&idx);
-                    }
-                    "random_club" => {
-                        p.random_club.remove(&idx);
-                    }
-                    "webserver" => {
-                        p.webserver.remove(&idx);
-                    }
-                    _ => tracing::warn!("config remove: unknown prefix '{prefix}'"),
-                });
-                scope = Some(id.clone());
-            } else {
-                tracing::warn!("config remove: invalid id '{id}'");
+        // Replace, upserts and removes.
+        action => {
+            let mut applied = true;
+            state.system.update(|p| applied = action.apply(p));
+            if !applied {
+                tracing::warn!(
+                    "config: ignoring {} with unknown id or profile",
+                    action.kind()
+                );
+                send_outcome(sender, request_id, ConfigReloadOutcome::default());
                 return;
             }
+            scope = action.section_id();
         }
     }
 
     // Reconcile actors (webserver included — its reconfigure() handles bind changes)
     let result = apply_config_reload(state, bus_tx, scope.as_deref());
+    state.system.record_change(
+        previous,
+        &msg.actor,
+        request_id.clone(),
+        action.kind(),
+        scope,
+        PostSettingsResponse {
+            restarted: result.restarted.clone(),
+            stopped: result.stopped.clone(),
+            started: result.started.clone(),
+            warnings: Vec::new(),
+        },
+    );
+    send_outcome(sender, request_id, result);
+}
 
+fn send_outcome(sender: &BusSender, request_id: &Option<String>, result: ConfigReloadOutcome) {
     sender.send(FlighthookMessage::new(FlighthookEvent::ConfigOutcome {
         request_id: request_id.clone(),
         restarted: result.restarted,
@@ -0,0 +1,81 @@
+//! Test scaffolding shared by the plugin, script and process actor tests.
+
+use std::io::Write;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use tempfile::NamedTempFile;
+
+use crate::bus::{Bus, BusReceiver, BusSender, ShutdownFlag};
+use flighthook::{FlighthookEvent, FlighthookMessage};
+
+/// An actor thread running on its own bus.
+pub struct TestActor {
+    pub bus: Bus,
+    /// Subscribed before the actor started, so it sees everything it sends.
+    pub watcher: BusReceiver,
+    pub shutdown: Arc<ShutdownFlag>,
+    pub thread: JoinHandle<()>,
+    /// File the actor was started from, removed when this is dropped.
+    _source: Option<NamedTempFile>,
+}
+
+impl TestActor {
+    /// Keeps `file` around for as long as the actor.
+    pub fn with_source(self, file: NamedTempFile) -> Self {
+        TestActor {
+            _source: Some(file),
+            ..self
+        }
+    }
+}
+
+/// Runs `run` as `actor_id` on a fresh bus.
+pub fn spawn_actor_for_test(
+    actor_id: &str,
+    run: impl FnOnce(BusSender, BusReceiver) + Send + 'static,
+) -> TestActor {
+    let bus = Bus::new(64);
+    let watcher = bus.subscribe();
+    let shutdown = Arc::new(ShutdownFlag::new());
+    let sender = BusSender::new(actor_id.into(), bus.clone(), Arc::clone(&shutdown));
+    let receiver = sender.subscribe();
+    let thread = std::thread::spawn(move || run(sender, receiver));
+    TestActor {
+        bus,
+        watcher,
+        shutdown,
+        thread,
+        _source: None,
+    }
+}
+
+/// Writes `contents` to a temp file ending in `suffix`.
+pub fn source_file(suffix: &str, contents: &str) -> NamedTempFile {
+    let mut file = tempfile::Builder::new()
+        .prefix("flighthook-")
+        .suffix(suffix)
+        .tempfile()
+        .unwrap();
+    file.write_all(contents.as_bytes()).unwrap();
+    file
+}
+
+/// Wait for a message from `actor_id` matching `pred`.
+pub fn expect_from(
+    rx: &mut BusReceiver,
+    actor_id: &str,
+    pred: impl Fn(&FlighthookEvent) -> bool,
+) -> FlighthookMessage {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+        if let Ok(Some(msg)) = rx.recv_timeout(Duration::from_millis(100))
+            && msg.actor == actor_id
+            && pred(&msg.event)
+        {
+            return msg;
+        }
+    }
+    panic!("no matching message from {actor_id}");
+}
@@ -0,0 +1,157 @@
+//! API token authentication.
+//!
+//! Tokens are configured per webserver section (`[[webserver.0.tokens]]`).
+//! With none configured the API stays open. Otherwise REST requests present a
+//! token as `Authorization: Bearer <token>` or `?token=<token>`, and the
+//! `/frp` WebSocket presents it in the `start` message (browsers cannot set
+//! headers on a WebSocket upgrade), falling back to the upgrade request.
+
+use std::sync::Arc;
+
+use axum::extract::{Query, Request, State};
+use axum::http::{HeaderMap, Method, StatusCode, Uri, header};
+use axum::middleware::Next;
+use axum::response::Response;
+use serde::Deserialize;
+
+use super::WebState;
+use flighthook::TokenScope;
+
+/// Scope a request needs, or `None` for routes anyone may use.
+///
+/// UI assets are public so the dashboard can load and then authenticate, and
+/// the WebSocket upgrade authenticates on its `start` message instead.
+fn required_scope(method: &Method, path: &str) -> Option<TokenScope> {
+    if *method == Method::OPTIONS {
+        return None;
+    }
+    match path {
+        "/" | "/flighthook-ui.js" | "/flighthook-ui_bg.wasm" => None,
+        p if p == flighthook::FRP_PATH => None,
+        p if p.starts_with("/api/settings") => Some(TokenScope::Admin),
+        // Switching profiles is day-to-day operation; editing them is not.
+        p if p.starts_with("/api/profiles/")
+            && (*method == Method::PUT || *method == Method::DELETE) =>
+        {
+            Some(TokenScope::Admin)
+        }
+        // Stateless conversion — nothing changes server-side.
+        "/api/shots/convert" => Some(TokenScope::Read),
+        _ if *method == Method::GET || *method == Method::HEAD => Some(TokenScope::Read),
+        _ => Some(TokenScope::Control),
+    }
+}
+
+/// Token presented on an HTTP request: bearer header first, then `?token=`.
+pub fn request_token(headers: &HeaderMap, uri: &Uri) -> Option<String> {
+    #[derive(Deserialize)]
+    struct TokenQuery {
+        token: Option<String>,
+    }
+
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|t| t.trim().to_string());
+    bearer.or_else(|| {
+        Query::<TokenQuery>::try_from_uri(uri)
+            .ok()
+            .and_then(|q| q.0.token)
+    })
+}
+
+/// Check a presented token against `required`.
+///
+/// `401 Unauthorized` for a missing or unknown token, `403 Forbidden` for a
+/// valid token with too narrow a scope.
+pub fn authorize(
+    state: &WebState,
+    presented: Option<&str>,
+    required: TokenScope,
+) -> Result<TokenScope, StatusCode> {
+    match state.granted_scope(presented) {
+        None => Err(StatusCode::UNAUTHORIZED),
+        Some(scope) if scope < required => Err(StatusCode::FORBIDDEN),
+        Some(scope) => Ok(scope),
+    }
+}
+
+/// Middleware enforcing [`required_scope`] on every route.
+pub async fn require_token(
+    State(state): State<Arc<WebState>>,
+    req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    if let Some(required) = required_scope(req.method(), req.uri().path()) {
+        let presented = request_token(req.headers(), req.uri());
+        if let Err(status) = authorize(&state, presented.as_deref(), required) {
+            tracing::debug!(
+                "web: {} {} rejected ({status})",
+                req.method(),
+                req.uri().path()
+            );
+            return Err(status);
+        }
+    }
+    Ok(next.run(req).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn route_scopes() {
+        assert_eq!(required_scope(&Method::GET, "/"), None);
+        assert_eq!(required_scope(&Method::GET, "/frp"), None);
+        assert_eq!(required_scope(&Method::OPTIONS, "/api/settings"), None);
+        assert_eq!(
+            required_scope(&Method::GET, "/api/status"),
+            Some(TokenScope::Read)
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/shots/convert"),
+            Some(TokenScope::Read)
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/mode"),
+            Some(TokenScope::Control)
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/api/settings"),
+            Some(TokenScope::Admin)
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/settings"),
+            Some(TokenScope::Admin)
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/api/settings/history"),
+            Some(TokenScope::Admin)
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/profiles/bay%201/activate"),
+            Some(TokenScope::Control)
+        );
+        assert_eq!(
+            required_scope(&Method::DELETE, "/api/profiles/bay%201"),
+            Some(TokenScope::Admin)
+        );
+    }
+
+    #[test]
+    fn token_from_header_or_query() {
+        let mut headers = HeaderMap::new();
+        let uri: Uri = "/api/status?token=fromquery".parse().unwrap();
+        assert_eq!(request_token(&headers, &uri).as_deref(), Some("fromquery"));
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer fromheader"),
+        );
+        assert_eq!(request_token(&headers, &uri).as_deref(), Some("fromheader"));
+        let bare: Uri = "/api/status".parse().unwrap();
+        assert_eq!(request_token(&HeaderMap::new(), &bare), None);
+    }
+}
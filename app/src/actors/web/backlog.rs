@@ -0,0 +1,224 @@
+//! Sequenced WebSocket feed and shot lifecycle backlog.
+//!
+//! The web state updater stamps every bus message with a monotonic sequence
+//! number before fanning it out to WebSocket clients. Shot lifecycle events
+//! are also kept in a bounded backlog so a reconnecting client can catch up
+//! (`resume_from` / `replay_shots` on the `start` message).
+
+use std::collections::{HashSet, VecDeque};
+
+use flighthook::{FlighthookEvent, FlighthookMessage, ShotKey};
+use serde::Serialize;
+
+/// Max shot lifecycle events retained for replay (~250 shots at 4 events each).
+pub const MAX_BACKLOG_EVENTS: usize = 1000;
+
+/// A bus message with its web-layer sequence number.
+#[derive(Debug, Clone)]
+pub struct SequencedMessage {
+    pub seq: u64,
+    pub msg: FlighthookMessage,
+}
+
+/// Outgoing WebSocket frame: the FRP envelope plus a top-level `seq` extension.
+#[derive(Serialize)]
+pub struct SequencedFrame<'a> {
+    pub seq: u64,
+    #[serde(flatten)]
+    pub msg: &'a FlighthookMessage,
+}
+
+impl SequencedMessage {
+    pub fn frame(&self) -> SequencedFrame<'_> {
+        SequencedFrame {
+            seq: self.seq,
+            msg: &self.msg,
+        }
+    }
+}
+
+/// Shot key of a shot lifecycle event, `None` for every other event.
+fn shot_key(event: &FlighthookEvent) -> Option<&ShotKey> {
+    match event {
+        FlighthookEvent::ShotTrigger { key }
+        | FlighthookEvent::BallFlight { key, .. }
+        | FlighthookEvent::ClubPath { key, .. }
+        | FlighthookEvent::FaceImpact { key, .. }
+        | FlighthookEvent::ShotFinished { key } => Some(key),
+        _ => None,
+    }
+}
+
+/// True for `shot_trigger` .. `shot_finished`.
+pub fn is_shot_lifecycle(event: &FlighthookEvent) -> bool {
+    shot_key(event).is_some()
+}
+
+/// Bounded FIFO of shot lifecycle events, in sequence order.
+pub struct ShotBacklog {
+    events: VecDeque<SequencedMessage>,
+    capacity: usize,
+    /// Highest sequence number evicted from the backlog (0 = nothing evicted).
+    evicted_through: u64,
+}
+
+impl ShotBacklog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            events: VecDeque::with_capacity(capacity),
+            capacity,
+            evicted_through: 0,
+        }
+    }
+
+    /// Record `msg` if it is a shot lifecycle event.
+    pub fn push(&mut self, seq: u64, msg: &FlighthookMessage) {
+        if shot_key(&msg.event).is_none() {
+            return;
+        }
+        if self.events.len() >= self.capacity
+            && let Some(old) = self.events.pop_front()
+        {
+            self.evicted_through = old.seq;
+        }
+        self.events.push_back(SequencedMessage {
+            seq,
+            msg: msg.clone(),
+        });
+    }
+
+    /// Events with a sequence number greater than `seq`.
+    pub fn since(&self, seq: u64) -> Vec<SequencedMessage> {
+        self.events
+            .iter()
+            .filter(|e| e.seq > seq)
+            .cloned()
+            .collect()
+    }
+
+    /// All buffered events of the last `n` shots (by trigger order).
+    /// Shots whose trigger has already been evicted are skipped.
+    pub fn last_shots(&self, n: usize) -> Vec<SequencedMessage> {
+        let shots: HashSet<(&str, &ShotKey)> = self
+            .events
+            .iter()
+            .rev()
+            .filter_map(|e| match &e.msg.event {
+                FlighthookEvent::ShotTrigger { key } => Some((e.msg.actor.as_str(), key)),
+                _ => None,
+            })
+            .take(n)
+            .collect();
+        self.events
+            .iter()
+            .filter(|e| {
+                shot_key(&e.msg.event).is_some_and(|k| shots.contains(&(e.msg.actor.as_str(), k)))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// True if events after `seq` have already been evicted, i.e. a client
+    /// resuming from `seq` will have a gap.
+    pub fn truncated_after(&self, seq: u64) -> bool {
+        seq < self.evicted_through
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(n: u32) -> ShotKey {
+        ShotKey {
+            shot_id: format!("shot-{n}"),
+            shot_number: n,
+        }
+    }
+
+    fn msg(actor: &str, event: FlighthookEvent) -> FlighthookMessage {
+        FlighthookMessage::new(event).actor(actor)
+    }
+
+    fn shot(backlog: &mut ShotBacklog, seq: &mut u64, actor: &str, n: u32) {
+        for event in [
+            FlighthookEvent::ShotTrigger { key: key(n) },
+            FlighthookEvent::ShotFinished { key: key(n) },
+        ] {
+            *seq += 1;
+            backlog.push(*seq, &msg(actor, event));
+        }
+    }
+
+    #[test]
+    fn only_shot_lifecycle_is_buffered() {
+        let mut backlog = ShotBacklog::new(10);
+        backlog.push(
+            1,
+            &msg(
+                "system",
+                FlighthookEvent::SetDetectionMode {
+                    mode: None,
+                    handed: None,
+                },
+            ),
+        );
+        backlog.push(
+            2,
+            &msg("mevo.0", FlighthookEvent::ShotTrigger { key: key(1) }),
+        );
+        let events = backlog.since(0);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].seq, 2);
+    }
+
+    #[test]
+    fn since_returns_newer_events() {
+        let mut backlog = ShotBacklog::new(10);
+        let mut seq = 0;
+        shot(&mut backlog, &mut seq, "mevo.0", 1);
+        shot(&mut backlog, &mut seq, "mevo.0", 2);
+        let seqs: Vec<u64> = backlog.since(2).iter().map(|e| e.seq).collect();
+        assert_eq!(seqs, vec![3, 4]);
+        assert!(backlog.since(4).is_empty());
+    }
+
+    #[test]
+    fn last_shots_groups_by_actor_and_key() {
+        let mut backlog = ShotBacklog::new(20);
+        let mut seq = 0;
+        shot(&mut backlog, &mut seq, "mevo.0", 1);
+        shot(&mut backlog, &mut seq, "mevo.0", 2);
+        shot(&mut backlog, &mut seq, "r10.0", 1);
+        let events = backlog.last_shots(2);
+        let seqs: Vec<u64> = events.iter().map(|e| e.seq).collect();
+        assert_eq!(seqs, vec![3, 4, 5, 6]);
+        assert!(backlog.last_shots(0).is_empty());
+    }
+
+    #[test]
+    fn eviction_reports_truncation() {
+        let mut backlog = ShotBacklog::new(2);
+        let mut seq = 0;
+        shot(&mut backlog, &mut seq, "mevo.0", 1);
+        assert!(!backlog.truncated_after(0));
+        shot(&mut backlog, &mut seq, "mevo.0", 2);
+        assert!(backlog.truncated_after(0));
+        assert!(backlog.truncated_after(1));
+        assert!(!backlog.truncated_after(2));
+        let seqs: Vec<u64> = backlog.since(0).iter().map(|e| e.seq).collect();
+        assert_eq!(seqs, vec![3, 4]);
+    }
+
+    #[test]
+    fn frame_carries_seq_next_to_envelope() {
+        let sequenced = SequencedMessage {
+            seq: 42,
+            msg: msg("mevo.0", FlighthookEvent::ShotTrigger { key: key(1) }),
+        };
+        let json = serde_json::to_value(sequenced.frame()).unwrap();
+        assert_eq!(json["seq"], 42);
+        assert_eq!(json["actor"], "mevo.0");
+        assert_eq!(json["event"]["kind"], "shot_trigger");
+    }
+}
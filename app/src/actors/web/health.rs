@@ -0,0 +1,159 @@
+//! Readiness and per-actor liveness for `GET /api/health`.
+//!
+//! Liveness comes from the actor registry: each actor's heartbeat (beaten by
+//! its `BusSender::send()` and `BusReceiver::poll()`) and whether its thread
+//! is still running. Status comes from the web layer's per-actor cache.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use flighthook::{ActorHealth, ActorStatus, ActorStatusResponse, HealthResponse, HealthSection};
+
+use crate::state::ActorLiveness;
+
+/// Build the health report from registry liveness, cached actor statuses,
+/// and the `[health]` policy.
+pub fn report(
+    liveness: &HashMap<String, ActorLiveness>,
+    statuses: &HashMap<String, ActorStatusResponse>,
+    policy: &HealthSection,
+) -> HealthResponse {
+    let stall_after = policy.stall_after();
+    let is_critical = |id: &str| id == "system" || policy.critical.iter().any(|c| c == id);
+
+    let mut actors: HashMap<String, ActorHealth> = liveness
+        .iter()
+        .map(|(id, l)| {
+            let health = ActorHealth {
+                status: statuses.get(id).map(|s| s.status),
+                running: !l.dead,
+                idle_ms: Some(millis(l.idle)),
+                uptime_ms: Some(millis(l.uptime)),
+                stalled: !l.dead && l.idle >= stall_after,
+                critical: is_critical(id),
+            };
+            (id.clone(), health)
+        })
+        .collect();
+    // Critical actors that are not registered (stopped, removed, never
+    // started) are reported as not running.
+    for id in policy.critical.iter().map(String::as_str).chain(["system"]) {
+        actors.entry(id.to_string()).or_insert_with(|| ActorHealth {
+            status: statuses.get(id).map(|s| s.status),
+            running: false,
+            idle_ms: None,
+            uptime_ms: None,
+            stalled: false,
+            critical: true,
+        });
+    }
+
+    let mut critical: Vec<(&String, &ActorHealth)> =
+        actors.iter().filter(|(_, a)| a.critical).collect();
+    critical.sort_by_key(|(id, _)| *id);
+    let problems: Vec<String> = critical
+        .into_iter()
+        .filter_map(|(id, a)| problem(id, a))
+        .collect();
+
+    HealthResponse {
+        ready: problems.is_empty(),
+        problems,
+        actors,
+    }
+}
+
+/// What keeps a critical actor from being healthy, if anything.
+fn problem(id: &str, actor: &ActorHealth) -> Option<String> {
+    if !actor.running {
+        return Some(format!("'{id}' is not running"));
+    }
+    if actor.stalled {
+        let idle = actor.idle_ms.unwrap_or_default() as f64 / 1000.0;
+        return Some(format!("'{id}' stalled (no bus activity for {idle:.1}s)"));
+    }
+    match actor.status {
+        None | Some(ActorStatus::Connected) => None,
+        Some(status) => Some(format!("'{id}' is {status}")),
+    }
+}
+
+fn millis(d: Duration) -> u64 {
+    u64::try_from(d.as_millis()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn live(idle_ms: u64, dead: bool) -> ActorLiveness {
+        ActorLiveness {
+            idle: Duration::from_millis(idle_ms),
+            uptime: Duration::from_secs(60),
+            dead,
+        }
+    }
+
+    fn status(status: ActorStatus) -> ActorStatusResponse {
+        ActorStatusResponse {
+            name: String::new(),
+            status,
+            telemetry: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn ready_needs_only_system_by_default() {
+        let liveness = HashMap::from([
+            ("system".into(), live(5, false)),
+            ("gspro.0".into(), live(30_000, false)),
+        ]);
+        let statuses = HashMap::from([("gspro.0".into(), status(ActorStatus::Reconnecting))]);
+        let health = report(&liveness, &statuses, &HealthSection::default());
+        assert!(health.ready);
+        assert!(health.actors["gspro.0"].stalled);
+        assert!(!health.actors["gspro.0"].critical);
+        assert!(health.actors["system"].critical);
+    }
+
+    #[test]
+    fn critical_actors_must_be_running_live_and_connected() {
+        let policy = HealthSection {
+            critical: vec![
+                "mevo.0".into(),
+                "gspro.0".into(),
+                "r10.0".into(),
+                "square.0".into(),
+            ],
+            stall_after_ms: Some(1000),
+        };
+        let liveness = HashMap::from([
+            ("system".into(), live(5, false)),
+            ("mevo.0".into(), live(5, false)),
+            ("gspro.0".into(), live(5, false)),
+            ("r10.0".into(), live(5, true)),
+            ("square.0".into(), live(2500, false)),
+        ]);
+        let statuses = HashMap::from([
+            ("mevo.0".into(), status(ActorStatus::Connected)),
+            ("gspro.0".into(), status(ActorStatus::Reconnecting)),
+        ]);
+        let health = report(&liveness, &statuses, &policy);
+        assert!(!health.ready);
+        assert_eq!(
+            health.problems,
+            [
+                "'gspro.0' is reconnecting",
+                "'r10.0' is not running",
+                "'square.0' stalled (no bus activity for 2.5s)",
+            ]
+        );
+
+        // A critical actor that is not registered at all (stopped, removed).
+        let mut liveness = liveness;
+        liveness.remove("mevo.0");
+        let health = report(&liveness, &statuses, &policy);
+        assert!(health.problems.contains(&"'mevo.0' is not running".into()));
+        assert_eq!(health.actors["mevo.0"].idle_ms, None);
+    }
+}
@@ -1,29 +1,41 @@
 //! Axum web server — REST endpoints + WebSocket event streaming.
 
+pub mod auth;
+pub mod backlog;
+pub mod health;
 pub mod routes;
+pub mod schema;
+pub mod shots;
+pub mod tls;
 pub mod types;
 pub mod ws;
 
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::net::SocketAddr;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use axum::Router;
-use axum::routing::{get, post};
+use axum::routing::{get, post, put};
 use tokio::sync::{RwLock, broadcast};
 use tower_http::cors::CorsLayer;
 
-use crate::actors::{Actor, ReconfigureOutcome, actor_names};
-use crate::bus::{BusReceiver, BusSender};
+use self::backlog::{MAX_BACKLOG_EVENTS, SequencedMessage, ShotBacklog};
+use self::tls::TlsSettings;
+use crate::actors::{Actor, ActorThread, ReconfigureOutcome, actor_names};
+use crate::bus::{Bus, BusReceiver, BusSender, Heartbeat, LagCounts};
 use crate::state::SystemState;
 use flighthook::{
-    ActorStatus, ActorStatusResponse, FlighthookEvent, FlighthookMessage, ShotAccumulator,
-    ShotData, ShotKey,
+    ActorStatus, ActorStatusResponse, FlighthookEvent, FlighthookMessage, Severity,
+    ShotAccumulator, ShotKey, ShotRecord, TokenScope,
 };
 
 const MAX_SHOTS: usize = 1000;
 
+/// How often the registry's bus lag counters are checked for changes.
+const LAG_REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
 fn new_actor(name: String) -> ActorStatusResponse {
     ActorStatusResponse {
         name,
@@ -35,25 +47,49 @@ fn new_actor(name: String) -> ActorStatusResponse {
 /// Shared state for the web layer.
 pub struct WebState {
     pub root: Arc<SystemState>,
-    pub bus_tx: broadcast::Sender<FlighthookMessage>,
+    pub bus_tx: Bus,
     pub actors: RwLock<HashMap<String, ActorStatusResponse>>,
-    pub shots: RwLock<VecDeque<ShotData>>,
+    /// Recorded shots, oldest first (at most `MAX_SHOTS`).
+    pub shots: RwLock<VecDeque<ShotRecord>>,
     /// Last `ActorStatus` message per actor — replayed to new WS clients.
     pub cached_actor_status: RwLock<HashMap<String, FlighthookMessage>>,
     /// Last `DeviceTelemetry` message per actor — replayed to new WS clients.
     pub cached_device_telemetry: RwLock<HashMap<String, FlighthookMessage>>,
+    /// Sequenced fan-out of every bus message to WS clients.
+    pub feed: broadcast::Sender<SequencedMessage>,
+    /// Last sequence number assigned by the state updater.
+    pub seq: AtomicU64,
+    /// Recent shot lifecycle events — replayed to resuming WS clients.
+    pub shot_backlog: RwLock<ShotBacklog>,
+    /// Shots injected via `POST /api/shots` — numbers them.
+    pub manual_shots: AtomicU32,
     pub addr: SocketAddr,
     pub actor_id: String,
+    /// This actor's registry heartbeat. The server sends on the raw bus, so
+    /// emitting status beats it; the state updater's receiver beats it too.
+    pub heartbeat: Arc<Heartbeat>,
     pub ws_count: AtomicU64,
+    /// Actor IDs held by the connected WebSocket sessions.
+    pub ws_actors: Mutex<HashSet<String>>,
     pub request_count: AtomicU64,
 }
 
+impl WebState {
+    /// Scope this server's config section grants to a presented API token.
+    /// `None` if the token is missing/unknown or the section is gone.
+    pub fn granted_scope(&self, presented: Option<&str>) -> Option<TokenScope> {
+        let index = self.actor_id.strip_prefix("webserver.").unwrap_or("0");
+        self.root
+            .system
+            .snapshot()
+            .webserver
+            .get(index)?
+            .granted_scope(presented)
+    }
+}
+
 /// Emit current telemetry as an ActorStatus event on the bus.
-fn emit_status(
-    status: ActorStatus,
-    state: &WebState,
-    bus_tx: &broadcast::Sender<FlighthookMessage>,
-) {
+async fn emit_status(status: ActorStatus, error: Option<&str>, state: &WebState, bus_tx: &Bus) {
     let mut telemetry = HashMap::from([
         ("bind".into(), state.addr.to_string()),
         (
@@ -65,18 +101,21 @@ fn emit_status(
             state.request_count.load(Ordering::Relaxed).to_string(),
         ),
     ]);
-    if status == ActorStatus::Disconnected {
-        telemetry.insert("error".into(), "bind failed".into());
+    if let Some(error) = error {
+        telemetry.insert("error".into(), error.into());
     }
-    let _ = bus_tx.send(
-        FlighthookMessage::new(FlighthookEvent::ActorStatus { status, telemetry })
-            .actor(&state.actor_id),
-    );
+    state.heartbeat.beat();
+    bus_tx
+        .send_async(
+            FlighthookMessage::new(FlighthookEvent::ActorStatus { status, telemetry })
+                .actor(&state.actor_id),
+        )
+        .await;
 }
 
 /// Emit Connected telemetry (convenience for periodic emitter + ws handlers).
-pub(super) fn emit_telemetry(state: &WebState, bus_tx: &broadcast::Sender<FlighthookMessage>) {
-    emit_status(ActorStatus::Connected, state, bus_tx);
+pub(super) async fn emit_telemetry(state: &WebState, bus_tx: &Bus) {
+    emit_status(ActorStatus::Connected, None, state, bus_tx).await;
 }
 
 // ---------------------------------------------------------------------------
@@ -87,35 +126,39 @@ pub(super) fn emit_telemetry(state: &WebState, bus_tx: &broadcast::Sender<Flight
 /// to run the axum server and state_updater task.
 pub struct WebActor {
     addr: SocketAddr,
+    tls: Option<TlsSettings>,
     shutdown_tx: Mutex<Option<tokio::sync::oneshot::Sender<()>>>,
 }
 
 impl WebActor {
-    pub fn new(addr: SocketAddr) -> Self {
+    pub fn new(addr: SocketAddr, tls: Option<TlsSettings>) -> Self {
         Self {
             addr,
+            tls,
             shutdown_tx: Mutex::new(None),
         }
     }
 }
 
 impl Actor for WebActor {
-    fn start(&self, state: Arc<SystemState>, sender: BusSender, _receiver: BusReceiver) {
+    fn start(
+        &self,
+        state: Arc<SystemState>,
+        sender: BusSender,
+        receiver: BusReceiver,
+    ) -> ActorThread {
         let addr = self.addr;
-        let actor_id = sender.actor_id().to_string();
-        let bus_tx = sender.raw_sender().clone();
+        let tls = self.tls.clone();
         let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
         *self.shutdown_tx.lock().unwrap_or_else(|e| e.into_inner()) = Some(shutdown_tx);
 
-        let thread_name = actor_id.clone();
         std::thread::Builder::new()
-            .name(thread_name)
+            .name(sender.actor_id().to_string())
             .spawn(move || {
                 let rt = tokio::runtime::Runtime::new()
                     .expect("failed to create webserver tokio runtime");
-                rt.block_on(run(addr, actor_id, state, bus_tx, shutdown_rx));
+                rt.block_on(run(addr, tls, state, sender, receiver, shutdown_rx));
             })
-            .expect("failed to spawn webserver thread");
     }
 
     fn stop(&self) {
@@ -132,10 +175,15 @@ impl Actor for WebActor {
     fn reconfigure(&self, state: &Arc<SystemState>, sender: &BusSender) -> ReconfigureOutcome {
         let snap = state.system.snapshot();
         let index = sender.actor_id().strip_prefix("webserver.").unwrap_or("0");
-        let new_bind = snap.webserver.get(index).map(|w| w.bind.as_str());
-        match new_bind.and_then(|b| b.parse::<SocketAddr>().ok()) {
-            Some(new_addr) if new_addr == self.addr => ReconfigureOutcome::Applied,
-            _ => ReconfigureOutcome::RestartRequired,
+        let Some(section) = snap.webserver.get(index) else {
+            return ReconfigureOutcome::RestartRequired;
+        };
+        // Tokens are read live per request; bind and TLS need a new listener.
+        let same_bind = section.bind.parse::<SocketAddr>().ok() == Some(self.addr);
+        if same_bind && TlsSettings::from_section(section) == self.tls {
+            ReconfigureOutcome::Applied
+        } else {
+            ReconfigureOutcome::RestartRequired
         }
     }
 }
@@ -147,9 +195,10 @@ impl Actor for WebActor {
 /// Run the web server. Blocks until shutdown signal or bus close.
 async fn run(
     addr: SocketAddr,
-    actor_id: String,
+    tls: Option<TlsSettings>,
     root: Arc<SystemState>,
-    bus_tx: broadcast::Sender<FlighthookMessage>,
+    sender: BusSender,
+    bus_rx: BusReceiver,
     shutdown_rx: tokio::sync::oneshot::Receiver<()>,
 ) {
     // Pre-populate per-actor state from config
@@ -157,9 +206,14 @@ async fn run(
     let names = actor_names(&snap);
     let mut actors = HashMap::new();
     for (id, name) in &names {
-        actors.insert(id.clone(), new_actor(name.clone()));
+        if snap.section_disabled(id) != Some(true) {
+            actors.insert(id.clone(), new_actor(name.clone()));
+        }
     }
 
+    // The server sends on the raw bus: messages are attributed per client
+    // or on behalf of other actors.
+    let bus_tx = sender.bus().clone();
     let state = Arc::new(WebState {
         root,
         bus_tx: bus_tx.clone(),
@@ -167,15 +221,20 @@ async fn run(
         shots: RwLock::new(VecDeque::with_capacity(MAX_SHOTS)),
         cached_actor_status: RwLock::new(HashMap::new()),
         cached_device_telemetry: RwLock::new(HashMap::new()),
+        feed: broadcast::channel(1024).0,
+        seq: AtomicU64::new(0),
+        shot_backlog: RwLock::new(ShotBacklog::new(MAX_BACKLOG_EVENTS)),
+        manual_shots: AtomicU32::new(0),
         addr,
-        actor_id,
+        actor_id: sender.actor_id().to_string(),
+        heartbeat: Arc::clone(sender.heartbeat()),
         ws_count: AtomicU64::new(0),
+        ws_actors: Mutex::new(HashSet::new()),
         request_count: AtomicU64::new(0),
     });
 
     // Background task: subscribe to bus and update web state
     let updater_state = Arc::clone(&state);
-    let bus_rx = bus_tx.subscribe();
     tokio::spawn(state_updater(updater_state, bus_rx));
 
     // Periodic telemetry emitter (every 5s)
@@ -185,7 +244,7 @@ async fn run(
         let mut interval = tokio::time::interval(std::time::Duration::from_secs(5));
         loop {
             interval.tick().await;
-            emit_telemetry(&telemetry_state, &telemetry_bus);
+            emit_telemetry(&telemetry_state, &telemetry_bus).await;
         }
     });
 
@@ -204,18 +263,68 @@ async fn run(
         .route("/flighthook-ui.js", get(routes::get_ui_js))
         .route("/flighthook-ui_bg.wasm", get(routes::get_ui_wasm))
         .route("/api/status", get(routes::get_status))
-        .route("/api/shots", get(routes::get_shots))
+        .route("/api/health", get(routes::get_health))
+        .route("/api/shots", get(routes::get_shots).post(routes::post_shot))
         .route("/api/shots/convert", post(routes::post_convert_shot))
         .route("/api/mode", post(routes::post_mode))
+        .route("/api/actors/{id}/restart", post(routes::post_actor_restart))
+        .route("/api/actors/{id}/stop", post(routes::post_actor_stop))
+        .route("/api/actors/{id}/start", post(routes::post_actor_start))
         .route(
             "/api/settings",
             get(routes::get_settings).post(routes::post_settings),
         )
+        .route(
+            "/api/settings/validate",
+            post(routes::post_settings_validate),
+        )
+        .route("/api/settings/history", get(routes::get_settings_history))
+        .route(
+            "/api/settings/history/{id}/rollback",
+            post(routes::post_settings_rollback),
+        )
+        .route("/api/profiles", get(routes::get_profiles))
+        .route(
+            "/api/profiles/{name}",
+            put(routes::put_profile).delete(routes::delete_profile),
+        )
+        .route(
+            "/api/profiles/{name}/activate",
+            post(routes::post_profile_activate),
+        )
+        .route("/api/schema", get(schema::get_openapi))
+        .route("/api/schema/message", get(schema::get_message_schema))
         .route(flighthook::FRP_PATH, get(ws::ws_upgrade))
+        .layer(axum::middleware::from_fn_with_state(
+            Arc::clone(&state),
+            auth::require_token,
+        ))
         .layer(count_middleware)
         .layer(CorsLayer::permissive())
         .with_state(Arc::clone(&state));
 
+    // Load (or generate) the certificate before binding, so a bad TLS
+    // config is reported instead of serving plain HTTP.
+    let tls_config = match tls.as_ref().map(|t| tls::server_config(t, addr)) {
+        None => None,
+        Some(Ok(config)) => Some(config),
+        Some(Err(e)) => {
+            tracing::error!("web server: TLS setup failed: {e}");
+            let message = format!("TLS setup failed: {e}");
+            emit_status(ActorStatus::Disconnected, Some(&message), &state, &bus_tx).await;
+            bus_tx
+                .send_async(
+                    FlighthookMessage::new(FlighthookEvent::Alert {
+                        severity: Severity::Error,
+                        message,
+                    })
+                    .actor(&state.actor_id),
+                )
+                .await;
+            return;
+        }
+    };
+
     // Retry bind until success or shutdown
     let mut shutdown_rx = shutdown_rx;
     let listener = loop {
@@ -223,7 +332,13 @@ async fn run(
             Ok(l) => break l,
             Err(e) => {
                 tracing::warn!("web server: failed to bind {addr}: {e}, retrying in 3s");
-                emit_status(ActorStatus::Disconnected, &state, &bus_tx);
+                emit_status(
+                    ActorStatus::Disconnected,
+                    Some("bind failed"),
+                    &state,
+                    &bus_tx,
+                )
+                .await;
                 tokio::select! {
                     _ = tokio::time::sleep(std::time::Duration::from_secs(3)) => continue,
                     _ = &mut shutdown_rx => return,
@@ -232,28 +347,134 @@ async fn run(
         }
     };
 
-    tracing::info!("web server listening on {addr}");
-    emit_status(ActorStatus::Connected, &state, &bus_tx);
+    emit_status(ActorStatus::Connected, None, &state, &bus_tx).await;
+
+    let Some(tls_config) = tls_config else {
+        tracing::info!("web server listening on http://{addr}");
+        axum::serve(listener, app)
+            .with_graceful_shutdown(async { drop(shutdown_rx.await) })
+            .await
+            .ok();
+        return;
+    };
 
-    axum::serve(listener, app)
-        .with_graceful_shutdown(async { drop(shutdown_rx.await) })
-        .await
-        .ok();
+    tracing::info!("web server listening on https://{addr}");
+    // The native GUI cannot speak TLS: give it a plain listener on loopback.
+    let loopback = match state.root.gui_loopback_for(&state.actor_id) {
+        Some(gui_addr) => match tokio::net::TcpListener::bind(gui_addr).await {
+            Ok(l) => {
+                tracing::info!("web server listening on http://{gui_addr} (native GUI)");
+                Some(tokio::spawn(axum::serve(l, app.clone()).into_future()))
+            }
+            Err(e) => {
+                tracing::warn!("web server: failed to bind GUI listener {gui_addr}: {e}");
+                None
+            }
+        },
+        None => None,
+    };
+    let handle = axum_server::Handle::new();
+    let shutdown_handle = handle.clone();
+    tokio::spawn(async move {
+        drop(shutdown_rx.await);
+        shutdown_handle.graceful_shutdown(Some(std::time::Duration::from_secs(1)));
+    });
+    let listener = match listener.into_std() {
+        Ok(l) => l,
+        Err(e) => {
+            tracing::error!("web server: failed to hand listener to TLS server: {e}");
+            return;
+        }
+    };
+    axum_server::from_tcp_rustls(
+        listener,
+        axum_server::tls_rustls::RustlsConfig::from_config(tls_config),
+    )
+    .handle(handle)
+    .serve(app.into_make_service())
+    .await
+    .ok();
+    if let Some(loopback) = loopback {
+        loopback.abort();
+    }
 }
 
-/// Background task that subscribes to the bus and keeps WebState current.
-async fn state_updater(state: Arc<WebState>, mut bus_rx: broadcast::Receiver<FlighthookMessage>) {
+/// Background task that subscribes to the bus, keeps WebState current, and
+/// sequences messages onto the WS feed.
+///
+/// The backlog is written before the feed send, so a WS client that subscribes
+/// to the feed and then reads the backlog sees every message at least once
+/// (duplicates are dropped by sequence number).
+///
+/// It also turns the registry's bus lag counters into `bus_lagged` /
+/// `bus_dropped` actor telemetry, published to this server's clients only.
+async fn state_updater(state: Arc<WebState>, mut bus_rx: BusReceiver) {
     let mut accumulators: HashMap<(String, ShotKey), ShotAccumulator> = HashMap::new();
+    let mut reported: HashMap<String, LagCounts> = HashMap::new();
+    let mut lag_check = tokio::time::interval(LAG_REPORT_INTERVAL);
 
     loop {
-        match bus_rx.recv().await {
-            Ok(msg) => apply_bus_event(&state, &msg, &mut accumulators).await,
-            Err(broadcast::error::RecvError::Closed) => break,
-            Err(broadcast::error::RecvError::Lagged(n)) => {
-                tracing::warn!("web state updater: lagged, dropped {n} events");
+        tokio::select! {
+            received = bus_rx.recv() => {
+                let Ok(msg) = received else { break };
+                publish(&state, msg, &mut accumulators).await;
             }
+            _ = lag_check.tick() => {
+                for msg in lag_reports(&state, &mut reported).await {
+                    publish(&state, msg, &mut accumulators).await;
+                }
+            }
+        }
+    }
+}
+
+/// Apply a message to the web state and sequence it onto the WS feed.
+async fn publish(
+    state: &WebState,
+    msg: FlighthookMessage,
+    accumulators: &mut HashMap<(String, ShotKey), ShotAccumulator>,
+) {
+    apply_bus_event(state, &msg, accumulators).await;
+    let seq = state.seq.fetch_add(1, Ordering::Relaxed) + 1;
+    state.shot_backlog.write().await.push(seq, &msg);
+    let _ = state.feed.send(SequencedMessage { seq, msg });
+}
+
+/// `ActorStatus` updates for actors whose bus lag counters moved since they
+/// were last reported. The counters live in the registry, not with the
+/// actor, so the update repeats the actor's last reported status and
+/// telemetry; actors that have not reported one yet are retried on the next
+/// check.
+async fn lag_reports(
+    state: &WebState,
+    reported: &mut HashMap<String, LagCounts>,
+) -> Vec<FlighthookMessage> {
+    let cached = state.cached_actor_status.read().await;
+    let mut reports = Vec::new();
+    for (id, counts) in state.root.actor_lag() {
+        if reported.get(&id).copied().unwrap_or_default() == counts {
+            continue;
         }
+        let Some(FlighthookEvent::ActorStatus { status, telemetry }) =
+            cached.get(&id).map(|m| &m.event)
+        else {
+            continue;
+        };
+        // Carry the rest forward: the message replaces the cached one that
+        // new WS clients are sent.
+        let mut telemetry = telemetry.clone();
+        telemetry.insert("bus_lagged".into(), counts.lagged.to_string());
+        telemetry.insert("bus_dropped".into(), counts.dropped.to_string());
+        reports.push(
+            FlighthookMessage::new(FlighthookEvent::ActorStatus {
+                status: *status,
+                telemetry,
+            })
+            .actor(id.as_str()),
+        );
+        reported.insert(id, counts);
     }
+    reports
 }
 
 async fn apply_bus_event(
@@ -282,12 +503,18 @@ async fn apply_bus_event(
                 .insert(msg.actor.clone(), msg.clone());
         }
         FlighthookEvent::ShotTrigger { key } => {
-            let acc = ShotAccumulator::new(msg.actor.clone(), key.clone());
+            let acc =
+                ShotAccumulator::new(msg.actor.clone(), key.clone()).triggered_at(msg.timestamp_ms);
             accumulators.insert((msg.actor.clone(), key.clone()), acc);
         }
-        FlighthookEvent::BallFlight { key, ball } => {
+        FlighthookEvent::BallFlight {
+            key,
+            ball,
+            estimated,
+        } => {
             if let Some(acc) = accumulators.get_mut(&(msg.actor.clone(), key.clone())) {
                 acc.set_ball(*ball.clone());
+                acc.set_estimated(*estimated);
             }
         }
         FlighthookEvent::FaceImpact { key, impact } => {
@@ -301,14 +528,49 @@ async fn apply_bus_event(
             }
         }
         FlighthookEvent::ShotFinished { key } => {
-            if let Some(acc) = accumulators.remove(&(msg.actor.clone(), key.clone()))
-                && let Some(shot) = acc.finish()
-            {
+            if let Some(acc) = accumulators.remove(&(msg.actor.clone(), key.clone())) {
+                let finished_ms = acc.latency_to(msg.timestamp_ms);
+                let Some(shot) = acc.finish() else { return };
+                let game = state.root.game.snapshot();
+                let config = state.root.system.snapshot();
                 let mut shots = state.shots.write().await;
+                let mut record = shots::record(shot, key, &game, &config, shots.back());
+                record.latency.finished_ms = finished_ms;
                 if shots.len() >= MAX_SHOTS {
                     shots.pop_front();
                 }
-                shots.push_back(shot);
+                shots.push_back(record);
+            }
+        }
+        FlighthookEvent::ShotDelivered {
+            key,
+            source,
+            latency_ms,
+        } => {
+            let mut shots = state.shots.write().await;
+            if let Some(record) = shots
+                .iter_mut()
+                .rev()
+                .find(|r| r.shot_id == key.shot_id && r.shot.actor == *source)
+            {
+                record
+                    .latency
+                    .delivered_ms
+                    .insert(msg.actor.clone(), *latency_ms);
+            }
+        }
+        FlighthookEvent::ShotSuppressed {
+            key, source, kept, ..
+        } => {
+            let mut shots = state.shots.write().await;
+            if let Some(record) = shots
+                .iter_mut()
+                .rev()
+                .find(|r| r.shot_id == key.shot_id && r.shot.actor == *source)
+            {
+                record
+                    .duplicate_of
+                    .insert(msg.actor.clone(), kept.shot_id.clone());
             }
         }
         FlighthookEvent::PlayerInfo { player_info } => {
@@ -340,6 +602,10 @@ async fn apply_bus_event(
 
                 let mut actors = state.actors.write().await;
                 for (id, name) in &names {
+                    // Disabled or lifecycle-stopped actors are not running.
+                    if snap.section_disabled(id) == Some(true) || state.root.is_held(id) {
+                        continue;
+                    }
                     let entry = actors
                         .entry(id.clone())
                         .or_insert_with(|| new_actor(name.clone()));
@@ -1,20 +1,26 @@
 //! REST endpoint handlers.
 
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 
 use axum::Json;
 use axum::body::Body;
-use axum::extract::{Query, State};
+use axum::extract::{Path, Query, State};
 use axum::http::{HeaderValue, StatusCode, header};
-use axum::response::Response;
+use axum::response::{IntoResponse, Response};
 use serde::Deserialize;
-use tokio::sync::broadcast;
 
-use super::WebState;
-use super::types::{ModeRequest, PostSettingsResponse, StatusResponse};
+use super::types::{
+    ConfigHistoryEntry, HealthResponse, ModeRequest, PostSettingsResponse, PostShotRequest,
+    PostShotResponse, ProfilesResponse, StatusResponse,
+};
+use super::{WebState, health, shots};
 use crate::state::config::FlighthookConfig;
-use flighthook::{ConfigAction, FlighthookEvent, FlighthookMessage, ShotData, UnitSystem};
+use flighthook::{
+    ConfigAction, ConfigValidation, FlighthookEvent, FlighthookMessage, ShotData,
+    ShotDetectionMode, ShotKey, UnitSystem,
+};
 
 // ---------------------------------------------------------------------------
 // Embedded UI assets (built by `make ui` in flighthook/ui/)
@@ -61,17 +67,6 @@ pub async fn get_ui_wasm() -> Response {
 // REST API
 // ---------------------------------------------------------------------------
 
-#[derive(Deserialize)]
-pub struct ShotsQuery {
-    #[serde(default = "default_limit")]
-    pub limit: usize,
-    pub units: Option<String>,
-}
-
-fn default_limit() -> usize {
-    50
-}
-
 /// GET /api/status
 pub async fn get_status(State(state): State<Arc<WebState>>) -> Json<StatusResponse> {
     let actors_guard = state.actors.read().await;
@@ -81,30 +76,126 @@ pub async fn get_status(State(state): State<Arc<WebState>>) -> Json<StatusRespon
         .collect();
 
     let mode = state.root.game.snapshot().mode;
-    Json(StatusResponse { actors, mode })
+    let global = mode.unwrap_or(ShotDetectionMode::Full);
+    let config = state.root.system.snapshot();
+    let device_modes = actors_guard
+        .keys()
+        .filter_map(|id| Some((id.clone(), config.device_mode(id, global)?)))
+        .collect();
+    Json(StatusResponse {
+        actors,
+        mode,
+        device_modes,
+    })
 }
 
-/// GET /api/shots?limit=50&units=imperial|metric
+/// GET /api/health — readiness and per-actor liveness. 503 when the system
+/// actor or a `[health] critical` actor is down, stalled or not connected.
+pub async fn get_health(State(state): State<Arc<WebState>>) -> (StatusCode, Json<HealthResponse>) {
+    let policy = state.root.system.snapshot().health;
+    let liveness = state.root.actor_liveness();
+    let report = health::report(&liveness, &*state.actors.read().await, &policy);
+    let code = if report.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (code, Json(report))
+}
+
+/// GET /api/shots — shot history, filtered, sorted and paginated (see
+/// [`shots::ShotsQuery`]). When more shots follow, the cursor for the next
+/// page is in the `X-Next-Cursor` header.
 pub async fn get_shots(
     State(state): State<Arc<WebState>>,
-    Query(query): Query<ShotsQuery>,
-) -> Json<Vec<ShotData>> {
-    let shots = state.shots.read().await;
-    let start = shots.len().saturating_sub(query.limit);
-    let unit_system = query.units.as_deref().and_then(|u| match u {
-        "imperial" => Some(UnitSystem::Imperial),
-        "metric" => Some(UnitSystem::Metric),
-        _ => None,
-    });
-    let result: Vec<ShotData> = shots
-        .iter()
-        .skip(start)
-        .map(|s| match unit_system {
-            Some(system) => s.to_unit_system(system),
-            None => s.clone(),
-        })
-        .collect();
-    Json(result)
+    Query(query): Query<shots::ShotsQuery>,
+) -> Result<Response, StatusCode> {
+    let page = shots::query(&*state.shots.read().await, &query)?;
+    let mut response = Json(page.shots).into_response();
+    if let Some(cursor) = page.next_cursor
+        && let Ok(value) = HeaderValue::from_str(&cursor)
+    {
+        response.headers_mut().insert(NEXT_CURSOR_HEADER, value);
+    }
+    Ok(response)
+}
+
+/// Response header carrying the `after` cursor of the next page.
+pub const NEXT_CURSOR_HEADER: &str = "x-next-cursor";
+
+/// Actor ID for shots injected via `POST /api/shots` without attribution.
+pub const MANUAL_ACTOR_ID: &str = "manual";
+
+/// Launch monitor section types a manual shot may be attributed to.
+const MONITOR_TYPES: [&str; 5] = [
+    "mevo",
+    "r10",
+    "square",
+    "openconnect_server",
+    "mock_monitor",
+];
+
+/// Actor a manual shot is published as: the dedicated `manual` actor, or the
+/// requested launch monitor if it is configured. `None` for anything else.
+fn manual_shot_actor(config: &FlighthookConfig, requested: Option<&str>) -> Option<String> {
+    let Some(id) = requested else {
+        return Some(MANUAL_ACTOR_ID.to_string());
+    };
+    let (prefix, _) = id.split_once('.')?;
+    (MONITOR_TYPES.contains(&prefix) && config.section_disabled(id).is_some())
+        .then(|| id.to_string())
+}
+
+/// POST /api/shots — inject a hand-entered or synthetic shot.
+///
+/// Publishes a full `ShotTrigger` .. `ShotFinished` lifecycle on the bus, so
+/// the shot is recorded, streamed and routed like one from a real monitor.
+/// `400` when the body carries no shot data or names an actor that is not a
+/// configured launch monitor.
+pub async fn post_shot(
+    State(state): State<Arc<WebState>>,
+    Json(body): Json<PostShotRequest>,
+) -> Result<(StatusCode, Json<PostShotResponse>), StatusCode> {
+    if body.ball.is_none() && body.club.is_none() && body.impact.is_none() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let actor = manual_shot_actor(&state.root.system.snapshot(), body.actor.as_deref())
+        .ok_or(StatusCode::BAD_REQUEST)?;
+    let key = ShotKey {
+        shot_id: uuid::Uuid::new_v4().to_string(),
+        shot_number: state.manual_shots.fetch_add(1, Ordering::Relaxed) + 1,
+    };
+
+    let mut events = vec![FlighthookEvent::ShotTrigger { key: key.clone() }];
+    if let Some(ball) = body.ball {
+        events.push(FlighthookEvent::BallFlight {
+            key: key.clone(),
+            ball: Box::new(ball),
+            estimated: false,
+        });
+    }
+    if let Some(club) = body.club {
+        events.push(FlighthookEvent::ClubPath {
+            key: key.clone(),
+            club: Box::new(club),
+        });
+    }
+    if let Some(impact) = body.impact {
+        events.push(FlighthookEvent::FaceImpact {
+            key: key.clone(),
+            impact: Box::new(impact),
+        });
+    }
+    events.push(FlighthookEvent::ShotFinished { key: key.clone() });
+
+    tracing::info!("manual shot #{} published as '{actor}'", key.shot_number);
+    for event in events {
+        state
+            .bus_tx
+            .send_async(FlighthookMessage::new(event).actor(&actor))
+            .await;
+    }
+    Ok((StatusCode::CREATED, Json(PostShotResponse { actor, key })))
 }
 
 // ---------------------------------------------------------------------------
@@ -139,13 +230,16 @@ pub async fn post_mode(
     Json(body): Json<ModeRequest>,
 ) -> StatusCode {
     let mode = body.mode;
-    let _ = state.bus_tx.send(
-        FlighthookMessage::new(FlighthookEvent::SetDetectionMode {
-            mode: Some(mode),
-            handed: None,
-        })
-        .actor("web"),
-    );
+    state
+        .bus_tx
+        .send_async(
+            FlighthookMessage::new(FlighthookEvent::SetDetectionMode {
+                mode: Some(mode),
+                handed: None,
+            })
+            .actor("web"),
+        )
+        .await;
     StatusCode::ACCEPTED
 }
 
@@ -197,10 +291,35 @@ fn scoped_action(scope: &str, config: &FlighthookConfig) -> Option<ConfigAction>
         "gspro" => upsert!(gspro, UpsertGsPro),
         "mock_monitor" => upsert!(mock_monitor, UpsertMockMonitor),
         "random_club" => upsert!(random_club, UpsertRandomClub),
+        "plugin" => upsert!(plugin, UpsertPlugin),
+        "script" => upsert!(script, UpsertScript),
+        "process" => upsert!(process, UpsertProcess),
         _ => return None,
     })
 }
 
+/// The settings action for a `POST /api/settings` body. `None` for an unknown
+/// scope.
+fn settings_action(scope: Option<&str>, config: FlighthookConfig) -> Option<ConfigAction> {
+    match scope {
+        None => Some(ConfigAction::ReplaceAll {
+            config: Box::new(config),
+        }),
+        Some(scope) => scoped_action(scope, &config),
+    }
+}
+
+/// Validate the config `action` would produce from `current`. See
+/// [`FlighthookConfig::validate_from`] for which errors count.
+pub(super) fn validate_change(
+    current: &FlighthookConfig,
+    action: &ConfigAction,
+) -> ConfigValidation {
+    let mut candidate = current.clone();
+    action.apply(&mut candidate);
+    candidate.validate_from(current)
+}
+
 /// POST /api/settings — config replacement via bus request-reply.
 ///
 /// Emits a `ConfigCommand` on the bus, waits for `ConfigOutcome` with a
@@ -210,76 +329,309 @@ fn scoped_action(scope: &str, config: &FlighthookConfig) -> Option<ConfigAction>
 /// `?scope=<global_id>` narrows the save to one actor: only that section is
 /// written and only that actor is reconciled. Without it the whole config is
 /// replaced and every actor is reconciled.
+///
+/// A change that would introduce validation errors is rejected with `422`
+/// and the errors; nothing is saved. Warnings are returned with the outcome.
 pub async fn post_settings(
     State(state): State<Arc<WebState>>,
     Query(query): Query<SettingsQuery>,
     Json(new_config): Json<FlighthookConfig>,
-) -> Json<PostSettingsResponse> {
-    let action = match query.scope.as_deref() {
-        None => ConfigAction::ReplaceAll {
-            config: Box::new(new_config),
-        },
-        Some(scope) => match scoped_action(scope, &new_config) {
-            Some(action) => action,
-            // Applying the body wholesale here would save far more than the
-            // caller asked for, so reject instead.
-            None => {
-                tracing::warn!("config update: unknown scope '{scope}', ignoring request");
-                return Json(PostSettingsResponse {
-                    restarted: Vec::new(),
-                    stopped: Vec::new(),
-                });
-            }
-        },
+) -> Result<Json<PostSettingsResponse>, (StatusCode, Json<ConfigValidation>)> {
+    let Some(action) = settings_action(query.scope.as_deref(), new_config) else {
+        // Applying the body wholesale here would save far more than the
+        // caller asked for, so reject instead.
+        tracing::warn!(
+            "config update: unknown scope '{}', ignoring request",
+            query.scope.unwrap_or_default()
+        );
+        return Ok(Json(PostSettingsResponse::default()));
+    };
+
+    let validation = validate_change(&state.root.system.snapshot(), &action);
+    if !validation.is_valid() {
+        for e in &validation.errors {
+            tracing::warn!(
+                "config update rejected: {}: {}",
+                e.section.as_deref().unwrap_or("config"),
+                e.message
+            );
+        }
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(validation)));
+    }
+
+    match config_round_trip(&state, action).await {
+        Some(response) => Ok(Json(PostSettingsResponse {
+            warnings: validation.warnings,
+            ..response
+        })),
+        None => {
+            tracing::warn!("config update: timed out waiting for ConfigOutcome");
+            Ok(Json(PostSettingsResponse::default()))
+        }
+    }
+}
+
+/// POST /api/settings/validate — check a `POST /api/settings` body without
+/// saving it. Same body and `scope` as the save; `400` for an unknown scope.
+pub async fn post_settings_validate(
+    State(state): State<Arc<WebState>>,
+    Query(query): Query<SettingsQuery>,
+    Json(new_config): Json<FlighthookConfig>,
+) -> Result<Json<ConfigValidation>, StatusCode> {
+    let action =
+        settings_action(query.scope.as_deref(), new_config).ok_or(StatusCode::BAD_REQUEST)?;
+    Ok(Json(validate_change(
+        &state.root.system.snapshot(),
+        &action,
+    )))
+}
+
+/// GET /api/settings/history — prior configs with the change that replaced
+/// each, newest first.
+pub async fn get_settings_history(
+    State(state): State<Arc<WebState>>,
+) -> Json<Vec<ConfigHistoryEntry>> {
+    Json(state.root.system.history())
+}
+
+/// POST /api/settings/history/{id}/rollback — restore the config a history
+/// entry recorded.
+///
+/// Replaces the whole config through the same `ConfigCommand` round trip as
+/// `POST /api/settings`, so actors are reconciled as usual and the rollback
+/// itself lands in the history (and can be undone). `404` when the entry is
+/// no longer kept.
+pub async fn post_settings_rollback(
+    State(state): State<Arc<WebState>>,
+    Path(id): Path<u64>,
+) -> Result<Json<PostSettingsResponse>, StatusCode> {
+    let entry = state
+        .root
+        .system
+        .history_entry(id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    tracing::info!(
+        "config rollback to history entry {id} ({})",
+        entry.recorded_at
+    );
+    let action = ConfigAction::ReplaceAll {
+        config: Box::new(entry.previous),
     };
+    match config_round_trip(&state, action).await {
+        Some(response) => Ok(Json(response)),
+        None => {
+            tracing::warn!("config rollback {id}: timed out waiting for ConfigOutcome");
+            Err(StatusCode::GATEWAY_TIMEOUT)
+        }
+    }
+}
 
+// ---------------------------------------------------------------------------
+// Profiles
+// ---------------------------------------------------------------------------
+
+/// GET /api/profiles — the active profile and all profile names.
+pub async fn get_profiles(State(state): State<Arc<WebState>>) -> Json<ProfilesResponse> {
+    let config = state.root.system.snapshot();
+    Json(ProfilesResponse {
+        profiles: config.profile_names(),
+        active: config.active_profile,
+    })
+}
+
+/// POST /api/profiles/{name}/activate — switch to a saved profile.
+///
+/// The live device and integration sections are saved under the current
+/// profile's name and replaced by those of `name`; actors are reconciled as
+/// for `POST /api/settings`, so only what differs is restarted. `404` for an
+/// unknown profile, `422` with the errors when the switch would introduce
+/// validation errors.
+pub async fn post_profile_activate(
+    State(state): State<Arc<WebState>>,
+    Path(name): Path<String>,
+) -> Result<Json<PostSettingsResponse>, Response> {
+    let current = state.root.system.snapshot();
+    if !current.profile_names().contains(&name) {
+        return Err(StatusCode::NOT_FOUND.into_response());
+    }
+    let action = ConfigAction::SwitchProfile { name: name.clone() };
+    let validation = validate_change(&current, &action);
+    if !validation.is_valid() {
+        tracing::warn!(
+            "profile switch to '{name}' rejected: {} validation error(s)",
+            validation.errors.len()
+        );
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(validation)).into_response());
+    }
+    tracing::info!("switching to profile '{name}'");
+    match config_round_trip(&state, action).await {
+        Some(mut response) => {
+            response.warnings = validation.warnings;
+            Ok(Json(response))
+        }
+        None => {
+            tracing::warn!("profile switch to '{name}': timed out waiting for ConfigOutcome");
+            Err(StatusCode::GATEWAY_TIMEOUT.into_response())
+        }
+    }
+}
+
+/// PUT /api/profiles/{name} — save the live sections as profile `name`,
+/// replacing any profile of that name. `409` for the active profile, whose
+/// sections are the live ones already.
+pub async fn put_profile(
+    State(state): State<Arc<WebState>>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, StatusCode> {
+    if name == state.root.system.snapshot().active_profile {
+        return Err(StatusCode::CONFLICT);
+    }
+    profile_round_trip(&state, ConfigAction::SaveProfile { name }).await
+}
+
+/// DELETE /api/profiles/{name} — delete an inactive profile. `404` for an
+/// unknown profile, `409` for the active one.
+pub async fn delete_profile(
+    State(state): State<Arc<WebState>>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, StatusCode> {
+    let current = state.root.system.snapshot();
+    if name == current.active_profile {
+        return Err(StatusCode::CONFLICT);
+    }
+    if !current.profiles.contains_key(&name) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    profile_round_trip(&state, ConfigAction::DeleteProfile { name }).await
+}
+
+/// Round trip for profile edits that start or stop nothing: `204` once
+/// applied.
+async fn profile_round_trip(
+    state: &WebState,
+    action: ConfigAction,
+) -> Result<StatusCode, StatusCode> {
+    let kind = action.kind();
+    match config_round_trip(state, action).await {
+        Some(_) => Ok(StatusCode::NO_CONTENT),
+        None => {
+            tracing::warn!("{kind}: timed out waiting for ConfigOutcome");
+            Err(StatusCode::GATEWAY_TIMEOUT)
+        }
+    }
+}
+
+/// Emit a `ConfigCommand` and wait (up to 10s) for its `ConfigOutcome`.
+async fn config_round_trip(state: &WebState, action: ConfigAction) -> Option<PostSettingsResponse> {
     let request_id = crate::state::config::generate_id();
     let mut bus_rx = state.bus_tx.subscribe();
 
     // Emit ConfigCommand on the bus
-    let _ = state.bus_tx.send(
-        FlighthookMessage::new(FlighthookEvent::ConfigCommand {
-            request_id: Some(request_id.clone()),
-            action: Box::new(action),
-        })
-        .actor("web"),
-    );
+    state
+        .bus_tx
+        .send_async(
+            FlighthookMessage::new(FlighthookEvent::ConfigCommand {
+                request_id: Some(request_id.clone()),
+                action: Box::new(action),
+            })
+            .actor("web"),
+        )
+        .await;
 
     // Wait for ConfigOutcome with matching request_id (10s timeout)
-    let result = tokio::time::timeout(Duration::from_secs(10), async {
-        loop {
-            match bus_rx.recv().await {
-                Ok(msg) => {
-                    if let FlighthookEvent::ConfigOutcome {
-                        request_id: Some(ref rid),
-                        ref restarted,
-                        ref stopped,
-                        ..
-                    } = msg.event
-                        && *rid == request_id
-                    {
-                        return Some((restarted.clone(), stopped.clone()));
-                    }
-                }
-                Err(broadcast::error::RecvError::Closed) => return None,
-                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while let Ok(msg) = bus_rx.recv().await {
+            if let FlighthookEvent::ConfigOutcome {
+                request_id: Some(ref rid),
+                ref restarted,
+                ref stopped,
+                ref started,
+            } = msg.event
+                && *rid == request_id
+            {
+                return Some(PostSettingsResponse {
+                    restarted: restarted.clone(),
+                    stopped: stopped.clone(),
+                    started: started.clone(),
+                    warnings: Vec::new(),
+                });
             }
         }
+        None
     })
-    .await;
+    .await
+    .ok()
+    .flatten()
+}
 
-    match result {
-        Ok(Some((restarted, stopped))) => Json(PostSettingsResponse { restarted, stopped }),
-        _ => {
-            tracing::warn!("config update: timed out waiting for ConfigOutcome");
-            Json(PostSettingsResponse {
-                restarted: Vec::new(),
-                stopped: Vec::new(),
-            })
+/// Actor lifecycle operations exposed under `/api/actors/{id}/...`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lifecycle {
+    Restart,
+    Stop,
+    Start,
+}
+
+/// Check a lifecycle request against the config and build its action.
+///
+/// `404` for an ID with no config section (including `system` and WebSocket
+/// sessions), `409` for starting a section marked `disabled`.
+fn lifecycle_action(
+    config: &FlighthookConfig,
+    id: &str,
+    op: Lifecycle,
+) -> Result<ConfigAction, StatusCode> {
+    let disabled = config.section_disabled(id).ok_or(StatusCode::NOT_FOUND)?;
+    let id = id.to_string();
+    match op {
+        Lifecycle::Stop => Ok(ConfigAction::StopActor { id }),
+        _ if disabled => Err(StatusCode::CONFLICT),
+        Lifecycle::Restart => Ok(ConfigAction::RestartActor { id }),
+        Lifecycle::Start => Ok(ConfigAction::StartActor { id }),
+    }
+}
+
+async fn actor_lifecycle(
+    state: &WebState,
+    id: &str,
+    op: Lifecycle,
+) -> Result<Json<PostSettingsResponse>, StatusCode> {
+    let action = lifecycle_action(&state.root.system.snapshot(), id, op)?;
+    match config_round_trip(state, action).await {
+        Some(response) => Ok(Json(response)),
+        None => {
+            tracing::warn!("actor {op:?} '{id}': timed out waiting for ConfigOutcome");
+            Err(StatusCode::GATEWAY_TIMEOUT)
         }
     }
 }
 
+/// POST /api/actors/{id}/restart — stop and recreate an actor from its
+/// current config section.
+pub async fn post_actor_restart(
+    State(state): State<Arc<WebState>>,
+    Path(id): Path<String>,
+) -> Result<Json<PostSettingsResponse>, StatusCode> {
+    actor_lifecycle(&state, &id, Lifecycle::Restart).await
+}
+
+/// POST /api/actors/{id}/stop — stop an actor until it is started again.
+/// The config is not changed.
+pub async fn post_actor_stop(
+    State(state): State<Arc<WebState>>,
+    Path(id): Path<String>,
+) -> Result<Json<PostSettingsResponse>, StatusCode> {
+    actor_lifecycle(&state, &id, Lifecycle::Stop).await
+}
+
+/// POST /api/actors/{id}/start — start an actor stopped via `/stop`.
+pub async fn post_actor_start(
+    State(state): State<Arc<WebState>>,
+    Path(id): Path<String>,
+) -> Result<Json<PostSettingsResponse>, StatusCode> {
+    actor_lifecycle(&state, &id, Lifecycle::Start).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -323,9 +675,79 @@ mod tests {
         }
     }
 
+    #[test]
+    fn lifecycle_checks_section_and_disabled_flag() {
+        let mut config = config();
+        config.mevo.insert("0".into(), mevo_section("Bay 1"));
+        let mut off = mevo_section("Bay 2");
+        off.disabled = true;
+        config.mevo.insert("1".into(), off);
+
+        assert!(matches!(
+            lifecycle_action(&config, "mevo.0", Lifecycle::Restart),
+            Ok(ConfigAction::RestartActor { id }) if id == "mevo.0"
+        ));
+        assert_eq!(
+            lifecycle_action(&config, "mevo.1", Lifecycle::Start).unwrap_err(),
+            StatusCode::CONFLICT
+        );
+        assert!(matches!(
+            lifecycle_action(&config, "mevo.1", Lifecycle::Stop),
+            Ok(ConfigAction::StopActor { .. })
+        ));
+        for id in ["mevo.9", "system", "ws.a1b2c3d4", "bogus"] {
+            assert_eq!(
+                lifecycle_action(&config, id, Lifecycle::Stop).unwrap_err(),
+                StatusCode::NOT_FOUND
+            );
+        }
+    }
+
+    #[test]
+    fn manual_shots_attribute_only_to_configured_monitors() {
+        let mut config = config();
+        config.mevo.insert("0".into(), mevo_section("Bay 1"));
+        config
+            .gspro
+            .insert("0".into(), flighthook::GsProSection::default());
+
+        assert_eq!(
+            manual_shot_actor(&config, None).as_deref(),
+            Some(MANUAL_ACTOR_ID)
+        );
+        assert_eq!(
+            manual_shot_actor(&config, Some("mevo.0")).as_deref(),
+            Some("mevo.0")
+        );
+        for id in ["mevo.1", "gspro.0", "webserver.0", "system", "manual"] {
+            assert_eq!(manual_shot_actor(&config, Some(id)), None, "{id}");
+        }
+    }
+
     #[test]
     fn malformed_and_unknown_scopes_are_rejected() {
         assert!(scoped_action("mevo", &config()).is_none());
         assert!(scoped_action("nosuchtype.0", &config()).is_none());
     }
+
+    #[test]
+    fn only_errors_a_change_introduces_block_it() {
+        let mut live = config();
+        let mut broken = mevo_section("Mevo");
+        broken.address = Some("not an address".into());
+        live.mevo.insert("0".into(), broken);
+
+        // Saving another section over an already-broken config is fine.
+        let mut body = live.clone();
+        body.mevo.insert("1".into(), mevo_section("Second"));
+        let action = scoped_action("mevo.1", &body).expect("known scope");
+        assert!(validate_change(&live, &action).is_valid());
+
+        // A change that breaks something new is not.
+        body.mevo.get_mut("1").unwrap().address = Some("nope".into());
+        let action = scoped_action("mevo.1", &body).expect("known scope");
+        let result = validate_change(&live, &action);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].section.as_deref(), Some("mevo.1"));
+    }
 }
@@ -0,0 +1,457 @@
+//! Machine-readable API description — OpenAPI 3.0 for the REST routes plus
+//! the `FlighthookMessage` JSON Schema for the `/frp` WebSocket.
+//!
+//! Component schemas are generated from the `flighthook` lib types, so they
+//! follow the code. The paths below mirror the router in `web/mod.rs` and are
+//! maintained by hand. Copies of both documents are committed under
+//! `docs/schema/`; the tests fail when those go stale (regenerate with
+//! `UPDATE_SCHEMAS=1 cargo test -p flighthook-app schema`).
+
+use std::sync::OnceLock;
+
+use axum::Json;
+use schemars::Schema;
+use schemars::generate::{SchemaGenerator, SchemaSettings};
+use serde_json::{Value, json};
+
+use flighthook::{
+    ConfigHistoryEntry, ConfigValidation, FlighthookConfig, FlighthookMessage, HealthResponse,
+    ModeRequest, PostSettingsResponse, PostShotRequest, PostShotResponse, ProfilesResponse,
+    ShotData, ShotRecord, StatusResponse, TokenScope,
+};
+
+/// GET /api/schema — OpenAPI 3.0 document for the REST API.
+pub async fn get_openapi() -> Json<Value> {
+    static DOC: OnceLock<Value> = OnceLock::new();
+    Json(DOC.get_or_init(openapi).clone())
+}
+
+/// GET /api/schema/message — JSON Schema of a WebSocket `FlighthookMessage`.
+pub async fn get_message_schema() -> Json<Value> {
+    static DOC: OnceLock<Value> = OnceLock::new();
+    Json(DOC.get_or_init(flighthook::schema::message_schema).clone())
+}
+
+/// Build the OpenAPI document.
+pub fn openapi() -> Value {
+    let mut g = SchemaSettings::openapi3().into_generator();
+
+    let list = |description: &str| json!({ "type": "string", "description": description });
+    let shot_params = json!([
+        query_param(
+            "limit",
+            false,
+            json!({ "type": "integer", "minimum": 0, "default": 50 }),
+            "Maximum number of shots to return."
+        ),
+        query_param(
+            "units",
+            false,
+            units_schema(),
+            "Convert the returned shots to this unit system."
+        ),
+        query_param(
+            "actor",
+            false,
+            list("Comma-separated"),
+            "Only shots from these actor IDs."
+        ),
+        query_param(
+            "club",
+            false,
+            list("Comma-separated"),
+            "Only shots with these selected club codes."
+        ),
+        query_param(
+            "mode",
+            false,
+            list("Comma-separated"),
+            "Only shots taken in these detection modes."
+        ),
+        query_param(
+            "session",
+            false,
+            json!({ "type": "integer", "minimum": 0 }),
+            "Only shots from this session."
+        ),
+        query_param(
+            "since",
+            false,
+            json!({ "type": "string", "format": "date-time" }),
+            "Only shots recorded at or after this time."
+        ),
+        query_param(
+            "until",
+            false,
+            json!({ "type": "string", "format": "date-time" }),
+            "Only shots recorded before this time."
+        ),
+        query_param(
+            "after",
+            false,
+            json!({ "type": "string" }),
+            "Cursor: continue after this shot ID (the previous page's `X-Next-Cursor`)."
+        ),
+        query_param(
+            "sort",
+            false,
+            json!({
+                "type": "string",
+                "pattern": "^-?(time|carry|total|ball_speed|club_speed|spin)$",
+            }),
+            "Sort key; a `-` prefix sorts descending. Shots missing the value sort last. \
+             Omitted: the newest `limit` shots, oldest first."
+        ),
+        query_param(
+            "fields",
+            false,
+            list("Comma-separated, dotted for nested fields"),
+            "Return only these fields of each shot, e.g. `shot_id,ball.carry_distance`."
+        ),
+    ]);
+    let convert_params = json!([query_param(
+        "units",
+        true,
+        units_schema(),
+        "Unit system to convert to."
+    )]);
+    let profile_param = json!([{
+        "name": "name",
+        "in": "path",
+        "required": true,
+        "description": "Profile name.",
+        "schema": { "type": "string" },
+    }]);
+    let settings_params = json!([query_param(
+        "scope",
+        false,
+        json!({ "type": "string" }),
+        "Save only this section, by global ID (e.g. `mevo.0`); every other section of the live config is left untouched."
+    )]);
+
+    let paths = json!({
+        "/api/status": {
+            "get": op("Actor status and detection mode", TokenScope::Read, None, json!({
+                "200": json_response("Current status", g.subschema_for::<StatusResponse>()),
+            })),
+        },
+        "/api/health": {
+            "get": op("Readiness and per-actor liveness", TokenScope::Read, None, json!({
+                "200": json_response("Ready", g.subschema_for::<HealthResponse>()),
+                "503": json_response(
+                    "Not ready: the system actor or a critical actor is down, stalled or not connected",
+                    g.subschema_for::<HealthResponse>(),
+                ),
+            })),
+        },
+        "/api/shots": {
+            "get": op("Query the shot history", TokenScope::Read, None, json!({
+                "200": json_response("One page of shots", g.subschema_for::<Vec<ShotRecord>>())
+                    .with("headers", json!({
+                        "X-Next-Cursor": {
+                            "description": "Pass as `after` to fetch the next page; absent on the last page.",
+                            "schema": { "type": "string" },
+                        },
+                    })),
+                "400": status("Unknown club, mode, sort key or units, malformed time, or unknown cursor"),
+            }))
+            .with("parameters", shot_params),
+            "post": op("Inject a manual or synthetic shot", TokenScope::Control,
+                Some(g.subschema_for::<PostShotRequest>()), json!({
+                "201": json_response("Shot published", g.subschema_for::<PostShotResponse>()),
+                "400": status("No shot data, or `actor` is not a configured launch monitor"),
+            })),
+        },
+        "/api/shots/convert": {
+            "post": op("Convert a shot to another unit system", TokenScope::Read,
+                Some(g.subschema_for::<ShotData>()), json!({
+                "200": json_response("Converted shot", g.subschema_for::<ShotData>()),
+                "400": status("Unknown unit system"),
+            }))
+            .with("parameters", convert_params),
+        },
+        "/api/mode": {
+            "post": op("Set the shot detection mode", TokenScope::Control,
+                Some(g.subschema_for::<ModeRequest>()), json!({
+                "202": status("Mode change published to the bus"),
+            })),
+        },
+        "/api/actors/{id}/restart": lifecycle(&mut g, "Stop and recreate an actor from its config section"),
+        "/api/actors/{id}/stop": lifecycle(&mut g, "Stop an actor until started again or the app restarts"),
+        "/api/actors/{id}/start": lifecycle(&mut g, "Start an actor stopped with `stop`"),
+        "/api/settings": {
+            "get": op("Full persisted config", TokenScope::Admin, None, json!({
+                "200": json_response("Current config", g.subschema_for::<FlighthookConfig>()),
+            })),
+            "post": op("Replace the config (or one section with `scope`)", TokenScope::Admin,
+                Some(g.subschema_for::<FlighthookConfig>()), json!({
+                "200": json_response("Actors affected by the change, and validation warnings",
+                    g.subschema_for::<PostSettingsResponse>()),
+                "422": json_response("The change would introduce validation errors; nothing was saved",
+                    g.subschema_for::<ConfigValidation>()),
+            }))
+            .with("parameters", settings_params.clone()),
+        },
+        "/api/settings/validate": {
+            "post": op("Validate a config without saving it", TokenScope::Admin,
+                Some(g.subschema_for::<FlighthookConfig>()), json!({
+                "200": json_response("Errors the change would introduce, and warnings",
+                    g.subschema_for::<ConfigValidation>()),
+                "400": status("Unknown `scope`"),
+            }))
+            .with("parameters", settings_params),
+        },
+        "/api/settings/history": {
+            "get": op("Prior configs and the changes that replaced them, newest first",
+                TokenScope::Admin, None, json!({
+                "200": json_response("Config change history",
+                    g.subschema_for::<Vec<ConfigHistoryEntry>>()),
+            })),
+        },
+        "/api/settings/history/{id}/rollback": {
+            "post": op("Restore the config recorded by a history entry", TokenScope::Admin,
+                None, json!({
+                "200": json_response("Actors affected by the rollback",
+                    g.subschema_for::<PostSettingsResponse>()),
+                "404": status("No history entry with this ID"),
+                "504": status("Timed out waiting for the system actor"),
+            }))
+            .with("parameters", json!([{
+                "name": "id",
+                "in": "path",
+                "required": true,
+                "description": "History entry ID.",
+                "schema": { "type": "integer", "minimum": 0 },
+            }])),
+        },
+        "/api/profiles": {
+            "get": op("Active profile and all profile names", TokenScope::Read, None, json!({
+                "200": json_response("Profiles", g.subschema_for::<ProfilesResponse>()),
+            })),
+        },
+        "/api/profiles/{name}": {
+            "put": op("Save the live devices and integrations as a profile", TokenScope::Admin,
+                None, json!({
+                "204": status("Saved"),
+                "409": status("This is the active profile"),
+                "504": status("Timed out waiting for the system actor"),
+            })),
+            "delete": op("Delete an inactive profile", TokenScope::Admin, None, json!({
+                "204": status("Deleted"),
+                "404": status("No profile with this name"),
+                "409": status("This is the active profile"),
+                "504": status("Timed out waiting for the system actor"),
+            })),
+            "parameters": profile_param.clone(),
+        },
+        "/api/profiles/{name}/activate": {
+            "post": op("Switch to a saved profile", TokenScope::Control, None, json!({
+                "200": json_response("Actors affected by the switch, and validation warnings",
+                    g.subschema_for::<PostSettingsResponse>()),
+                "404": status("No profile with this name"),
+                "422": json_response("The switch would introduce validation errors",
+                    g.subschema_for::<ConfigValidation>()),
+                "504": status("Timed out waiting for the system actor"),
+            })),
+            "parameters": profile_param,
+        },
+        "/api/schema": {
+            "get": op("This OpenAPI document", TokenScope::Read, None, json!({
+                "200": { "description": "OpenAPI 3.0 document" },
+            })),
+        },
+        "/api/schema/message": {
+            "get": op("JSON Schema of a WebSocket message", TokenScope::Read, None, json!({
+                "200": { "description": "JSON Schema (draft 2020-12) of `FlighthookMessage`" },
+            })),
+        },
+        flighthook::FRP_PATH: {
+            "get": {
+                "summary": "WebSocket event stream",
+                "description": "Upgrades to a WebSocket carrying `FlighthookMessage` JSON frames \
+                    (see `/api/schema/message`). The token, if required, is checked by the \
+                    `start` handshake rather than on the upgrade.",
+                "security": [],
+                "responses": { "101": { "description": "Switching protocols" } },
+            },
+        },
+    });
+
+    // Not referenced by any REST path, but the main type WebSocket consumers
+    // generate code from.
+    g.subschema_for::<FlighthookMessage>();
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "flighthook",
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": "REST API of the flighthook web server. Events are streamed over \
+                the WebSocket; its messages are the `FlighthookMessage` component.",
+        },
+        "paths": paths,
+        "components": {
+            "schemas": g.take_definitions(true),
+            "securitySchemes": {
+                "bearer": { "type": "http", "scheme": "bearer" },
+                "query": { "type": "apiKey", "in": "query", "name": "token" },
+            },
+        },
+        "security": [{ "bearer": [] }, { "query": [] }],
+    })
+}
+
+/// One operation. The required token scope goes in the description, since
+/// OpenAPI scopes only apply to OAuth flows.
+fn op(summary: &str, scope: TokenScope, body: Option<Schema>, responses: Value) -> Value {
+    let mut responses = responses;
+    responses["401"] = status("Missing or unknown token");
+    responses["403"] = status("Token scope too narrow");
+    let mut op = json!({
+        "summary": summary,
+        "description": format!("Requires a `{}` token when tokens are configured.", scope_name(scope)),
+        "responses": responses,
+    });
+    if let Some(schema) = body {
+        op["requestBody"] = json!({
+            "required": true,
+            "content": { "application/json": { "schema": schema } },
+        });
+    }
+    op
+}
+
+fn lifecycle(g: &mut SchemaGenerator, summary: &str) -> Value {
+    json!({
+        "post": op(summary, TokenScope::Control, None, json!({
+            "200": json_response("Actors affected", g.subschema_for::<PostSettingsResponse>()),
+            "404": status("No config section with this ID"),
+            "409": status("The section is disabled in config"),
+            "504": status("Timed out waiting for the system actor"),
+        }))
+        .with("parameters", json!([{
+            "name": "id",
+            "in": "path",
+            "required": true,
+            "description": "Global actor ID, e.g. `mevo.0`.",
+            "schema": { "type": "string" },
+        }])),
+    })
+}
+
+fn json_response(description: &str, schema: Schema) -> Value {
+    json!({
+        "description": description,
+        "content": { "application/json": { "schema": schema } },
+    })
+}
+
+fn status(description: &str) -> Value {
+    json!({ "description": description })
+}
+
+fn query_param(name: &str, required: bool, schema: Value, description: &str) -> Value {
+    json!({
+        "name": name,
+        "in": "query",
+        "required": required,
+        "description": description,
+        "schema": schema,
+    })
+}
+
+fn units_schema() -> Value {
+    json!({ "type": "string", "enum": ["imperial", "metric"] })
+}
+
+fn scope_name(scope: TokenScope) -> String {
+    serde_json::to_value(scope)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_owned))
+        .unwrap_or_default()
+}
+
+/// Builder-style field insert for `json!` values.
+trait With {
+    fn with(self, key: &str, value: Value) -> Self;
+}
+
+impl With for Value {
+    fn with(mut self, key: &str, value: Value) -> Self {
+        self[key] = value;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::PathBuf;
+
+    fn schema_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../docs/schema")
+    }
+
+    /// Compare `doc` to the committed copy, or rewrite it with `UPDATE_SCHEMAS=1`.
+    ///
+    /// `info.version` is ignored so a release bump alone does not fail the test.
+    fn check_committed(file: &str, doc: &Value) {
+        let path = schema_dir().join(file);
+        let generated = serde_json::to_string_pretty(doc).unwrap() + "\n";
+        if std::env::var_os("UPDATE_SCHEMAS").is_some() {
+            std::fs::create_dir_all(schema_dir()).unwrap();
+            std::fs::write(&path, generated).unwrap();
+            return;
+        }
+
+        let without_version = |mut v: Value| {
+            if let Some(info) = v.get_mut("info").and_then(Value::as_object_mut) {
+                info.remove("version");
+            }
+            v
+        };
+        let committed: Value = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or(Value::Null);
+        assert!(
+            without_version(committed) == without_version(doc.clone()),
+            "{} is out of date; regenerate with \
+             `UPDATE_SCHEMAS=1 cargo test -p flighthook-app schema`",
+            path.display()
+        );
+    }
+
+    #[test]
+    fn committed_openapi_is_current() {
+        check_committed("openapi.json", &openapi());
+    }
+
+    #[test]
+    fn committed_message_schema_is_current() {
+        check_committed(
+            "flighthook-message.schema.json",
+            &flighthook::schema::message_schema(),
+        );
+    }
+
+    #[test]
+    fn every_ref_resolves() {
+        let doc = openapi();
+        let text = doc.to_string();
+        let schemas = doc["components"]["schemas"].as_object().unwrap();
+        for name in [
+            "FlighthookMessage",
+            "StatusResponse",
+            "ShotData",
+            "ConfigAction",
+        ] {
+            assert!(schemas.contains_key(name), "{name} missing");
+        }
+        for r in text.split("\"$ref\":\"#/components/schemas/").skip(1) {
+            let name = &r[..r.find('"').unwrap()];
+            assert!(schemas.contains_key(name), "dangling $ref {name}");
+        }
+    }
+}
@@ -0,0 +1,532 @@
+//! Shot history for `GET /api/shots` — recording, filtering, sorting, cursor
+//! pagination and field projection over the in-memory history.
+
+use std::collections::VecDeque;
+
+use axum::http::StatusCode;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use flighthook::{
+    Club, FlighthookConfig, GameStateSnapshot, ShotData, ShotDetectionMode, ShotKey, ShotLatency,
+    ShotRecord, UnitSystem,
+};
+
+/// Query string of `GET /api/shots`. List-valued filters are comma-separated
+/// and match any of their values; all given filters must match.
+#[derive(Debug, Default, Deserialize)]
+pub struct ShotsQuery {
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+    pub units: Option<String>,
+    /// Actor IDs, e.g. `mevo.0,manual`.
+    pub actor: Option<String>,
+    /// Selected club codes, e.g. `7I,8I`.
+    pub club: Option<String>,
+    /// Detection modes, e.g. `chipping,putting`.
+    pub mode: Option<String>,
+    pub session: Option<u32>,
+    /// RFC 3339 bounds on `recorded_at`: `since` inclusive, `until` exclusive.
+    pub since: Option<String>,
+    pub until: Option<String>,
+    /// Cursor: return the shots that follow this shot ID in the sort order.
+    pub after: Option<String>,
+    /// Sort key, `-` prefix for descending. Without one, the newest shots
+    /// are selected as for `-time` and returned oldest first.
+    pub sort: Option<String>,
+    /// Comma-separated fields to return, dotted for nested ones
+    /// (`shot_id,ball.carry_distance`). Default: everything.
+    pub fields: Option<String>,
+}
+
+fn default_limit() -> usize {
+    50
+}
+
+/// One page of results. `next_cursor` is set when more shots follow.
+#[derive(Debug)]
+pub struct Page {
+    pub shots: Vec<Value>,
+    pub next_cursor: Option<String>,
+}
+
+/// Build the history record for a finished shot. `previous` is the most
+/// recent record, whose session continues unless the player changed. The
+/// shot's mode is its monitor's, after the section's `mode` pin or mapping.
+pub fn record(
+    shot: ShotData,
+    key: &ShotKey,
+    game: &GameStateSnapshot,
+    config: &FlighthookConfig,
+    previous: Option<&ShotRecord>,
+) -> ShotRecord {
+    let player = game.player_info.as_ref().and_then(|p| p.name.clone());
+    let session = match previous {
+        None => 1,
+        Some(prev) if prev.player == player => prev.session,
+        Some(prev) => prev.session + 1,
+    };
+    ShotRecord {
+        shot_id: key.shot_id.clone(),
+        recorded_at: timestamp(Utc::now()),
+        session,
+        player,
+        selected_club: game.club_info.map(|c| c.club),
+        mode: game
+            .mode
+            .map(|global| config.device_mode(&shot.actor, global).unwrap_or(global)),
+        latency: ShotLatency::default(),
+        duplicate_of: Default::default(),
+        shot,
+    }
+}
+
+/// Fixed-width UTC form, so timestamps compare correctly as strings.
+fn timestamp(t: DateTime<Utc>) -> String {
+    t.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SortKey {
+    Time,
+    Carry,
+    Total,
+    BallSpeed,
+    ClubSpeed,
+    Spin,
+}
+
+impl SortKey {
+    fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "time" => Self::Time,
+            "carry" => Self::Carry,
+            "total" => Self::Total,
+            "ball_speed" => Self::BallSpeed,
+            "club_speed" => Self::ClubSpeed,
+            "spin" => Self::Spin,
+            _ => return None,
+        })
+    }
+
+    /// Sort value in canonical units; `None` when the shot lacks it.
+    fn value(self, r: &ShotRecord) -> Option<f64> {
+        let ball = r.shot.ball.as_ref();
+        match self {
+            Self::Time => None,
+            Self::Carry => ball?.carry_distance.map(|d| d.as_yards()),
+            Self::Total => ball?.total_distance.map(|d| d.as_yards()),
+            Self::BallSpeed => ball?.launch_speed.map(|v| v.as_mph()),
+            Self::ClubSpeed => r.shot.club.as_ref()?.club_speed.map(|v| v.as_mph()),
+            Self::Spin => ball?.backspin_rpm.map(f64::from),
+        }
+    }
+}
+
+/// Parsed filters. `None` fields match everything.
+#[derive(Debug, Default)]
+struct Filter {
+    actors: Option<Vec<String>>,
+    clubs: Option<Vec<Club>>,
+    modes: Option<Vec<ShotDetectionMode>>,
+    session: Option<u32>,
+    since: Option<String>,
+    until: Option<String>,
+}
+
+impl Filter {
+    fn parse(q: &ShotsQuery) -> Option<Self> {
+        let modes = match &q.mode {
+            Some(list) => Some(
+                split(list)
+                    .map(|m| serde_json::from_value(Value::String(m.into())).ok())
+                    .collect::<Option<Vec<_>>>()?,
+            ),
+            None => None,
+        };
+        let clubs = match &q.club {
+            Some(list) => Some(
+                split(list)
+                    .map(Club::from_code)
+                    .collect::<Option<Vec<_>>>()?,
+            ),
+            None => None,
+        };
+        let bound = |s: &Option<String>| -> Option<Option<String>> {
+            match s {
+                Some(s) => DateTime::parse_from_rfc3339(s)
+                    .ok()
+                    .map(|t| Some(timestamp(t.to_utc()))),
+                None => Some(None),
+            }
+        };
+        Some(Self {
+            actors: q
+                .actor
+                .as_deref()
+                .map(|a| split(a).map(String::from).collect()),
+            clubs,
+            modes,
+            session: q.session,
+            since: bound(&q.since)?,
+            until: bound(&q.until)?,
+        })
+    }
+
+    fn matches(&self, r: &ShotRecord) -> bool {
+        self.actors
+            .as_ref()
+            .is_none_or(|a| a.contains(&r.shot.actor))
+            && self
+                .clubs
+                .as_ref()
+                .is_none_or(|c| r.selected_club.is_some_and(|club| c.contains(&club)))
+            && self
+                .modes
+                .as_ref()
+                .is_none_or(|m| r.mode.is_some_and(|mode| m.contains(&mode)))
+            && self.session.is_none_or(|s| r.session == s)
+            && self
+                .since
+                .as_ref()
+                .is_none_or(|t| r.recorded_at.as_str() >= t.as_str())
+            && self
+                .until
+                .as_ref()
+                .is_none_or(|t| r.recorded_at.as_str() < t.as_str())
+    }
+}
+
+fn split(list: &str) -> impl Iterator<Item = &str> {
+    list.split(',').map(str::trim).filter(|s| !s.is_empty())
+}
+
+/// Run `query` over `history` (oldest first). `400` for an unparsable
+/// filter, sort key or unit system, or a cursor no longer in the history.
+pub fn query(history: &VecDeque<ShotRecord>, query: &ShotsQuery) -> Result<Page, StatusCode> {
+    let filter = Filter::parse(query).ok_or(StatusCode::BAD_REQUEST)?;
+    let sort = query.sort.as_deref().unwrap_or("-time");
+    let (key, descending) = match sort.strip_prefix('-') {
+        Some(key) => (key, true),
+        None => (sort, false),
+    };
+    let key = SortKey::parse(key).ok_or(StatusCode::BAD_REQUEST)?;
+    let units = match query.units.as_deref() {
+        None => None,
+        Some("imperial") => Some(UnitSystem::Imperial),
+        Some("metric") => Some(UnitSystem::Metric),
+        Some(_) => return Err(StatusCode::BAD_REQUEST),
+    };
+
+    let mut matched: Vec<&ShotRecord> = history.iter().filter(|r| filter.matches(r)).collect();
+    if key == SortKey::Time {
+        if descending {
+            matched.reverse();
+        }
+    } else {
+        // Stable, so ties stay in time order; shots lacking the value go last
+        // either way.
+        matched.sort_by(|a, b| match (key.value(a), key.value(b)) {
+            (Some(x), Some(y)) if descending => y.total_cmp(&x),
+            (Some(x), Some(y)) => x.total_cmp(&y),
+            (a, b) => b.is_some().cmp(&a.is_some()),
+        });
+    }
+
+    let start = match &query.after {
+        Some(cursor) => {
+            matched
+                .iter()
+                .position(|r| &r.shot_id == cursor)
+                .ok_or(StatusCode::BAD_REQUEST)?
+                + 1
+        }
+        None => 0,
+    };
+    let mut page: Vec<&ShotRecord> = matched
+        .iter()
+        .skip(start)
+        .take(query.limit)
+        .copied()
+        .collect();
+    let next_cursor = (start + page.len() < matched.len())
+        .then(|| page.last().map(|r| r.shot_id.clone()))
+        .flatten();
+    // The original `?limit=N` contract: the last N shots, oldest first. The
+    // cursor still walks on to older shots.
+    if query.sort.is_none() {
+        page.reverse();
+    }
+
+    let fields: Option<Vec<&str>> = query.fields.as_deref().map(|f| split(f).collect());
+    let shots = page
+        .into_iter()
+        .map(|r| {
+            let mut r = r.clone();
+            if let Some(system) = units {
+                r.shot = r.shot.to_unit_system(system);
+            }
+            let value = serde_json::to_value(r).unwrap_or_default();
+            match &fields {
+                Some(fields) => project(&value, fields),
+                None => value,
+            }
+        })
+        .collect();
+    Ok(Page { shots, next_cursor })
+}
+
+/// Keep only `fields` (dotted paths) of `value`. Missing paths are skipped.
+fn project(value: &Value, fields: &[&str]) -> Value {
+    let mut out = Map::new();
+    for field in fields {
+        let path: Vec<&str> = field.split('.').collect();
+        let Some(found) = path.iter().try_fold(value, |v, key| v.get(key)) else {
+            continue;
+        };
+        let (last, parents) = path.split_last().expect("split yields one segment");
+        let mut target = &mut out;
+        for key in parents {
+            target = target
+                .entry(key.to_string())
+                .or_insert_with(|| Value::Object(Map::new()))
+                .as_object_mut()
+                .expect("projected parents are objects");
+        }
+        target.insert(last.to_string(), found.clone());
+    }
+    Value::Object(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flighthook::{BallFlight, ClubInfo, Distance, PlayerInfo};
+
+    fn game(player: &str, club: Club, mode: ShotDetectionMode) -> GameStateSnapshot {
+        GameStateSnapshot {
+            player_info: Some(PlayerInfo {
+                name: Some(player.into()),
+            }),
+            club_info: Some(ClubInfo { club }),
+            mode: Some(mode),
+            handed: None,
+        }
+    }
+
+    fn shot(actor: &str, n: u32, carry: Option<f64>) -> ShotData {
+        ShotData {
+            actor: actor.into(),
+            shot_number: n,
+            ball: Some(BallFlight {
+                carry_distance: carry.map(Distance::Yards),
+                ..Default::default()
+            }),
+            club: None,
+            impact: None,
+            estimated: false,
+        }
+    }
+
+    /// Six shots: two players, two actors, driver and 7-iron.
+    fn history() -> VecDeque<ShotRecord> {
+        let full = ShotDetectionMode::Full;
+        let specs = [
+            ("mevo.0", "Ann", Club::Driver, Some(250.0)),
+            ("mevo.0", "Ann", Club::Iron7, Some(160.0)),
+            ("manual", "Ann", Club::Iron7, None),
+            ("mevo.0", "Bob", Club::Driver, Some(270.0)),
+            ("mevo.0", "Bob", Club::Iron7, Some(150.0)),
+            ("r10.0", "Ann", Club::Driver, Some(240.0)),
+        ];
+        let mut out: VecDeque<ShotRecord> = VecDeque::new();
+        for (i, (actor, player, club, carry)) in specs.into_iter().enumerate() {
+            let n = i as u32 + 1;
+            let key = ShotKey::new(format!("id{n}"), n);
+            let mut r = record(
+                shot(actor, n, carry),
+                &key,
+                &game(player, club, full),
+                &FlighthookConfig::default(),
+                out.back(),
+            );
+            r.recorded_at = format!("2026-10-18T10:00:0{n}.000Z");
+            out.push_back(r);
+        }
+        out
+    }
+
+    fn ids(page: &Page) -> Vec<&str> {
+        page.shots
+            .iter()
+            .map(|s| s["shot_id"].as_str().unwrap())
+            .collect()
+    }
+
+    fn run(q: ShotsQuery) -> Result<Page, StatusCode> {
+        query(&history(), &q)
+    }
+
+    fn q() -> ShotsQuery {
+        ShotsQuery {
+            limit: 50,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn sessions_follow_player_changes() {
+        let sessions: Vec<u32> = history().iter().map(|r| r.session).collect();
+        assert_eq!(sessions, [1, 1, 1, 2, 2, 3]);
+    }
+
+    #[test]
+    fn pinned_monitor_records_its_own_mode() {
+        let mut config = FlighthookConfig::default();
+        let mut square = flighthook::SquareSection::default();
+        square.mode.pinned = Some(ShotDetectionMode::Putting);
+        config.square.insert("0".into(), square);
+        let game = game("Ann", Club::Driver, ShotDetectionMode::Full);
+        let key = ShotKey::new("id1", 1);
+
+        let putt = record(shot("square.0", 1, None), &key, &game, &config, None);
+        assert_eq!(putt.mode, Some(ShotDetectionMode::Putting));
+        let drive = record(shot("mevo.0", 1, None), &key, &game, &config, None);
+        assert_eq!(drive.mode, Some(ShotDetectionMode::Full));
+    }
+
+    #[test]
+    fn default_is_latest_shots_oldest_first() {
+        let page = run(ShotsQuery { limit: 3, ..q() }).unwrap();
+        assert_eq!(ids(&page), ["id4", "id5", "id6"]);
+        assert_eq!(page.next_cursor.as_deref(), Some("id4"));
+
+        let page = run(ShotsQuery {
+            sort: Some("-time".into()),
+            ..q()
+        })
+        .unwrap();
+        assert_eq!(ids(&page), ["id6", "id5", "id4", "id3", "id2", "id1"]);
+        assert_eq!(page.next_cursor, None);
+    }
+
+    #[test]
+    fn filters_combine() {
+        let page = run(ShotsQuery {
+            actor: Some("mevo.0,r10.0".into()),
+            club: Some("DR".into()),
+            sort: Some("time".into()),
+            ..q()
+        })
+        .unwrap();
+        assert_eq!(ids(&page), ["id1", "id4", "id6"]);
+
+        let page = run(ShotsQuery {
+            session: Some(2),
+            ..q()
+        })
+        .unwrap();
+        assert_eq!(ids(&page), ["id4", "id5"]);
+
+        let page = run(ShotsQuery {
+            since: Some("2026-10-18T12:00:02+02:00".into()),
+            until: Some("2026-10-18T10:00:04Z".into()),
+            ..q()
+        })
+        .unwrap();
+        assert_eq!(ids(&page), ["id2", "id3"]);
+
+        let page = run(ShotsQuery {
+            mode: Some("putting".into()),
+            ..q()
+        })
+        .unwrap();
+        assert!(page.shots.is_empty());
+    }
+
+    #[test]
+    fn cursor_walks_pages() {
+        let mut seen = Vec::new();
+        let mut after = None;
+        loop {
+            let page = run(ShotsQuery {
+                limit: 4,
+                after: after.clone(),
+                ..q()
+            })
+            .unwrap();
+            seen.push(ids(&page).join(","));
+            match page.next_cursor {
+                Some(cursor) => after = Some(cursor),
+                None => break,
+            }
+        }
+        assert_eq!(seen, ["id3,id4,id5,id6", "id1,id2"]);
+    }
+
+    #[test]
+    fn sort_by_metric_puts_missing_last() {
+        let page = run(ShotsQuery {
+            sort: Some("-carry".into()),
+            ..q()
+        })
+        .unwrap();
+        assert_eq!(ids(&page), ["id4", "id1", "id6", "id2", "id5", "id3"]);
+        let page = run(ShotsQuery {
+            sort: Some("carry".into()),
+            limit: 2,
+            ..q()
+        })
+        .unwrap();
+        assert_eq!(ids(&page), ["id5", "id2"]);
+        assert_eq!(page.next_cursor.as_deref(), Some("id2"));
+    }
+
+    #[test]
+    fn projection_keeps_requested_paths() {
+        let page = run(ShotsQuery {
+            fields: Some("shot_id,ball.carry_distance,nope.x".into()),
+            limit: 1,
+            sort: Some("time".into()),
+            ..q()
+        })
+        .unwrap();
+        assert_eq!(
+            page.shots[0],
+            serde_json::json!({ "shot_id": "id1", "ball": { "carry_distance": "250yd" } })
+        );
+    }
+
+    #[test]
+    fn bad_parameters_are_rejected() {
+        for bad in [
+            ShotsQuery {
+                club: Some("XX".into()),
+                ..q()
+            },
+            ShotsQuery {
+                mode: Some("bunker".into()),
+                ..q()
+            },
+            ShotsQuery {
+                since: Some("yesterday".into()),
+                ..q()
+            },
+            ShotsQuery {
+                sort: Some("-height".into()),
+                ..q()
+            },
+            ShotsQuery {
+                after: Some("evicted".into()),
+                ..q()
+            },
+            ShotsQuery {
+                units: Some("furlongs".into()),
+                ..q()
+            },
+        ] {
+            assert_eq!(run(bad).unwrap_err(), StatusCode::BAD_REQUEST);
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! TLS for the webserver — certificate loading and self-signed generation.
+//!
+//! Enabled per webserver section with `tls_cert` + `tls_key` (PEM files).
+//! With `tls_self_signed = true`, a missing certificate/key pair is generated
+//! on first start and reused afterwards.
+
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use rustls::ServerConfig;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+
+/// TLS settings resolved from a `WebserverSection`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsSettings {
+    pub cert: PathBuf,
+    pub key: PathBuf,
+    pub self_signed: bool,
+}
+
+impl TlsSettings {
+    pub fn from_section(section: &flighthook::WebserverSection) -> Option<Self> {
+        let (cert, key) = section.tls_paths()?;
+        Some(Self {
+            cert: PathBuf::from(cert),
+            key: PathBuf::from(key),
+            self_signed: section.tls_self_signed.unwrap_or(false),
+        })
+    }
+}
+
+/// Errors setting up TLS.
+#[derive(Debug)]
+pub enum TlsError {
+    Io(PathBuf, std::io::Error),
+    Pem(PathBuf, String),
+    Generate(rcgen::Error),
+    Config(rustls::Error),
+}
+
+impl fmt::Display for TlsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, e) => write!(f, "{}: {e}", path.display()),
+            Self::Pem(path, e) => write!(f, "{}: invalid PEM: {e}", path.display()),
+            Self::Generate(e) => write!(f, "self-signed certificate generation failed: {e}"),
+            Self::Config(e) => write!(f, "TLS config: {e}"),
+        }
+    }
+}
+
+/// Build the rustls server config, generating a self-signed pair first if
+/// configured and the files are missing.
+pub fn server_config(tls: &TlsSettings, addr: SocketAddr) -> Result<Arc<ServerConfig>, TlsError> {
+    if tls.self_signed && !tls.cert.exists() && !tls.key.exists() {
+        generate_self_signed(&tls.cert, &tls.key, addr)?;
+        tracing::info!(
+            "web server: generated self-signed certificate {}",
+            tls.cert.display()
+        );
+    }
+
+    let certs = CertificateDer::pem_file_iter(&tls.cert)
+        .and_then(|it| it.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| pem_error(&tls.cert, e))?;
+    let key = PrivateKeyDer::from_pem_file(&tls.key).map_err(|e| pem_error(&tls.key, e))?;
+
+    let config =
+        ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(TlsError::Config)?
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .map_err(TlsError::Config)?;
+    Ok(Arc::new(config))
+}
+
+fn pem_error(path: &Path, e: rustls::pki_types::pem::Error) -> TlsError {
+    match e {
+        rustls::pki_types::pem::Error::Io(io) => TlsError::Io(path.to_path_buf(), io),
+        other => TlsError::Pem(path.to_path_buf(), other.to_string()),
+    }
+}
+
+/// Names the self-signed certificate is valid for: localhost, loopback, and
+/// the bind address when it is a specific IP.
+fn subject_alt_names(addr: SocketAddr) -> Vec<String> {
+    let mut names = vec!["localhost".to_string(), "127.0.0.1".to_string()];
+    let ip = addr.ip();
+    if !ip.is_unspecified() && !ip.is_loopback() {
+        names.push(ip.to_string());
+    }
+    names
+}
+
+fn generate_self_signed(cert: &Path, key: &Path, addr: SocketAddr) -> Result<(), TlsError> {
+    let generated =
+        rcgen::generate_simple_self_signed(subject_alt_names(addr)).map_err(TlsError::Generate)?;
+    for (path, pem, private) in [
+        (cert, generated.cert.pem(), false),
+        (key, generated.key_pair.serialize_pem(), true),
+    ] {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| TlsError::Io(parent.to_path_buf(), e))?;
+        }
+        write_pem(path, &pem, private).map_err(|e| TlsError::Io(path.to_path_buf(), e))?;
+    }
+    Ok(())
+}
+
+/// Write a PEM file. A private key is created readable by its owner only.
+fn write_pem(path: &Path, pem: &str, private: bool) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    #[cfg(not(unix))]
+    let _ = private;
+    options.open(path)?.write_all(pem.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn self_signed_pair_is_generated_once_and_loads() {
+        let dir = std::env::temp_dir().join(format!("flighthook-tls-{}", std::process::id()));
+        let tls = TlsSettings {
+            cert: dir.join("cert.pem"),
+            key: dir.join("key.pem"),
+            self_signed: true,
+        };
+        let addr: SocketAddr = "0.0.0.0:5880".parse().unwrap();
+
+        server_config(&tls, addr).expect("generate + load");
+        let first = std::fs::read(&tls.cert).unwrap();
+        server_config(&tls, addr).expect("reload");
+        assert_eq!(first, std::fs::read(&tls.cert).unwrap(), "cert regenerated");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&tls.key).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600, "key readable by others");
+        }
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn missing_files_without_self_signed_fail() {
+        let tls = TlsSettings {
+            cert: PathBuf::from("/nonexistent/flighthook/cert.pem"),
+            key: PathBuf::from("/nonexistent/flighthook/key.pem"),
+            self_signed: false,
+        };
+        let err = server_config(&tls, "127.0.0.1:5880".parse().unwrap()).unwrap_err();
+        assert!(matches!(err, TlsError::Io(..)), "{err}");
+    }
+
+    #[test]
+    fn san_includes_specific_bind_ip() {
+        let names = subject_alt_names("192.168.1.20:5880".parse().unwrap());
+        assert!(names.contains(&"192.168.1.20".to_string()));
+        assert_eq!(subject_alt_names("0.0.0.0:5880".parse().unwrap()).len(), 2);
+    }
+}
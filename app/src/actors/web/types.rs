@@ -2,4 +2,7 @@
 //!
 //! All types live in the `flighthook` lib so the UI crate can share them.
 
-pub use flighthook::{ModeRequest, PostSettingsResponse, StatusResponse};
+pub use flighthook::{
+    ConfigHistoryEntry, HealthResponse, ModeRequest, PostSettingsResponse, PostShotRequest,
+    PostShotResponse, ProfilesResponse, StatusResponse,
+};
@@ -2,32 +2,59 @@
 //!
 //! Protocol (FRP-compliant with flighthook extensions):
 //!   1. Client sends:  `{ "kind": "start", "version": ["0.1.0"], "name": "My Dashboard" }`
+//!      (optionally with `kinds`, `actors`, `raw_payload` subscription filters)
 //!   2. Server negotiates version (highest mutually supported)
-//!   3. Server sends:  `{ "kind": "init", "version": "0.1.0", "actor_id": "ws.abc123", "global_state": { ... } }`
-//!   4. Server streams `FlighthookMessage` events
+//!   3. Server sends:  `{ "kind": "init", "version": "0.1.0", "actor_id": "ws.my-dashboard", "global_state": { ... } }`
+//!   4. Server streams `FlighthookMessage` events matching the subscription
+//!
+//! The `start` message may also ask for a replay of buffered shot lifecycle
+//! events (`resume_from` a sequence number, or the last `replay_shots` shots),
+//! sent after the status replay and before live streaming. Every streamed
+//! message carries a top-level `seq` extension field.
+//!
+//! The client can replace its subscription at any time with
+//! `{ "kind": "subscribe", "kinds": [...], "actors": [...], "raw_payload": false }`.
+//!
+//! After the handshake the client may publish `FlighthookMessage` frames of its
+//! own (shot lifecycle, device telemetry, club/player info, alerts). They are
+//! re-stamped with the session's actor ID, `ws.<name>` after the client's
+//! `name`, so an external program can act as a launch monitor without
+//! patching flighthook, and be routed to by that ID across reconnects.
 
+use std::collections::HashMap;
 use std::sync::Arc;
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use axum::extract::State;
 use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::http::{HeaderMap, Uri};
 use axum::response::IntoResponse;
 use futures_util::{SinkExt, StreamExt};
 
-use super::{WebState, emit_telemetry};
-use crate::state::SystemState;
+use super::backlog::{SequencedMessage, is_shot_lifecycle};
+use super::{WebState, auth, emit_telemetry, routes};
+use crate::bus::{self, Publisher};
 use crate::state::config;
-use flighthook::{FRP_VERSION, FlighthookEvent, FlighthookMessage, ShotDetectionMode};
+use flighthook::{
+    ActorStatus, ConfigAction, FRP_VERSION, FlighthookEvent, FlighthookMessage, Severity,
+    ShotDetectionMode, TokenScope,
+};
 
 /// GET /frp — upgrade to WebSocket.
+///
+/// A token on the upgrade request (header or `?token=`) is kept as a fallback
+/// for clients that do not send one in `start`.
 pub async fn ws_upgrade(
     State(state): State<Arc<WebState>>,
+    headers: HeaderMap,
+    uri: Uri,
     ws: WebSocketUpgrade,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_ws(socket, state))
+    let upgrade_token = auth::request_token(&headers, &uri);
+    ws.on_upgrade(move |socket| handle_ws(socket, state, upgrade_token))
 }
 
-async fn handle_ws(socket: WebSocket, state: Arc<WebState>) {
+async fn handle_ws(socket: WebSocket, state: Arc<WebState>, upgrade_token: Option<String>) {
     let (mut ws_tx, mut ws_rx) = socket.split();
 
     // Phase 1: Wait for "start" message from client and negotiate version
@@ -57,12 +84,31 @@ async fn handle_ws(socket: WebSocket, state: Arc<WebState>) {
         return;
     };
 
+    // Authenticate: reading the stream needs at least a `read` token.
+    let token = start.token.or(upgrade_token);
+    let scope = match auth::authorize(&state, token.as_deref(), TokenScope::Read) {
+        Ok(scope) => scope,
+        Err(status) => {
+            tracing::warn!("ws: client '{}' rejected ({status})", start.name);
+            let alert = serde_json::json!({
+                "kind": "alert",
+                "severity": "critical",
+                "message": "Unauthorized: a valid API token is required",
+            });
+            let _ = ws_tx.send(Message::text(alert.to_string())).await;
+            let _ = ws_tx.close().await;
+            return;
+        }
+    };
+
     let client_name = start.name;
+    let (filter_tx, mut filter_rx) = tokio::sync::watch::channel(start.subscription);
 
     // Phase 2: Send "init" response with actor_id and global state
-    let actor_id = format!("ws.{}", config::generate_id());
+    let session = SessionActor::claim(&state, &client_name);
+    let actor_id = session.id.clone();
     state.ws_count.fetch_add(1, Ordering::Relaxed);
-    emit_telemetry(&state, &state.bus_tx);
+    emit_telemetry(&state, &state.bus_tx).await;
     tracing::info!(
         "ws: client '{}' connected (actor_id={}, version={})",
         client_name,
@@ -70,11 +116,17 @@ async fn handle_ws(socket: WebSocket, state: Arc<WebState>) {
         negotiated_version
     );
 
+    // Subscribe to the feed before reading the sequence number and backlog,
+    // so nothing falls between the replay and the live stream.
+    let mut feed_rx = state.feed.subscribe();
+    let latest_seq = state.seq.load(Ordering::Relaxed);
+
     let global_state = state.root.game.snapshot();
     let init_msg = serde_json::json!({
         "kind": "init",
         "version": negotiated_version,
         "actor_id": actor_id,
+        "seq": latest_seq,
         "global_state": global_state,
     });
     if ws_tx
@@ -89,7 +141,10 @@ async fn handle_ws(socket: WebSocket, state: Arc<WebState>) {
     {
         let cache = state.cached_actor_status.read().await;
         for msg in cache.values() {
-            if let Ok(json) = serde_json::to_string(msg)
+            let Some(msg) = filter_rx.borrow().apply(msg.clone()) else {
+                continue;
+            };
+            if let Ok(json) = serde_json::to_string(&msg)
                 && ws_tx.send(Message::text(json)).await.is_err()
             {
                 return;
@@ -101,7 +156,10 @@ async fn handle_ws(socket: WebSocket, state: Arc<WebState>) {
     {
         let cache = state.cached_device_telemetry.read().await;
         for msg in cache.values() {
-            if let Ok(json) = serde_json::to_string(msg)
+            let Some(msg) = filter_rx.borrow().apply(msg.clone()) else {
+                continue;
+            };
+            if let Ok(json) = serde_json::to_string(&msg)
                 && ws_tx.send(Message::text(json)).await.is_err()
             {
                 return;
@@ -109,22 +167,103 @@ async fn handle_ws(socket: WebSocket, state: Arc<WebState>) {
         }
     }
 
-    // Phase 3: Stream bus events + receive commands
-    let mut bus_rx = state.bus_tx.subscribe();
+    // Replay buffered shot lifecycle events the client asked for
+    let replay = {
+        let backlog = state.shot_backlog.read().await;
+        match start.replay {
+            // A resume point ahead of the server means it restarted since
+            // the client's last session: everything buffered is new to it.
+            Replay::Since(from) if from > latest_seq => backlog.since(0),
+            Replay::Since(from) => {
+                if backlog.truncated_after(from) {
+                    let alert = backlog_gap_alert(&state, from);
+                    if let Ok(json) = serde_json::to_string(&alert)
+                        && ws_tx.send(Message::text(json)).await.is_err()
+                    {
+                        return;
+                    }
+                }
+                backlog.since(from)
+            }
+            Replay::LastShots(n) => backlog.last_shots(n),
+            Replay::None => Vec::new(),
+        }
+    };
+    // Live shot events at or below this were covered by the replay.
+    let mut replayed_through = replay.last().map_or(0, |e| e.seq);
+    // Highest sequence number sent so far, where a catch-up after feed lag
+    // resumes from.
+    let mut sent_through = latest_seq.max(replayed_through);
+    let subscription = filter_rx.borrow().clone();
+    for sequenced in replay {
+        let Some(msg) = subscription.apply(sequenced.msg) else {
+            continue;
+        };
+        let sequenced = SequencedMessage {
+            seq: sequenced.seq,
+            msg,
+        };
+        if let Ok(json) = serde_json::to_string(&sequenced.frame())
+            && ws_tx.send(Message::text(json)).await.is_err()
+        {
+            return;
+        }
+    }
 
+    // Phase 3: Stream bus events + receive commands
+    let send_state = Arc::clone(&state);
+    let send_actor = actor_id.clone();
     let mut send_task = tokio::spawn(async move {
         loop {
-            match bus_rx.recv().await {
-                Ok(msg) => {
-                    if let Ok(json) = serde_json::to_string(&msg)
-                        && ws_tx.send(Message::text(json)).await.is_err()
+            // A client too slow for the feed loses the events it skipped,
+            // except shot lifecycle events, which are replayed from the
+            // backlog (ahead of the older live events still queued).
+            let batch = match feed_rx.recv().await {
+                Ok(sequenced) => {
+                    if sequenced.seq <= replayed_through && is_shot_lifecycle(&sequenced.msg.event)
                     {
-                        break;
+                        continue;
                     }
+                    vec![sequenced]
                 }
                 Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
                 Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
-                    tracing::warn!("ws: lagged {n}");
+                    tracing::warn!("ws: {send_actor} lagged {n}, replaying missed shot events");
+                    let (truncated, missed) = {
+                        let backlog = send_state.shot_backlog.read().await;
+                        (
+                            backlog.truncated_after(sent_through),
+                            backlog.since(sent_through),
+                        )
+                    };
+                    if truncated {
+                        let alert = backlog_gap_alert(&send_state, sent_through);
+                        if let Ok(json) = serde_json::to_string(&alert)
+                            && ws_tx.send(Message::text(json)).await.is_err()
+                        {
+                            break;
+                        }
+                    }
+                    replayed_through = missed.last().map_or(replayed_through, |e| e.seq);
+                    missed
+                }
+            };
+            for sequenced in batch {
+                sent_through = sent_through.max(sequenced.seq);
+                if !visible_to(scope, &sequenced.msg.event) {
+                    continue;
+                }
+                let Some(msg) = filter_rx.borrow_and_update().apply(sequenced.msg) else {
+                    continue;
+                };
+                let sequenced = SequencedMessage {
+                    seq: sequenced.seq,
+                    msg,
+                };
+                if let Ok(json) = serde_json::to_string(&sequenced.frame())
+                    && ws_tx.send(Message::text(json)).await.is_err()
+                {
+                    return;
                 }
             }
         }
@@ -132,12 +271,54 @@ async fn handle_ws(socket: WebSocket, state: Arc<WebState>) {
 
     let ws_actor = actor_id.clone();
     let bus_tx = state.bus_tx.clone();
-    let system = Arc::clone(&state.root);
+    // Set once the client publishes device events, so its status is
+    // tracked like a built-in launch monitor.
+    let announced = Arc::new(AtomicBool::new(false));
+    let recv_announced = Arc::clone(&announced);
+    let recv_state = Arc::clone(&state);
     let mut recv_task = tokio::spawn(async move {
         while let Some(Ok(msg)) = ws_rx.next().await {
             match msg {
                 Message::Text(text) => {
-                    handle_ws_command(&text, &ws_actor, &bus_tx, &system);
+                    if let Some(sub) = parse_subscribe_message(&text) {
+                        tracing::debug!("ws: {ws_actor} updated subscription: {sub:?}");
+                        let _ = filter_tx.send(sub);
+                        continue;
+                    }
+                    let Some(msg) = parse_client_frame(&text, &ws_actor) else {
+                        continue;
+                    };
+                    if scope < TokenScope::Control {
+                        tracing::debug!("ws: {ws_actor} has a read-only token, ignoring publish");
+                        continue;
+                    }
+                    // Profile switches are held to the same rule as the REST
+                    // endpoint: no new validation errors.
+                    if let FlighthookEvent::ConfigCommand { action, .. } = &msg.event {
+                        let current = recv_state.root.system.snapshot();
+                        let validation = routes::validate_change(&current, action);
+                        if !validation.is_valid() {
+                            tracing::warn!(
+                                "ws: {ws_actor} {} rejected: {} validation error(s)",
+                                action.kind(),
+                                validation.errors.len()
+                            );
+                            continue;
+                        }
+                    }
+                    if is_device_event(&msg.event) && !recv_announced.swap(true, Ordering::Relaxed)
+                    {
+                        bus_tx
+                            .send_async(
+                                FlighthookMessage::new(FlighthookEvent::ActorStatus {
+                                    status: ActorStatus::Connected,
+                                    telemetry: HashMap::new(),
+                                })
+                                .actor(ws_actor.as_str()),
+                            )
+                            .await;
+                    }
+                    bus_tx.send_async(msg).await;
                 }
                 Message::Close(_) => break,
                 _ => {}
@@ -150,8 +331,21 @@ async fn handle_ws(socket: WebSocket, state: Arc<WebState>) {
         _ = &mut recv_task => send_task.abort(),
     }
 
+    if announced.load(Ordering::Relaxed) {
+        state
+            .bus_tx
+            .send_async(
+                FlighthookMessage::new(FlighthookEvent::ActorStatus {
+                    status: ActorStatus::Disconnected,
+                    telemetry: HashMap::new(),
+                })
+                .actor(actor_id.as_str()),
+            )
+            .await;
+    }
+
     state.ws_count.fetch_sub(1, Ordering::Relaxed);
-    emit_telemetry(&state, &state.bus_tx);
+    emit_telemetry(&state, &state.bus_tx).await;
     tracing::info!(
         "ws: client '{}' disconnected (actor_id={})",
         client_name,
@@ -159,11 +353,82 @@ async fn handle_ws(socket: WebSocket, state: Arc<WebState>) {
     );
 }
 
+/// Warning sent when the shot backlog no longer reaches back to `seq`.
+fn backlog_gap_alert(state: &WebState, seq: u64) -> FlighthookMessage {
+    FlighthookMessage::new(FlighthookEvent::Alert {
+        severity: Severity::Warn,
+        message: format!("Shot backlog no longer reaches seq {seq}; some shots were missed"),
+    })
+    .actor(state.actor_id.as_str())
+}
+
 /// Parsed result from a "start" handshake message.
 struct StartResult {
     name: String,
     /// Negotiated FRP version, or `None` if no compatible version found.
     version: Option<String>,
+    subscription: Subscription,
+    replay: Replay,
+    /// API token, if the client sent one.
+    token: Option<String>,
+}
+
+/// Shot backlog replay requested on `start`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Replay {
+    None,
+    /// Events with a sequence number after this one (`resume_from`).
+    Since(u64),
+    /// Every buffered event of the last N shots (`replay_shots`).
+    LastShots(usize),
+}
+
+/// Per-connection filter applied in the send task.
+///
+/// Empty `kinds` / `actors` lists match everything, so a plain `start`
+/// message keeps receiving every bus event.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+struct Subscription {
+    /// Event kinds to forward (serde `kind` tags, e.g. `"ball_flight"`).
+    #[serde(default)]
+    kinds: Vec<String>,
+    /// Actor IDs to forward (e.g. `"mevo.0"`).
+    #[serde(default)]
+    actors: Vec<String>,
+    /// Whether to keep `raw_payload` on forwarded messages.
+    #[serde(default = "default_raw_payload")]
+    raw_payload: bool,
+}
+
+fn default_raw_payload() -> bool {
+    true
+}
+
+impl Default for Subscription {
+    fn default() -> Self {
+        Self {
+            kinds: Vec::new(),
+            actors: Vec::new(),
+            raw_payload: true,
+        }
+    }
+}
+
+impl Subscription {
+    /// Return the message as it should be sent to this client, or `None`
+    /// if it is filtered out.
+    fn apply(&self, mut msg: FlighthookMessage) -> Option<FlighthookMessage> {
+        if !self.kinds.is_empty() && !self.kinds.iter().any(|k| k == msg.event.kind()) {
+            return None;
+        }
+        if !self.actors.is_empty() && !self.actors.contains(&msg.actor) {
+            return None;
+        }
+        if !self.raw_payload {
+            msg.raw_payload = None;
+        }
+        Some(msg)
+    }
 }
 
 /// Supported FRP versions, in ascending order.
@@ -181,6 +446,14 @@ fn parse_start_message(text: &str) -> Option<StartResult> {
         name: String,
         #[serde(default)]
         version: Vec<String>,
+        #[serde(default)]
+        token: Option<String>,
+        #[serde(default)]
+        resume_from: Option<u64>,
+        #[serde(default)]
+        replay_shots: Option<usize>,
+        #[serde(flatten)]
+        subscription: Subscription,
     }
     let msg: StartMsg = serde_json::from_str(text).ok()?;
     if msg.kind != "start" {
@@ -203,43 +476,342 @@ fn parse_start_message(text: &str) -> Option<StartResult> {
     Some(StartResult {
         name,
         version: negotiated,
+        subscription: msg.subscription,
+        // `resume_from` is exact, so it wins over `replay_shots`.
+        replay: match (msg.resume_from, msg.replay_shots) {
+            (Some(seq), _) => Replay::Since(seq),
+            (None, Some(n)) => Replay::LastShots(n),
+            (None, None) => Replay::None,
+        },
+        token: msg.token,
     })
 }
 
-/// Parse a client command and emit on the bus.
-/// Expected formats:
-///   `{ "cmd": "mode", "mode": "putting", "device": "mevo.0" }`
-fn handle_ws_command(
-    text: &str,
-    actor: &str,
-    bus_tx: &tokio::sync::broadcast::Sender<FlighthookMessage>,
-    _system: &SystemState,
-) {
+/// Parse a `{"kind": "subscribe", ...}` message replacing the client's filter.
+/// Omitted fields fall back to their defaults (all kinds, all actors, raw on).
+fn parse_subscribe_message(text: &str) -> Option<Subscription> {
+    #[derive(serde::Deserialize)]
+    struct SubscribeMsg {
+        kind: String,
+        #[serde(flatten)]
+        subscription: Subscription,
+    }
+    let msg: SubscribeMsg = serde_json::from_str(text).ok()?;
+    (msg.kind == "subscribe").then_some(msg.subscription)
+}
+
+/// Parse a client frame into a bus message stamped with the session's actor ID.
+///
+/// Accepts either a full `FlighthookMessage` envelope
+/// (`{ "device": "...", "event": { "kind": "ball_flight", ... } }`) or the
+/// command shapes `{ "cmd": "mode", "mode": "putting" }` and
+/// `{ "cmd": "profile", "profile": "putting green" }` (a `SwitchProfile`
+/// config command). The client's own `actor` field is ignored. Events a
+/// client may not publish (config, actor status) and unparseable frames
+/// yield `None`.
+pub(crate) fn parse_client_frame(text: &str, actor: &str) -> Option<FlighthookMessage> {
     #[derive(serde::Deserialize)]
     struct WsCmd {
         cmd: String,
         mode: Option<String>,
+        profile: Option<String>,
     }
 
-    let Ok(msg) = serde_json::from_str::<WsCmd>(text) else {
-        return;
+    let value: serde_json::Value = serde_json::from_str(text).ok()?;
+
+    if value.get("event").is_some() {
+        let mut msg: FlighthookMessage = serde_json::from_value(value).ok()?;
+        if !bus::may_publish(Publisher::WebSocket, &msg.event) {
+            tracing::debug!("ws: {actor} tried to publish a restricted event, ignoring");
+            return None;
+        }
+        msg.actor = actor.to_string();
+        return Some(msg);
+    }
+
+    let cmd: WsCmd = serde_json::from_value(value).ok()?;
+    if cmd.cmd == "profile" {
+        return Some(
+            FlighthookMessage::new(FlighthookEvent::ConfigCommand {
+                request_id: None,
+                action: Box::new(ConfigAction::SwitchProfile { name: cmd.profile? }),
+            })
+            .actor(actor),
+        );
+    }
+    if cmd.cmd.as_str() != "mode" {
+        return None;
+    }
+    let mode = match cmd.mode.as_deref() {
+        Some("full") => ShotDetectionMode::Full,
+        Some("putting") => ShotDetectionMode::Putting,
+        Some("chipping") => ShotDetectionMode::Chipping,
+        _ => return None,
     };
+    Some(
+        FlighthookMessage::new(FlighthookEvent::SetDetectionMode {
+            mode: Some(mode),
+            handed: None,
+        })
+        .actor(actor),
+    )
+}
+
+/// Actor ID for a client named `name`: `ws.` and the name lowercased, with
+/// runs of anything but letters, digits, `_` and `-` turned into `-`. A
+/// client without a usable name gets a random ID.
+pub(crate) fn client_actor_id(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.trim().chars().flat_map(char::to_lowercase) {
+        if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        format!("ws.{}", config::generate_id())
+    } else {
+        format!("ws.{slug}")
+    }
+}
+
+/// A session's hold on its actor ID, released when the session ends. A
+/// second client with the same name while the first is connected gets a
+/// random suffix, so the two never share an ID.
+struct SessionActor {
+    state: Arc<WebState>,
+    id: String,
+}
 
-    if msg.cmd.as_str() == "mode" {
-        let mode = match msg.mode.as_deref() {
-            Some("full") => Some(ShotDetectionMode::Full),
-            Some("putting") => Some(ShotDetectionMode::Putting),
-            Some("chipping") => Some(ShotDetectionMode::Chipping),
-            _ => None,
+impl SessionActor {
+    fn claim(state: &Arc<WebState>, name: &str) -> Self {
+        let wanted = client_actor_id(name);
+        let mut held = state.ws_actors.lock().unwrap_or_else(|e| e.into_inner());
+        let id = if held.contains(&wanted) {
+            format!("{wanted}-{}", config::generate_id())
+        } else {
+            wanted
         };
-        if let Some(m) = mode {
-            let _ = bus_tx.send(
-                FlighthookMessage::new(FlighthookEvent::SetDetectionMode {
-                    mode: Some(m),
-                    handed: None,
-                })
-                .actor(actor),
-            );
+        held.insert(id.clone());
+        Self {
+            state: Arc::clone(state),
+            id,
         }
     }
 }
+
+impl Drop for SessionActor {
+    fn drop(&mut self) {
+        self.state
+            .ws_actors
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&self.id);
+    }
+}
+
+/// Whether a client holding `scope` may see `event`.
+///
+/// Config commands carry whole config sections, API tokens included, so they
+/// are held to the same `admin` scope as `GET /api/settings`.
+fn visible_to(scope: TokenScope, event: &FlighthookEvent) -> bool {
+    scope >= TokenScope::Admin || !matches!(event, FlighthookEvent::ConfigCommand { .. })
+}
+
+/// Events that mark the client as a launch monitor.
+fn is_device_event(event: &FlighthookEvent) -> bool {
+    matches!(
+        event,
+        FlighthookEvent::ShotTrigger { .. }
+            | FlighthookEvent::BallFlight { .. }
+            | FlighthookEvent::ClubPath { .. }
+            | FlighthookEvent::FaceImpact { .. }
+            | FlighthookEvent::ShotFinished { .. }
+            | FlighthookEvent::DeviceTelemetry { .. }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn client_frame_restamped_with_session_actor() {
+        let text = r#"{
+            "actor": "mevo.0",
+            "device": "MyRig",
+            "event": {
+                "kind": "shot_trigger",
+                "key": { "shot_id": "550e8400-e29b-41d4-a716-446655440000", "shot_number": 7 }
+            }
+        }"#;
+        let msg = parse_client_frame(text, "ws.a1b2c3d4").unwrap();
+        assert_eq!(msg.actor, "ws.a1b2c3d4");
+        assert_eq!(msg.device.as_deref(), Some("MyRig"));
+        assert!(matches!(msg.event, FlighthookEvent::ShotTrigger { .. }));
+        assert!(is_device_event(&msg.event));
+    }
+
+    #[test]
+    fn actor_id_follows_the_client_name() {
+        assert_eq!(client_actor_id("Uneekor Bridge"), "ws.uneekor-bridge");
+        assert_eq!(client_actor_id("  My_Rig (bay 2) "), "ws.my_rig-bay-2");
+        let unnamed = client_actor_id(" ?! ");
+        assert!(unnamed.starts_with("ws.") && unnamed.len() > 3);
+        assert_ne!(unnamed, client_actor_id(""));
+    }
+
+    #[test]
+    fn club_info_frame_accepted() {
+        let text = r#"{ "event": { "kind": "club_info", "club_info": { "club": "7I" } } }"#;
+        let msg = parse_client_frame(text, "ws.a1b2c3d4").unwrap();
+        assert_eq!(msg.actor, "ws.a1b2c3d4");
+        assert!(!is_device_event(&msg.event));
+    }
+
+    #[test]
+    fn restricted_events_rejected() {
+        let status =
+            r#"{ "event": { "kind": "actor_status", "status": "connected", "telemetry": {} } }"#;
+        assert!(parse_client_frame(status, "ws.a1b2c3d4").is_none());
+        let command = r#"{ "event": { "kind": "config_command", "action": { "type": "remove", "id": "mevo.0" } } }"#;
+        assert!(parse_client_frame(command, "ws.a1b2c3d4").is_none());
+    }
+
+    #[test]
+    fn config_commands_hidden_below_admin() {
+        let mut config = flighthook::FlighthookConfig::default();
+        config.webserver.insert(
+            "0".into(),
+            flighthook::WebserverSection {
+                name: "Web Server".into(),
+                disabled: false,
+                bind: "0.0.0.0:5880".into(),
+                tokens: vec![flighthook::ApiToken {
+                    name: "admin".into(),
+                    token: "s3cret-admin-token".into(),
+                    scope: TokenScope::Admin,
+                }],
+                tls_cert: None,
+                tls_key: None,
+                tls_self_signed: None,
+            },
+        );
+        let msg = FlighthookMessage::new(FlighthookEvent::ConfigCommand {
+            request_id: None,
+            action: Box::new(ConfigAction::ReplaceAll {
+                config: Box::new(config),
+            }),
+        })
+        .actor("system");
+
+        let sent = |scope: TokenScope| {
+            visible_to(scope, &msg.event)
+                .then(|| Subscription::default().apply(msg.clone()))
+                .flatten()
+                .map(|m| serde_json::to_string(&m).unwrap())
+        };
+        assert_eq!(sent(TokenScope::Read), None);
+        assert_eq!(sent(TokenScope::Control), None);
+        assert!(
+            sent(TokenScope::Admin)
+                .unwrap()
+                .contains("s3cret-admin-token")
+        );
+    }
+
+    fn trigger(actor: &str) -> FlighthookMessage {
+        parse_client_frame(
+            r#"{ "event": { "kind": "shot_trigger", "key": { "shot_id": "a", "shot_number": 1 } } }"#,
+            actor,
+        )
+        .unwrap()
+        .raw_binary(vec![0xde, 0xad])
+    }
+
+    #[test]
+    fn start_without_filters_subscribes_to_everything() {
+        let start = parse_start_message(r#"{ "kind": "start", "version": ["0.1.0"] }"#).unwrap();
+        assert_eq!(start.subscription, Subscription::default());
+        let msg = start.subscription.apply(trigger("mevo.0")).unwrap();
+        assert!(msg.raw_payload.is_some());
+    }
+
+    #[test]
+    fn start_filters_kinds_actors_and_raw() {
+        let start = parse_start_message(
+            r#"{ "kind": "start", "version": ["0.1.0"], "kinds": ["shot_trigger"],
+                 "actors": ["mevo.0"], "raw_payload": false }"#,
+        )
+        .unwrap();
+        let sub = start.subscription;
+        let msg = sub.apply(trigger("mevo.0")).unwrap();
+        assert!(msg.raw_payload.is_none());
+        assert!(sub.apply(trigger("mevo.1")).is_none());
+
+        let status = FlighthookMessage::new(FlighthookEvent::ActorStatus {
+            status: ActorStatus::Connected,
+            telemetry: HashMap::new(),
+        })
+        .actor("mevo.0");
+        assert!(sub.apply(status).is_none());
+    }
+
+    #[test]
+    fn start_replay_options() {
+        let parse = |text: &str| parse_start_message(text).unwrap().replay;
+        assert_eq!(parse(r#"{ "kind": "start" }"#), Replay::None);
+        assert_eq!(
+            parse(r#"{ "kind": "start", "replay_shots": 5 }"#),
+            Replay::LastShots(5)
+        );
+        assert_eq!(
+            parse(r#"{ "kind": "start", "resume_from": 120, "replay_shots": 5 }"#),
+            Replay::Since(120)
+        );
+    }
+
+    #[test]
+    fn subscribe_message_replaces_filter() {
+        let sub =
+            parse_subscribe_message(r#"{ "kind": "subscribe", "kinds": ["alert"] }"#).unwrap();
+        assert_eq!(sub.kinds, vec!["alert".to_string()]);
+        assert!(sub.actors.is_empty());
+        assert!(sub.raw_payload);
+        assert!(parse_subscribe_message(r#"{ "kind": "start" }"#).is_none());
+        assert!(parse_client_frame(r#"{ "kind": "subscribe" }"#, "ws.x").is_none());
+    }
+
+    #[test]
+    fn legacy_mode_command_still_supported() {
+        let msg = parse_client_frame(r#"{ "cmd": "mode", "mode": "putting" }"#, "ws.x").unwrap();
+        assert_eq!(msg.actor, "ws.x");
+        assert!(matches!(
+            msg.event,
+            FlighthookEvent::SetDetectionMode {
+                mode: Some(ShotDetectionMode::Putting),
+                handed: None
+            }
+        ));
+        assert!(parse_client_frame(r#"{ "cmd": "mode", "mode": "bogus" }"#, "ws.x").is_none());
+        assert!(parse_client_frame(r#"{ "cmd": "profile" }"#, "ws.x").is_none());
+        assert!(parse_client_frame(r#"{ "cmd": "nope" }"#, "ws.x").is_none());
+        assert!(parse_client_frame("not json", "ws.x").is_none());
+    }
+
+    #[test]
+    fn profile_command_switches_profile() {
+        let text = r#"{ "cmd": "profile", "profile": "putting green" }"#;
+        let msg = parse_client_frame(text, "ws.x").unwrap();
+        assert_eq!(msg.actor, "ws.x");
+        let FlighthookEvent::ConfigCommand { request_id, action } = msg.event else {
+            panic!("expected a config command");
+        };
+        assert_eq!(request_id, None);
+        assert!(
+            matches!(*action, ConfigAction::SwitchProfile { ref name } if name == "putting green")
+        );
+    }
+}
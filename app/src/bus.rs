@@ -1,12 +1,108 @@
-//! Bus abstraction layer — wraps `tokio::sync::broadcast` so callers never
-//! touch the broadcast types directly.
+//! Bus abstraction layer — wraps the tokio channels so callers never touch
+//! them directly.
+//!
+//! The bus has two lanes. Shot lifecycle and config events (see
+//! [`is_critical`]) go through a bounded queue per subscriber: a sender
+//! waits for room rather than dropping them. Everything else (status,
+//! telemetry, alerts) goes through a `broadcast` channel, where a subscriber
+//! that falls behind skips the oldest events. Every message is numbered
+//! (`FlighthookMessage::bus_seq`) as it is sent and each receiver merges the
+//! two lanes back in that order, so one sender's messages arrive in the order
+//! it sent them. Events a receiver misses either way are counted in its
+//! actor's [`BusLag`].
+//!
+//! Actor threads are plain threads, not tokio tasks. They either drain the
+//! bus with the non-blocking `BusReceiver::poll()` or block in
+//! `recv_timeout()`/`wait()`, which park the thread until a message arrives,
+//! the actor's `ShutdownFlag` is set, or the timeout passes. Async code (the
+//! web server) uses `recv()` and `send_async()`, which wait without parking
+//! a runtime thread.
 
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::future::{Future, poll_fn};
+use std::pin::pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::time::{Duration, Instant};
 
-use tokio::sync::broadcast;
+use tokio::sync::mpsc::error::{TryRecvError, TrySendError};
+use tokio::sync::{Notify, broadcast, mpsc};
 
-use flighthook::FlighthookMessage;
+use flighthook::{FlighthookEvent, FlighthookMessage, Severity, now_ms};
+
+/// Longest a sender waits for room in a subscriber's critical queue before
+/// dropping the event for that subscriber and marking it stalled.
+const CRITICAL_SEND_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Source of messages that come from outside flighthook's own code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Publisher {
+    /// A `/frp` WebSocket client.
+    WebSocket,
+    /// A WebAssembly plugin (`[plugin.<idx>]`).
+    Plugin,
+    /// A Rhai script (`[script.<idx>]`).
+    Script,
+    /// An external process (`[process.<idx>]`).
+    Process,
+}
+
+/// Whether `publisher` may put `event` on the bus.
+///
+/// Config commands/outcomes go through the settings API, never straight onto
+/// the bus. Plugins, scripts and processes are actors and report their own
+/// `actor_status`, and may act as integrations (`shot_delivered`,
+/// `shot_suppressed`); a WebSocket client's status is derived from its
+/// connection, so it may do neither.
+pub fn may_publish(publisher: Publisher, event: &FlighthookEvent) -> bool {
+    match event {
+        FlighthookEvent::ShotTrigger { .. }
+        | FlighthookEvent::BallFlight { .. }
+        | FlighthookEvent::ClubPath { .. }
+        | FlighthookEvent::FaceImpact { .. }
+        | FlighthookEvent::ShotFinished { .. }
+        | FlighthookEvent::DeviceTelemetry { .. }
+        | FlighthookEvent::Alert { .. }
+        | FlighthookEvent::SetDetectionMode { .. }
+        | FlighthookEvent::PlayerInfo { .. }
+        | FlighthookEvent::ClubInfo { .. } => true,
+        FlighthookEvent::ActorStatus { .. }
+        | FlighthookEvent::ShotDelivered { .. }
+        | FlighthookEvent::ShotSuppressed { .. } => publisher != Publisher::WebSocket,
+        FlighthookEvent::ConfigCommand { .. } | FlighthookEvent::ConfigOutcome { .. } => false,
+    }
+}
+
+/// Whether an actor subscribed to `kinds` (by `event.kind`) is handed
+/// `event`. Empty means every kind except config commands/outcomes: those
+/// carry whole config sections, API tokens included, so a plugin, script or
+/// process only sees them if it lists them by name.
+pub fn subscribed(kinds: &[String], event: &FlighthookEvent) -> bool {
+    if kinds.is_empty() {
+        return !matches!(
+            event,
+            FlighthookEvent::ConfigCommand { .. } | FlighthookEvent::ConfigOutcome { .. }
+        );
+    }
+    kinds.iter().any(|k| k == event.kind())
+}
+
+/// Events that must reach every subscriber: the shot lifecycle and config
+/// commands/outcomes.
+pub fn is_critical(event: &FlighthookEvent) -> bool {
+    matches!(
+        event,
+        FlighthookEvent::ShotTrigger { .. }
+            | FlighthookEvent::BallFlight { .. }
+            | FlighthookEvent::ClubPath { .. }
+            | FlighthookEvent::FaceImpact { .. }
+            | FlighthookEvent::ShotFinished { .. }
+            | FlighthookEvent::ShotDelivered { .. }
+            | FlighthookEvent::ShotSuppressed { .. }
+            | FlighthookEvent::ConfigCommand { .. }
+            | FlighthookEvent::ConfigOutcome { .. }
+    )
+}
 
 // ---------------------------------------------------------------------------
 // PollError
@@ -19,6 +115,302 @@ pub enum PollError {
     Shutdown,
 }
 
+// ---------------------------------------------------------------------------
+// ShutdownFlag
+// ---------------------------------------------------------------------------
+
+/// Per-actor stop signal, shared by the registry and the actor's bus
+/// wrappers. Setting it wakes the actor if it is blocked in
+/// `BusReceiver::recv_timeout()` or `wait()`.
+#[derive(Default)]
+pub struct ShutdownFlag {
+    set: AtomicBool,
+    notify: Notify,
+}
+
+impl ShutdownFlag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&self) {
+        self.set.store(true, Ordering::Relaxed);
+        self.notify.notify_waiters();
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.set.load(Ordering::Relaxed)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Heartbeat
+// ---------------------------------------------------------------------------
+
+/// When an actor last touched the bus. Shared by its `BusSender` and
+/// `BusReceiver`s: every `send()` and `poll()` beats. Read by the registry
+/// for `GET /api/health`.
+pub struct Heartbeat {
+    created: Instant,
+    /// Milliseconds after `created` of the last beat.
+    last: AtomicU64,
+}
+
+impl Heartbeat {
+    pub fn new() -> Self {
+        Self {
+            created: Instant::now(),
+            last: AtomicU64::new(0),
+        }
+    }
+
+    pub fn beat(&self) {
+        let ms = u64::try_from(self.created.elapsed().as_millis()).unwrap_or(u64::MAX);
+        self.last.store(ms, Ordering::Relaxed);
+    }
+
+    /// Time since the last beat (or since creation, if none yet).
+    pub fn elapsed(&self) -> Duration {
+        let last = Duration::from_millis(self.last.load(Ordering::Relaxed));
+        self.created.elapsed().saturating_sub(last)
+    }
+}
+
+impl Default for Heartbeat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// BusLag
+// ---------------------------------------------------------------------------
+
+/// Events an actor's receivers never got. Shared like the heartbeat; read by
+/// the registry and reported by the web server as `bus_lagged` /
+/// `bus_dropped` actor telemetry.
+#[derive(Default)]
+pub struct BusLag {
+    /// Best-effort events skipped because the receiver fell behind.
+    lagged: AtomicU64,
+    /// Critical events dropped after waiting for room in a full queue.
+    dropped: AtomicU64,
+}
+
+/// Snapshot of a [`BusLag`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LagCounts {
+    pub lagged: u64,
+    pub dropped: u64,
+}
+
+impl BusLag {
+    pub fn counts(&self) -> LagCounts {
+        LagCounts {
+            lagged: self.lagged.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+
+    fn record_lagged(&self, n: u64) {
+        tracing::warn!("bus: lagged, dropped {n} events");
+        self.lagged.fetch_add(n, Ordering::Relaxed);
+    }
+
+    fn record_dropped(&self, msg: &FlighthookMessage) {
+        tracing::warn!(
+            "bus: subscriber queue full, dropped {} from '{}'",
+            msg.event.kind(),
+            msg.actor
+        );
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Bus
+// ---------------------------------------------------------------------------
+
+/// One subscriber's critical queue, as seen by senders.
+#[derive(Clone)]
+struct CriticalQueue {
+    tx: mpsc::Sender<FlighthookMessage>,
+    /// Actor the subscriber belongs to, if any, for alerts.
+    owner: Option<String>,
+    lag: Arc<BusLag>,
+    /// Set when a send timed out; further events are dropped without
+    /// waiting until the subscriber takes one from the queue.
+    stalled: Arc<AtomicBool>,
+}
+
+struct Lanes {
+    events: broadcast::Sender<FlighthookMessage>,
+    critical: Mutex<Vec<CriticalQueue>>,
+    capacity: usize,
+    seq: AtomicU64,
+}
+
+/// The shared bus. Cheap to clone; the lanes close when the last clone is
+/// dropped.
+#[derive(Clone)]
+pub struct Bus {
+    lanes: Arc<Lanes>,
+}
+
+impl Bus {
+    /// Create a bus holding up to `capacity` events per lane per subscriber.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lanes: Arc::new(Lanes {
+                events: broadcast::channel(capacity).0,
+                critical: Mutex::new(Vec::new()),
+                capacity,
+                seq: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Send a message as is (the caller sets `actor`). May block for up to
+    /// a second per stalled subscriber if `msg` is critical, parking the
+    /// calling thread; async code uses [`send_async`](Self::send_async).
+    ///
+    /// Assigns `bus_seq`, and stamps `timestamp_ms` if the message has none.
+    pub fn send(&self, msg: FlighthookMessage) {
+        self.publish(msg, None);
+    }
+
+    /// Like [`send`](Self::send), for code running on a tokio runtime: waits
+    /// for room in a full critical queue without blocking the thread.
+    pub async fn send_async(&self, msg: FlighthookMessage) {
+        for (queue, msg) in self.deliver(msg, None) {
+            let sent = tokio::time::timeout(CRITICAL_SEND_TIMEOUT, queue.tx.send(msg.clone()));
+            if sent.await.is_err() {
+                self.stall(&queue, &msg);
+            }
+        }
+    }
+
+    /// A receiver with its own shutdown flag, heartbeat, and lag counters,
+    /// for code that is not a registered actor.
+    pub fn subscribe(&self) -> BusReceiver {
+        self.subscribe_with(
+            None,
+            Arc::new(ShutdownFlag::new()),
+            Arc::new(Heartbeat::new()),
+            Arc::new(BusLag::default()),
+        )
+    }
+
+    fn subscribe_with(
+        &self,
+        owner: Option<String>,
+        shutdown: Arc<ShutdownFlag>,
+        heartbeat: Arc<Heartbeat>,
+        lag: Arc<BusLag>,
+    ) -> BusReceiver {
+        let (tx, critical) = mpsc::channel(self.lanes.capacity);
+        let stalled = Arc::new(AtomicBool::new(false));
+        self.lanes
+            .critical
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(CriticalQueue {
+                tx,
+                owner,
+                lag: Arc::clone(&lag),
+                stalled: Arc::clone(&stalled),
+            });
+        BusReceiver {
+            events: self.lanes.events.subscribe(),
+            critical,
+            next_event: None,
+            next_critical: None,
+            stalled,
+            shutdown,
+            heartbeat,
+            lag,
+        }
+    }
+
+    /// Number and deliver `msg`, waiting (parked) for room in full critical
+    /// queues. `own` is the sending actor's lag counters: the sender never
+    /// waits on its own receivers' queues, which it cannot drain while it is
+    /// blocked here.
+    fn publish(&self, msg: FlighthookMessage, own: Option<&Arc<BusLag>>) {
+        for (queue, msg) in self.deliver(msg, own) {
+            let deadline = Instant::now() + CRITICAL_SEND_TIMEOUT;
+            if block_until(deadline, queue.tx.send(msg.clone())).is_none() {
+                self.stall(&queue, &msg);
+            }
+        }
+    }
+
+    /// Number `msg` and hand it to every subscriber with room for it.
+    /// Returns the critical queues that are full and worth waiting on.
+    fn deliver(
+        &self,
+        mut msg: FlighthookMessage,
+        own: Option<&Arc<BusLag>>,
+    ) -> Vec<(CriticalQueue, FlighthookMessage)> {
+        msg.timestamp_ms.get_or_insert_with(now_ms);
+        msg.bus_seq = Some(self.lanes.seq.fetch_add(1, Ordering::Relaxed));
+        if !is_critical(&msg.event) {
+            let _ = self.lanes.events.send(msg);
+            return Vec::new();
+        }
+        let queues = {
+            let mut queues = self
+                .lanes
+                .critical
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            queues.retain(|q| !q.tx.is_closed());
+            queues.clone()
+        };
+        let mut full = Vec::new();
+        for queue in queues {
+            let msg = match queue.tx.try_send(msg.clone()) {
+                Ok(()) | Err(TrySendError::Closed(_)) => continue,
+                Err(TrySendError::Full(msg)) => msg,
+            };
+            let own_queue = own.is_some_and(|lag| Arc::ptr_eq(lag, &queue.lag));
+            if own_queue || queue.stalled.load(Ordering::Relaxed) {
+                self.dropped(&queue, &msg);
+            } else {
+                full.push((queue, msg));
+            }
+        }
+        full
+    }
+
+    /// A send to `queue` timed out: stop waiting on it until it catches up.
+    fn stall(&self, queue: &CriticalQueue, msg: &FlighthookMessage) {
+        queue.stalled.store(true, Ordering::Relaxed);
+        self.dropped(queue, msg);
+    }
+
+    /// Count a critical event `queue` never got. A lost `shot_finished`
+    /// means a whole shot is missing downstream, so it is also raised as an
+    /// alert.
+    fn dropped(&self, queue: &CriticalQueue, msg: &FlighthookMessage) {
+        queue.lag.record_dropped(msg);
+        let FlighthookEvent::ShotFinished { key } = &msg.event else {
+            return;
+        };
+        let owner = queue.owner.as_deref().unwrap_or("bus");
+        self.send(
+            FlighthookMessage::new(FlighthookEvent::Alert {
+                severity: Severity::Error,
+                message: format!(
+                    "{owner} fell behind on the bus and missed shot #{} from '{}'",
+                    key.shot_number, msg.actor
+                ),
+            })
+            .actor(owner),
+        );
+    }
+}
+
 // ---------------------------------------------------------------------------
 // BusSender
 // ---------------------------------------------------------------------------
@@ -26,20 +418,20 @@ pub enum PollError {
 /// Cloneable sender that auto-stamps `actor` on every outbound message.
 pub struct BusSender {
     actor_id: String,
-    inner: broadcast::Sender<FlighthookMessage>,
-    shutdown: Arc<AtomicBool>,
+    bus: Bus,
+    shutdown: Arc<ShutdownFlag>,
+    heartbeat: Arc<Heartbeat>,
+    lag: Arc<BusLag>,
 }
 
 impl BusSender {
-    pub fn new(
-        actor_id: String,
-        inner: broadcast::Sender<FlighthookMessage>,
-        shutdown: Arc<AtomicBool>,
-    ) -> Self {
+    pub fn new(actor_id: String, bus: Bus, shutdown: Arc<ShutdownFlag>) -> Self {
         Self {
             actor_id,
-            inner,
+            bus,
             shutdown,
+            heartbeat: Arc::new(Heartbeat::new()),
+            lag: Arc::new(BusLag::default()),
         }
     }
 
@@ -47,25 +439,44 @@ impl BusSender {
         &self.actor_id
     }
 
-    /// Access the underlying broadcast sender (e.g. for WebState).
-    pub fn raw_sender(&self) -> &broadcast::Sender<FlighthookMessage> {
-        &self.inner
+    /// The shutdown flag this sender and its receivers share.
+    pub fn shutdown(&self) -> &Arc<ShutdownFlag> {
+        &self.shutdown
     }
 
-    /// Send a message, auto-stamping actor from the actor ID.
-    /// The message's timestamp is already set by `FlighthookMessage::new()`.
+    /// The heartbeat this sender and its receivers beat.
+    pub fn heartbeat(&self) -> &Arc<Heartbeat> {
+        &self.heartbeat
+    }
+
+    /// Events this sender's receivers have missed.
+    pub fn lag(&self) -> &Arc<BusLag> {
+        &self.lag
+    }
+
+    /// Access the underlying bus (e.g. for WebState).
+    pub fn bus(&self) -> &Bus {
+        &self.bus
+    }
+
+    /// Send a message, auto-stamping actor from the actor ID. Its
+    /// `timestamp_ms` is the origin time from `FlighthookMessage::new()`;
+    /// the bus assigns `bus_seq`.
     pub fn send(&self, mut msg: FlighthookMessage) {
         msg.actor = self.actor_id.clone();
-        let _ = self.inner.send(msg);
+        self.heartbeat.beat();
+        self.bus.publish(msg, Some(&self.lag));
     }
 
     /// Create a new receiver subscribed to this bus, sharing this sender's
-    /// shutdown flag.
+    /// shutdown flag, heartbeat, and lag counters.
     pub fn subscribe(&self) -> BusReceiver {
-        BusReceiver {
-            inner: self.inner.subscribe(),
-            shutdown: Arc::clone(&self.shutdown),
-        }
+        self.bus.subscribe_with(
+            Some(self.actor_id.clone()),
+            Arc::clone(&self.shutdown),
+            Arc::clone(&self.heartbeat),
+            Arc::clone(&self.lag),
+        )
     }
 }
 
@@ -73,8 +484,10 @@ impl Clone for BusSender {
     fn clone(&self) -> Self {
         Self {
             actor_id: self.actor_id.clone(),
-            inner: self.inner.clone(),
+            bus: self.bus.clone(),
             shutdown: Arc::clone(&self.shutdown),
+            heartbeat: Arc::clone(&self.heartbeat),
+            lag: Arc::clone(&self.lag),
         }
     }
 }
@@ -83,16 +496,25 @@ impl Clone for BusSender {
 // BusReceiver
 // ---------------------------------------------------------------------------
 
-/// Receiver wrapper. Holds the broadcast Receiver and a shutdown flag.
+/// Receiver wrapper. Holds both lanes, a shutdown flag, and the actor's
+/// heartbeat and lag counters.
 pub struct BusReceiver {
-    inner: broadcast::Receiver<FlighthookMessage>,
-    shutdown: Arc<AtomicBool>,
+    events: broadcast::Receiver<FlighthookMessage>,
+    critical: mpsc::Receiver<FlighthookMessage>,
+    /// Next message from each lane, held until the other lane is checked so
+    /// the lower sequence number goes first. `wait()` leaves messages here.
+    next_event: Option<FlighthookMessage>,
+    next_critical: Option<FlighthookMessage>,
+    stalled: Arc<AtomicBool>,
+    shutdown: Arc<ShutdownFlag>,
+    heartbeat: Arc<Heartbeat>,
+    lag: Arc<BusLag>,
 }
 
 impl BusReceiver {
     /// Check whether this actor's shutdown flag has been set.
     pub fn is_shutdown(&self) -> bool {
-        self.shutdown.load(Ordering::Relaxed)
+        self.shutdown.is_set()
     }
 
     /// Non-blocking drain: returns the next message, `Ok(None)` if empty,
@@ -101,29 +523,417 @@ impl BusReceiver {
         if self.is_shutdown() {
             return Err(PollError::Shutdown);
         }
+        self.heartbeat.beat();
+        if !self.fill()? {
+            return Ok(None);
+        }
+        Ok(self.take_next())
+    }
+
+    /// Blocking receive: returns the next message as soon as one arrives,
+    /// `Ok(None)` once `timeout` has passed without one, or
+    /// `Err(PollError::Shutdown)` (immediately when the shutdown flag is set).
+    /// Parks the calling thread; not for use on a tokio runtime thread.
+    pub fn recv_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<Option<FlighthookMessage>, PollError> {
+        if self.is_shutdown() {
+            return Err(PollError::Shutdown);
+        }
+        if self.block(timeout)? {
+            return self.poll();
+        }
+        Ok(None)
+    }
+
+    /// Block like [`recv_timeout`](Self::recv_timeout), but leave the message
+    /// for the next `poll()`. For loops that service a device or socket
+    /// between bus messages: use in place of a sleep, then drain with `poll()`
+    /// as before.
+    pub fn wait(&mut self, timeout: Duration) {
+        if !self.is_shutdown() {
+            let _ = self.block(timeout);
+        }
+    }
+
+    /// Async receive for code running on a tokio runtime: the next message,
+    /// or `Err(PollError::Shutdown)` once the bus closes or the shutdown flag
+    /// is seen set.
+    pub async fn recv(&mut self) -> Result<FlighthookMessage, PollError> {
         loop {
-            match self.inner.try_recv() {
-                Ok(msg) => return Ok(Some(msg)),
-                Err(broadcast::error::TryRecvError::Empty) => return Ok(None),
-                Err(broadcast::error::TryRecvError::Closed) => return Err(PollError::Shutdown),
-                Err(broadcast::error::TryRecvError::Lagged(n)) => {
-                    tracing::warn!("bus: lagged, dropped {n} events");
-                    continue;
+            if let Some(msg) = self.poll()? {
+                return Ok(msg);
+            }
+            self.arrival().await?;
+        }
+    }
+
+    /// Wait until a message is ready for `poll()` (`Ok(true)`), `timeout`
+    /// passes (`Ok(false)`), or shutdown.
+    fn block(&mut self, timeout: Duration) -> Result<bool, PollError> {
+        if self.fill()? {
+            return Ok(true);
+        }
+        let deadline = Instant::now() + timeout;
+        let shutdown = Arc::clone(&self.shutdown);
+        let woke = {
+            let mut stop = pin!(shutdown.notify.notified());
+            let mut arrival = pin!(self.arrival());
+            block_until(
+                deadline,
+                poll_fn(|cx| {
+                    // Register for the wakeup before checking, so a `set()`
+                    // in between is not missed.
+                    if stop.as_mut().poll(cx).is_ready() || shutdown.is_set() {
+                        return Poll::Ready(Err(PollError::Shutdown));
+                    }
+                    arrival.as_mut().poll(cx)
+                }),
+            )
+        };
+        self.heartbeat.beat();
+        woke.map_or(Ok(false), |r| r.map(|()| true))
+    }
+
+    /// Move whatever is ready on each lane into its slot, without blocking.
+    /// `Ok(true)` if a message is ready to take.
+    fn fill(&mut self) -> Result<bool, PollError> {
+        let mut closed = false;
+        while self.next_event.is_none() {
+            match self.events.try_recv() {
+                Ok(msg) => self.next_event = Some(msg),
+                Err(broadcast::error::TryRecvError::Empty) => break,
+                Err(broadcast::error::TryRecvError::Closed) => {
+                    closed = true;
+                    break;
                 }
+                Err(broadcast::error::TryRecvError::Lagged(n)) => self.lag.record_lagged(n),
+            }
+        }
+        if self.next_critical.is_none() {
+            match self.critical.try_recv() {
+                Ok(msg) => self.next_critical = Some(msg),
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => closed = true,
+            }
+        }
+        if self.next_event.is_some() || self.next_critical.is_some() {
+            Ok(true)
+        } else if closed {
+            Err(PollError::Shutdown)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Take the filled slot with the lower sequence number.
+    fn take_next(&mut self) -> Option<FlighthookMessage> {
+        let critical_first = match (&self.next_event, &self.next_critical) {
+            (Some(event), Some(critical)) => critical.bus_seq < event.bus_seq,
+            (None, Some(_)) => true,
+            _ => false,
+        };
+        if critical_first {
+            self.stalled.store(false, Ordering::Relaxed);
+            self.next_critical.take()
+        } else {
+            self.next_event.take()
+        }
+    }
+
+    /// Wait for the next message on either lane and put it in its slot.
+    /// Only called with both slots empty.
+    async fn arrival(&mut self) -> Result<(), PollError> {
+        let Self {
+            events,
+            critical,
+            next_event,
+            next_critical,
+            lag,
+            ..
+        } = self;
+        let mut event = pin!(next_broadcast(events, lag));
+        poll_fn(|cx| {
+            if let Poll::Ready(received) = critical.poll_recv(cx) {
+                let Some(msg) = received else {
+                    return Poll::Ready(Err(PollError::Shutdown));
+                };
+                *next_critical = Some(msg);
+                return Poll::Ready(Ok(()));
             }
+            event
+                .as_mut()
+                .poll(cx)
+                .map_ok(|msg| *next_event = Some(msg))
+        })
+        .await
+    }
+}
+
+/// Next message from the best-effort lane, counting lag.
+async fn next_broadcast(
+    events: &mut broadcast::Receiver<FlighthookMessage>,
+    lag: &BusLag,
+) -> Result<FlighthookMessage, PollError> {
+    loop {
+        match events.recv().await {
+            Ok(msg) => return Ok(msg),
+            Err(broadcast::error::RecvError::Closed) => return Err(PollError::Shutdown),
+            Err(broadcast::error::RecvError::Lagged(n)) => lag.record_lagged(n),
         }
     }
 }
 
-// ---------------------------------------------------------------------------
-// Convenience: create BusReceiver from raw broadcast receiver
-// ---------------------------------------------------------------------------
+/// Drive `fut` on the current thread until it completes (`Some`) or
+/// `deadline` passes (`None`), parking between wakeups. The bus futures need
+/// only a waker, not a tokio runtime.
+fn block_until<F: Future>(deadline: Instant, fut: F) -> Option<F::Output> {
+    struct Unpark(std::thread::Thread);
 
-impl From<broadcast::Receiver<FlighthookMessage>> for BusReceiver {
-    fn from(inner: broadcast::Receiver<FlighthookMessage>) -> Self {
-        Self {
-            inner,
-            shutdown: Arc::new(AtomicBool::new(false)),
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(Unpark(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut fut = pin!(fut);
+    loop {
+        if let Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+            return Some(out);
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return None;
         }
+        std::thread::park_timeout(deadline - now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flighthook::{ActorStatus, ShotDetectionMode, ShotKey};
+    use std::collections::HashMap;
+
+    fn receiver() -> (BusSender, BusReceiver) {
+        let sender = BusSender::new("test".into(), Bus::new(16), Arc::new(ShutdownFlag::new()));
+        let receiver = sender.subscribe();
+        (sender, receiver)
+    }
+
+    fn mode_message() -> FlighthookMessage {
+        FlighthookMessage::new(FlighthookEvent::SetDetectionMode {
+            mode: Some(ShotDetectionMode::Putting),
+            handed: None,
+        })
+    }
+
+    fn trigger(n: u32) -> FlighthookMessage {
+        FlighthookMessage::new(FlighthookEvent::ShotTrigger {
+            key: ShotKey {
+                shot_id: format!("shot-{n}"),
+                shot_number: n,
+            },
+        })
+    }
+
+    fn status(n: u32) -> FlighthookMessage {
+        FlighthookMessage::new(FlighthookEvent::ActorStatus {
+            status: ActorStatus::Connected,
+            telemetry: HashMap::from([("n".into(), n.to_string())]),
+        })
+    }
+
+    fn shot_number(msg: &FlighthookMessage) -> Option<u32> {
+        match &msg.event {
+            FlighthookEvent::ShotTrigger { key } => Some(key.shot_number),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn publish_policy_per_publisher() {
+        let command = FlighthookEvent::ConfigCommand {
+            request_id: None,
+            action: Box::new(flighthook::ConfigAction::SwitchProfile { name: "a".into() }),
+        };
+        for publisher in [
+            Publisher::WebSocket,
+            Publisher::Plugin,
+            Publisher::Script,
+            Publisher::Process,
+        ] {
+            assert!(may_publish(publisher, &trigger(1).event));
+            assert!(!may_publish(publisher, &command));
+        }
+        assert!(!may_publish(Publisher::WebSocket, &status(1).event));
+        assert!(may_publish(Publisher::Plugin, &status(1).event));
+    }
+
+    #[test]
+    fn config_commands_only_reach_actors_that_list_them() {
+        let command = FlighthookEvent::ConfigCommand {
+            request_id: None,
+            action: Box::new(flighthook::ConfigAction::SwitchProfile { name: "a".into() }),
+        };
+        assert!(subscribed(&[], &trigger(1).event));
+        assert!(!subscribed(&[], &command));
+        assert!(subscribed(&["config_command".into()], &command));
+        assert!(!subscribed(&["shot_finished".into()], &trigger(1).event));
+    }
+
+    #[test]
+    fn recv_timeout_wakes_on_message_shutdown_or_timeout() {
+        let (sender, mut rx) = receiver();
+        assert!(matches!(
+            rx.recv_timeout(Duration::from_millis(20)),
+            Ok(None)
+        ));
+
+        let from = sender.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(30));
+            from.send(mode_message());
+        });
+        let start = Instant::now();
+        let msg = rx.recv_timeout(Duration::from_secs(10)).unwrap().unwrap();
+        assert_eq!(msg.actor, "test");
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        let shutdown = Arc::clone(&sender.shutdown);
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(30));
+            shutdown.set();
+        });
+        let start = Instant::now();
+        assert!(rx.recv_timeout(Duration::from_secs(10)).is_err());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn wait_leaves_the_message_for_poll() {
+        let (sender, mut rx) = receiver();
+        sender.send(mode_message());
+        rx.wait(Duration::from_secs(10));
+        assert!(rx.poll().unwrap().is_some());
+        assert!(rx.poll().unwrap().is_none());
+    }
+
+    #[test]
+    fn critical_events_survive_a_slow_receiver_in_order() {
+        let bus = Bus::new(4);
+        let sender = BusSender::new("monitor".into(), bus.clone(), Arc::new(ShutdownFlag::new()));
+        let reader = BusSender::new("reader".into(), bus, Arc::new(ShutdownFlag::new()));
+        let mut rx = reader.subscribe();
+
+        let writer = std::thread::spawn(move || {
+            for n in 0..50 {
+                sender.send(status(n));
+                sender.send(trigger(n));
+            }
+        });
+        let mut shots = Vec::new();
+        while shots.len() < 50 {
+            if let Some(msg) = rx.recv_timeout(Duration::from_secs(5)).unwrap() {
+                shots.extend(shot_number(&msg));
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        writer.join().unwrap();
+
+        assert_eq!(shots, (0..50).collect::<Vec<_>>());
+        let lag = reader.lag().counts();
+        assert!(lag.lagged > 0, "status events should have been skipped");
+        assert_eq!(lag.dropped, 0);
+    }
+
+    #[test]
+    fn stalled_receiver_costs_one_timeout_then_drops() {
+        let bus = Bus::new(4);
+        let sender = BusSender::new("monitor".into(), bus.clone(), Arc::new(ShutdownFlag::new()));
+        let stuck = BusSender::new("stuck".into(), bus, Arc::new(ShutdownFlag::new()));
+        let mut rx = stuck.subscribe();
+
+        let start = Instant::now();
+        for n in 0..20 {
+            sender.send(trigger(n));
+        }
+        let elapsed = start.elapsed();
+        assert!(elapsed >= CRITICAL_SEND_TIMEOUT);
+        assert!(elapsed < CRITICAL_SEND_TIMEOUT * 3);
+        assert_eq!(stuck.lag().counts().dropped, 16);
+
+        // The queue still holds the first four, and draining it clears the
+        // stall: the next event waits for room again.
+        let kept: Vec<u32> = std::iter::from_fn(|| rx.poll().unwrap())
+            .filter_map(|m| shot_number(&m))
+            .collect();
+        assert_eq!(kept, [0, 1, 2, 3]);
+        sender.send(trigger(20));
+        assert_eq!(shot_number(&rx.poll().unwrap().unwrap()), Some(20));
+    }
+
+    #[test]
+    fn send_async_waits_for_room_without_blocking_the_runtime() {
+        let bus = Bus::new(2);
+        let reader = BusSender::new("reader".into(), bus.clone(), Arc::new(ShutdownFlag::new()));
+        let mut rx = reader.subscribe();
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+
+        // The reader runs on the sender's thread, so a parked send would
+        // never see room and would time out.
+        let shots = rt.block_on(async move {
+            let reading = tokio::spawn(async move {
+                let mut shots = Vec::new();
+                while shots.len() < 10 {
+                    shots.extend(shot_number(&rx.recv().await.unwrap()));
+                }
+                shots
+            });
+            for n in 0..10 {
+                bus.send_async(trigger(n)).await;
+            }
+            reading.await.unwrap()
+        });
+        assert_eq!(shots, (0..10).collect::<Vec<_>>());
+        assert_eq!(reader.lag().counts().dropped, 0);
+    }
+
+    #[test]
+    fn missed_shot_is_raised_as_an_alert() {
+        let bus = Bus::new(1);
+        let sender = BusSender::new("monitor".into(), bus.clone(), Arc::new(ShutdownFlag::new()));
+        let stuck = BusSender::new("stuck".into(), bus.clone(), Arc::new(ShutdownFlag::new()));
+        let _stuck_rx = stuck.subscribe();
+        let mut watcher = bus.subscribe();
+
+        let mut alerts = Vec::new();
+        for n in 0..3 {
+            sender.send(FlighthookMessage::new(FlighthookEvent::ShotFinished {
+                key: ShotKey {
+                    shot_id: format!("shot-{n}"),
+                    shot_number: n,
+                },
+            }));
+            while let Some(msg) = watcher.poll().unwrap() {
+                if let FlighthookEvent::Alert { message, .. } = msg.event {
+                    alerts.push((msg.actor, message));
+                }
+            }
+        }
+
+        // The first shot fits; the second times out and stalls the queue,
+        // the third is dropped without waiting.
+        assert_eq!(stuck.lag().counts().dropped, 2);
+        assert_eq!(alerts.len(), 2, "{alerts:?}");
+        assert_eq!(alerts[0].0, "stuck");
+        assert!(alerts[0].1.contains("shot #1 from 'monitor'"), "{alerts:?}");
+        assert!(alerts[1].1.contains("shot #2"), "{alerts:?}");
     }
 }
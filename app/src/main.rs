@@ -8,7 +8,6 @@ use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
 use clap::Parser;
-use tokio::sync::broadcast;
 use tracing_subscriber::EnvFilter;
 
 mod actors;
@@ -16,8 +15,7 @@ mod bus;
 mod state;
 
 use actors::Actor;
-use bus::BusSender;
-use flighthook::FlighthookMessage;
+use bus::{Bus, BusSender, ShutdownFlag};
 use state::SystemState;
 
 #[derive(Parser, Debug, Clone)]
@@ -32,6 +30,24 @@ struct Config {
     headless: bool,
 }
 
+/// Webserver that also serves the native GUI on loopback: the first enabled
+/// one, when every enabled webserver uses TLS.
+#[cfg(feature = "gui")]
+fn gui_loopback_webserver(config: &flighthook::FlighthookConfig) -> Option<String> {
+    let mut enabled: Vec<_> = config
+        .webserver
+        .iter()
+        .filter(|(_, w)| !w.disabled && w.bind.parse::<std::net::SocketAddr>().is_ok())
+        .collect();
+    enabled.sort_by_key(|(index, _)| *index);
+    if enabled.iter().any(|(_, w)| w.tls_paths().is_none()) {
+        return None;
+    }
+    enabled
+        .first()
+        .map(|(index, _)| format!("webserver.{index}"))
+}
+
 // ---------------------------------------------------------------------------
 // Main
 // ---------------------------------------------------------------------------
@@ -48,19 +64,18 @@ fn main() -> anyhow::Result<()> {
 
     let cli = Config::parse();
 
-    // Load (or create) config file
+    // Config file (loaded, or created, by `SystemState::new`)
     let config_path = cli
         .config
         .clone()
         .unwrap_or_else(state::config::default_config_path);
-    let _persisted = state::config::load(&config_path);
 
     // Create tokio runtime manually -- eframe::run_native() needs the main thread
     let rt = tokio::runtime::Runtime::new()?;
     let _guard = rt.enter();
 
     // Single unified bus
-    let (bus_tx, _) = broadcast::channel::<FlighthookMessage>(1024);
+    let bus_tx = Bus::new(1024);
 
     // Build shared state root
     let (system_state, game_writer) = SystemState::new(config_path);
@@ -76,24 +91,42 @@ fn main() -> anyhow::Result<()> {
                     "0".into(),
                     flighthook::WebserverSection {
                         name: "Web Server".into(),
+                        disabled: false,
                         bind: "127.0.0.1:5880".into(),
+                        tokens: Vec::new(),
+                        tls_cert: None,
+                        tls_key: None,
+                        tls_self_signed: None,
                     },
                 );
             });
         }
     }
 
+    // The native GUI's HTTP/WS clients have no TLS. If every webserver uses
+    // TLS, the first one also serves plain HTTP on a loopback port for it.
+    #[cfg(feature = "gui")]
+    if !cli.headless
+        && let Some(webserver) = gui_loopback_webserver(&state.system.snapshot())
+    {
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")?.local_addr()?;
+        state.set_gui_loopback(state::GuiLoopback { webserver, addr });
+    }
+
     // System actor — always-on internal housekeeping (GameState updates via writer).
     // Must be fully up before other actors start so no bus events are missed.
     {
-        let shutdown = Arc::new(AtomicBool::new(false));
-        let sender = BusSender::new("system".into(), bus_tx.clone(), Arc::clone(&shutdown));
+        let sender = BusSender::new(
+            "system".into(),
+            bus_tx.clone(),
+            Arc::new(ShutdownFlag::new()),
+        );
         let receiver = sender.subscribe();
         let (actor, ready_rx) =
             actors::system::SystemActor::new(game_writer, Arc::clone(&state), bus_tx.clone());
-        actor.start(Arc::clone(&state), sender, receiver);
+        let thread = actor.start(Arc::clone(&state), sender.clone(), receiver)?;
         ready_rx.recv().expect("system actor failed to start");
-        state.register_actor("system".into(), Box::new(actor), shutdown);
+        state.register_actor("system".into(), Box::new(actor), Ok(thread), &sender);
     }
 
     // Start all actors from config (launch monitors, integrations, webserver)
@@ -104,19 +137,17 @@ fn main() -> anyhow::Result<()> {
         actors::start_actor(ra.id, ra.actor, &state, &bus_tx);
     }
 
-    // Drain bus (keeps broadcast channel healthy when no other subscriber)
+    // Restart actors whose thread dies.
+    let supervisor_shutdown = Arc::new(AtomicBool::new(false));
+    actors::supervisor::spawn(
+        Arc::clone(&state),
+        bus_tx.clone(),
+        Arc::clone(&supervisor_shutdown),
+    )?;
+
+    // Drain bus (keeps the broadcast lane healthy when no other subscriber)
     let mut drain_rx = bus_tx.subscribe();
-    let drain_handle = tokio::spawn(async move {
-        loop {
-            match drain_rx.recv().await {
-                Ok(_) => {}
-                Err(broadcast::error::RecvError::Closed) => break,
-                Err(broadcast::error::RecvError::Lagged(n)) => {
-                    tracing::warn!("drain subscriber lagged, dropped {n} events");
-                }
-            }
-        }
-    });
+    let drain_handle = tokio::spawn(async move { while drain_rx.recv().await.is_ok() {} });
 
     if cli.headless {
         tracing::info!("running headless (no native GUI)");
@@ -124,14 +155,31 @@ fn main() -> anyhow::Result<()> {
     } else {
         #[cfg(feature = "gui")]
         {
-            let web_addr: std::net::SocketAddr = state
-                .system
-                .snapshot()
+            let snap = state.system.snapshot();
+            // A plain webserver, or else the loopback listener set up above.
+            let plain = snap
                 .webserver
-                .values()
-                .next()
-                .and_then(|w| w.bind.parse().ok())
-                .ok_or_else(|| anyhow::anyhow!("no valid webserver bind address in config"))?;
+                .iter()
+                .filter(|(_, w)| !w.disabled && w.tls_paths().is_none())
+                .find_map(|(index, w)| Some((index, w.bind.parse().ok()?)));
+            let loopback = || {
+                snap.webserver.keys().find_map(|index| {
+                    let addr = state.gui_loopback_for(&format!("webserver.{index}"))?;
+                    Some((index, addr))
+                })
+            };
+            let (index, web_addr): (&String, std::net::SocketAddr) =
+                plain.or_else(loopback).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "no enabled webserver with a valid bind address in config \
+                         (the native GUI needs one; or run with --headless)"
+                    )
+                })?;
+            let webserver = &snap.webserver[index];
+            // The native GUI is the local operator: hand it the broadest token.
+            if let Some(token) = webserver.tokens.iter().max_by_key(|t| t.scope) {
+                flighthook_ui::net::set_api_token(token.token.clone());
+            }
             let gui_url = if web_addr.ip().is_unspecified() {
                 format!("http://127.0.0.1:{}", web_addr.port())
             } else {
@@ -174,10 +222,11 @@ fn main() -> anyhow::Result<()> {
 
     // Shutdown — stop all actors (including webserver) via registry
     tracing::info!("shutting down...");
+    supervisor_shutdown.store(true, std::sync::atomic::Ordering::Relaxed);
     for id in state.actor_ids() {
         state.stop_actor(&id);
     }
-    // Drop bus_tx closes the broadcast channel as secondary signal
+    // Drop bus_tx closes the bus as secondary signal
     drop(bus_tx);
     drain_handle.abort();
 
@@ -3,14 +3,18 @@
 //! Handles the TOML config file (~/.config/flighthook/config.toml) with
 //! type-prefixed sections: `[mevo.<id>]`, `[gspro.<id>]`, etc.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::path::{Path, PathBuf};
-use std::sync::RwLock;
+use std::sync::{Mutex, RwLock};
+use std::time::SystemTime;
 
 use serde::Deserialize;
 
 // Re-export config types from schemas for crate-internal use
-pub use flighthook::{FlighthookConfig, MevoSection};
+pub use flighthook::{ConfigHistoryEntry, FlighthookConfig, MevoSection, PostSettingsResponse};
+
+/// Number of prior configs kept in the change history.
+const MAX_HISTORY: usize = 50;
 
 /// Build a global ID from a type prefix and index: `"mevo.0"`, `"gspro.0"`, etc.
 pub fn global_id(prefix: &str, index: &str) -> String {
@@ -77,6 +81,11 @@ impl LegacyFlighthookConfig {
             default_units: self.default_units,
             chipping_clubs: self.chipping_clubs,
             putting_clubs: self.putting_clubs,
+            active_profile: flighthook::DEFAULT_PROFILE.into(),
+            supervisor: Default::default(),
+            health: Default::default(),
+            routing: HashMap::new(),
+            dedup: Default::default(),
             webserver,
             mevo: self.mevo,
             r10: HashMap::new(),
@@ -85,6 +94,10 @@ impl LegacyFlighthookConfig {
             openconnect_server: HashMap::new(),
             gspro: self.gspro,
             random_club: self.random_club,
+            plugin: HashMap::new(),
+            script: HashMap::new(),
+            process: HashMap::new(),
+            profiles: HashMap::new(),
         }
     }
 }
@@ -92,12 +105,17 @@ impl LegacyFlighthookConfig {
 /// Load persisted config from disk. If the file does not exist, creates it
 /// with all-defaults and returns that. Migrates legacy singleton `[webserver]`
 /// to the indexed `[webserver.0]` format.
+///
+/// Validation problems are logged; the config is used as-is. A file that does
+/// not parse at all is copied to `config.invalid.toml` before falling back to
+/// defaults, since the next save overwrites it.
 pub fn load(path: &Path) -> FlighthookConfig {
     match std::fs::read_to_string(path) {
         Ok(contents) => {
             match toml::from_str::<FlighthookConfig>(&contents) {
                 Ok(config) => {
                     tracing::info!("loaded config from {}", path.display());
+                    log_validation(path, &config);
                     config
                 }
                 Err(e) => {
@@ -107,10 +125,19 @@ pub fn load(path: &Path) -> FlighthookConfig {
                             tracing::info!("migrating legacy config from {}", path.display());
                             let config = legacy.into_current();
                             save_to(path, &config);
+                            log_validation(path, &config);
                             config
                         }
                         Err(_) => {
-                            tracing::warn!("failed to parse {}: {e}", path.display());
+                            let backup = path.with_extension("invalid.toml");
+                            tracing::error!(
+                                "failed to parse {}: {e}; using defaults (original kept at {})",
+                                path.display(),
+                                backup.display()
+                            );
+                            if let Err(e) = std::fs::write(&backup, &contents) {
+                                tracing::warn!("failed to write {}: {e}", backup.display());
+                            }
                             FlighthookConfig::default()
                         }
                     }
@@ -130,6 +157,19 @@ pub fn load(path: &Path) -> FlighthookConfig {
     }
 }
 
+/// Log the validation errors and warnings of a loaded config.
+fn log_validation(path: &Path, config: &FlighthookConfig) {
+    let result = config.validate();
+    for e in &result.errors {
+        let section = e.section.as_deref().unwrap_or("config");
+        tracing::error!("{}: {section}: {}", path.display(), e.message);
+    }
+    for w in &result.warnings {
+        let section = w.section.as_deref().unwrap_or("config");
+        tracing::warn!("{}: {section}: {}", path.display(), w.message);
+    }
+}
+
 /// Write config to a specific path. Creates parent dirs if needed. Never panics.
 pub fn save_to(path: &Path, config: &FlighthookConfig) {
     if let Some(dir) = path.parent()
@@ -150,10 +190,38 @@ pub fn save_to(path: &Path, config: &FlighthookConfig) {
     }
 }
 
+/// Read and parse the config file, without legacy migration or fallbacks.
+fn read_file(path: &Path) -> Result<FlighthookConfig, String> {
+    let contents = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    toml::from_str(&contents).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+/// Modification time and length of the config file, if it exists.
+fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
 // ---------------------------------------------------------------------------
 // Cached config — RwLock-backed, no external lock
 // ---------------------------------------------------------------------------
 
+/// What the config file held when it was last read or written.
+struct DiskState {
+    /// Modification time and length, to notice edits without reading.
+    stamp: Option<(SystemTime, u64)>,
+    /// Parsed contents. `None` when the file did not parse.
+    config: Option<FlighthookConfig>,
+}
+
+/// An edit to the config file made outside flighthook.
+pub enum FileChange {
+    /// The file parsed; the config it now holds.
+    Changed(Box<FlighthookConfig>),
+    /// The file no longer parses. Carries the parse error.
+    Invalid(String),
+}
+
 /// Cached configuration backed by a TOML file.
 ///
 /// Reads are cheap (RwLock read guard + clone). Writes acquire the write lock,
@@ -164,15 +232,25 @@ pub fn save_to(path: &Path, config: &FlighthookConfig) {
 pub struct SystemConfig {
     path: PathBuf,
     inner: RwLock<FlighthookConfig>,
+    /// Prior configs, oldest first. Persisted next to the config file.
+    history: RwLock<VecDeque<ConfigHistoryEntry>>,
+    disk: Mutex<DiskState>,
 }
 
 impl SystemConfig {
     /// Load config from disk (or create defaults) and cache it.
     pub fn new(path: PathBuf) -> Self {
         let config = load(&path);
+        let history = load_history(&history_path(&path));
+        let disk = DiskState {
+            stamp: file_stamp(&path),
+            config: read_file(&path).ok(),
+        };
         Self {
             path,
             inner: RwLock::new(config),
+            history: RwLock::new(history),
+            disk: Mutex::new(disk),
         }
     }
 
@@ -182,16 +260,239 @@ impl SystemConfig {
     }
 
     /// Mutate the cached config in place, then persist to disk.
+    ///
+    /// The write is skipped when the file already holds the result, so a
+    /// reload of an externally edited file does not reformat it.
     pub fn update(&self, f: impl FnOnce(&mut FlighthookConfig)) {
         let mut guard = self.inner.write().unwrap_or_else(|e| e.into_inner());
         f(&mut guard);
+        let mut disk = self.disk.lock().unwrap_or_else(|e| e.into_inner());
+        if disk.config.as_ref() == Some(&*guard) {
+            return;
+        }
         save_to(&self.path, &guard);
+        disk.stamp = file_stamp(&self.path);
+        disk.config = Some(guard.clone());
     }
 
-    /// Replace the entire cached config and persist to disk.
-    pub fn replace(&self, new: FlighthookConfig) {
-        let mut guard = self.inner.write().unwrap_or_else(|e| e.into_inner());
-        *guard = new;
-        save_to(&self.path, &guard);
+    /// Check the config file for an edit made outside flighthook since it
+    /// was last read or written. Cheap when nothing changed: only the
+    /// file's metadata is read. A missing file is not a change.
+    pub fn poll_file(&self) -> Option<FileChange> {
+        let stamp = file_stamp(&self.path)?;
+        let mut disk = self.disk.lock().unwrap_or_else(|e| e.into_inner());
+        if disk.stamp == Some(stamp) {
+            return None;
+        }
+        disk.stamp = Some(stamp);
+        match read_file(&self.path) {
+            Ok(config) => {
+                if disk.config.as_ref() == Some(&config) {
+                    return None;
+                }
+                disk.config = Some(config.clone());
+                Some(FileChange::Changed(Box::new(config)))
+            }
+            Err(e) => {
+                disk.config = None;
+                Some(FileChange::Invalid(e))
+            }
+        }
+    }
+
+    // ----- Change history -----
+
+    /// Record a processed config change. `previous` is the config before it;
+    /// nothing is recorded when the change left the config as it was.
+    pub fn record_change(
+        &self,
+        previous: FlighthookConfig,
+        source: &str,
+        request_id: Option<String>,
+        action: &str,
+        scope: Option<String>,
+        outcome: PostSettingsResponse,
+    ) {
+        if previous == self.snapshot() {
+            return;
+        }
+        let mut history = self.history.write().unwrap_or_else(|e| e.into_inner());
+        let id = history.back().map_or(1, |e| e.id + 1);
+        if history.len() >= MAX_HISTORY {
+            history.pop_front();
+        }
+        history.push_back(ConfigHistoryEntry {
+            id,
+            recorded_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            source: source.to_string(),
+            request_id,
+            action: action.to_string(),
+            scope,
+            previous,
+            outcome,
+        });
+        save_history(&history_path(&self.path), &history);
+    }
+
+    /// The change history, newest first.
+    pub fn history(&self) -> Vec<ConfigHistoryEntry> {
+        let history = self.history.read().unwrap_or_else(|e| e.into_inner());
+        history.iter().rev().cloned().collect()
+    }
+
+    /// The history entry with this ID, if it is still kept.
+    pub fn history_entry(&self, id: u64) -> Option<ConfigHistoryEntry> {
+        let history = self.history.read().unwrap_or_else(|e| e.into_inner());
+        history.iter().find(|e| e.id == id).cloned()
+    }
+}
+
+/// `config.toml` -> `config.history.json`, next to it.
+fn history_path(config_path: &Path) -> PathBuf {
+    config_path.with_extension("history.json")
+}
+
+/// Load the change history. A missing or unreadable file starts it empty.
+fn load_history(path: &Path) -> VecDeque<ConfigHistoryEntry> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+            tracing::warn!("failed to parse {}: {e}", path.display());
+            VecDeque::new()
+        }),
+        Err(e) => {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!("failed to read {}: {e}", path.display());
+            }
+            VecDeque::new()
+        }
+    }
+}
+
+/// Write the change history. Never panics.
+fn save_history(path: &Path, history: &VecDeque<ConfigHistoryEntry>) {
+    match serde_json::to_string_pretty(history) {
+        Ok(contents) => {
+            if let Err(e) = std::fs::write(path, contents) {
+                tracing::warn!("failed to write {}: {e}", path.display());
+            }
+        }
+        Err(e) => {
+            tracing::warn!("failed to serialize config history: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_config(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("flighthook-config-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir.join("config.toml")
+    }
+
+    fn change(config: &SystemConfig, units: flighthook::UnitSystem) {
+        let previous = config.snapshot();
+        config.update(|c| c.default_units = units);
+        config.record_change(
+            previous,
+            "web",
+            None,
+            "replace_all",
+            None,
+            PostSettingsResponse::default(),
+        );
+    }
+
+    #[test]
+    fn history_records_prior_config_newest_first() {
+        let path = temp_config("order");
+        let config = SystemConfig::new(path.clone());
+        let original = config.snapshot().default_units;
+        change(&config, flighthook::UnitSystem::Metric);
+        change(&config, flighthook::UnitSystem::Imperial);
+
+        let history = config.history();
+        let ids: Vec<u64> = history.iter().map(|e| e.id).collect();
+        assert_eq!(ids, [2, 1]);
+        assert_eq!(history[1].previous.default_units, original);
+        assert_eq!(
+            history[0].previous.default_units,
+            flighthook::UnitSystem::Metric
+        );
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn unchanged_config_is_not_recorded() {
+        let path = temp_config("noop");
+        let config = SystemConfig::new(path.clone());
+        let units = config.snapshot().default_units;
+        change(&config, units);
+        assert!(config.history().is_empty());
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn history_is_bounded_and_persisted() {
+        let path = temp_config("bounded");
+        let config = SystemConfig::new(path.clone());
+        for i in 0..MAX_HISTORY + 5 {
+            let units = if i % 2 == 0 {
+                flighthook::UnitSystem::Metric
+            } else {
+                flighthook::UnitSystem::Imperial
+            };
+            change(&config, units);
+        }
+        let history = config.history();
+        assert_eq!(history.len(), MAX_HISTORY);
+        assert!(config.history_entry(1).is_none());
+
+        let reloaded = SystemConfig::new(path.clone());
+        let ids: Vec<u64> = reloaded.history().iter().map(|e| e.id).collect();
+        assert_eq!(ids, history.iter().map(|e| e.id).collect::<Vec<_>>());
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn poll_file_reports_outside_edits_only() {
+        let path = temp_config("poll");
+        let config = SystemConfig::new(path.clone());
+        assert!(config.poll_file().is_none());
+
+        // Our own write is not a change.
+        config.update(|c| c.default_units = flighthook::UnitSystem::Metric);
+        assert!(config.poll_file().is_none());
+
+        let mut edited = config.snapshot();
+        edited.putting_clubs.clear();
+        std::fs::write(&path, toml::to_string_pretty(&edited).unwrap()).unwrap();
+        match config.poll_file() {
+            Some(FileChange::Changed(c)) => assert_eq!(*c, edited),
+            _ => panic!("expected a change"),
+        }
+        assert!(config.poll_file().is_none());
+
+        // Applying the edit leaves the file as written.
+        let written = std::fs::read_to_string(&path).unwrap();
+        config.update(|c| *c = edited.clone());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), written);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn poll_file_reports_unparseable_file() {
+        let path = temp_config("poll-invalid");
+        let config = SystemConfig::new(path.clone());
+        std::fs::write(&path, "default_units = [").unwrap();
+        assert!(matches!(config.poll_file(), Some(FileChange::Invalid(_))));
+
+        // The next save replaces the broken file, even with the same config.
+        config.update(|_| {});
+        assert_eq!(read_file(&path).unwrap(), config.snapshot());
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
     }
 }
@@ -3,23 +3,72 @@ mod game;
 
 pub use game::{GameState, GameStateWriter};
 
-use std::collections::HashMap;
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{Duration, Instant};
 
-use crate::actors::{Actor, ReconfigureOutcome};
-use crate::bus::BusSender;
+use crate::actors::{Actor, ActorThread, ReconfigureOutcome};
+use crate::bus::{BusLag, BusSender, Heartbeat, LagCounts, ShutdownFlag};
 use config::SystemConfig;
 
+/// A registered actor, with what is needed to stop and supervise it.
+struct ActorEntry {
+    actor: Box<dyn Actor>,
+    shutdown: Arc<ShutdownFlag>,
+    /// The actor's thread, until `reap_actors()` has reported its exit.
+    thread: Option<ActorThread>,
+    started: Instant,
+    heartbeat: Arc<Heartbeat>,
+    lag: Arc<BusLag>,
+}
+
+/// An actor whose thread ended without the actor being stopped.
+pub struct ActorExit {
+    pub id: String,
+    /// Whether the thread panicked (or never started), as opposed to
+    /// returning on its own.
+    pub crashed: bool,
+    /// Panic message, spawn error, or a note that the thread returned.
+    pub reason: String,
+    /// How long the thread ran.
+    pub uptime: Duration,
+}
+
+/// Liveness of a registered actor, for `GET /api/health`.
+pub struct ActorLiveness {
+    /// Time since the actor last sent on or polled the bus.
+    pub idle: Duration,
+    /// Time since the actor was started.
+    pub uptime: Duration,
+    /// The actor's thread ended without it being stopped.
+    pub dead: bool,
+}
+
 /// Root entry point for all managed application state.
 ///
 /// Passed as `Arc<SystemState>` to all actors and the web layer.
 pub struct SystemState {
     pub system: SystemConfig,
     pub game: GameState,
-    #[allow(clippy::type_complexity)]
-    actors: RwLock<HashMap<String, (Box<dyn Actor>, Arc<AtomicBool>)>>,
+    actors: RwLock<HashMap<String, ActorEntry>>,
+    /// Actors stopped via the lifecycle API. Config reloads leave them
+    /// stopped until they are started again.
+    held: RwLock<HashSet<String>>,
+    /// Plain-HTTP loopback listener for the native GUI, when every webserver
+    /// uses TLS. Set once at startup.
+    gui_loopback: OnceLock<GuiLoopback>,
+}
+
+/// Extra listener a TLS webserver opens on loopback for the native GUI,
+/// whose HTTP/WS clients have no TLS. Not part of the config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuiLoopback {
+    /// Webserver actor that serves it (e.g. `webserver.0`).
+    pub webserver: String,
+    pub addr: SocketAddr,
 }
 
 impl SystemState {
@@ -30,19 +79,51 @@ impl SystemState {
                 system: SystemConfig::new(config_path),
                 game,
                 actors: RwLock::new(HashMap::new()),
+                held: RwLock::new(HashSet::new()),
+                gui_loopback: OnceLock::new(),
             },
             writer,
         )
     }
 
+    /// Set the native GUI's loopback listener. Only the first call counts.
+    #[cfg_attr(not(feature = "gui"), allow(dead_code))]
+    pub fn set_gui_loopback(&self, loopback: GuiLoopback) {
+        let _ = self.gui_loopback.set(loopback);
+    }
+
+    /// Loopback listener `webserver` should open for the native GUI, if any.
+    pub fn gui_loopback_for(&self, webserver: &str) -> Option<SocketAddr> {
+        self.gui_loopback
+            .get()
+            .filter(|l| l.webserver == webserver)
+            .map(|l| l.addr)
+    }
+
     // ----- Actor registry -----
 
-    /// Register an actor in the registry with its shutdown flag.
-    pub fn register_actor(&self, id: String, actor: Box<dyn Actor>, shutdown: Arc<AtomicBool>) {
+    /// Register an actor in the registry with the thread its `start()`
+    /// returned. The shutdown flag, heartbeat, and lag counters are taken
+    /// from the `BusSender` it was started with.
+    pub fn register_actor(
+        &self,
+        id: String,
+        actor: Box<dyn Actor>,
+        thread: ActorThread,
+        sender: &BusSender,
+    ) {
+        let entry = ActorEntry {
+            actor,
+            shutdown: Arc::clone(sender.shutdown()),
+            thread: Some(thread),
+            started: Instant::now(),
+            heartbeat: Arc::clone(sender.heartbeat()),
+            lag: Arc::clone(sender.lag()),
+        };
         self.actors
             .write()
             .unwrap_or_else(|e| e.into_inner())
-            .insert(id, (actor, shutdown));
+            .insert(id, entry);
     }
 
     /// Get the list of all registered actor IDs.
@@ -62,7 +143,7 @@ impl SystemState {
         F: FnOnce(&dyn Actor) -> R,
     {
         let guard = self.actors.read().unwrap_or_else(|e| e.into_inner());
-        guard.get(id).map(|(a, _)| f(a.as_ref()))
+        guard.get(id).map(|e| f(e.actor.as_ref()))
     }
 
     /// Call `reconfigure()` on the actor identified by `id`.
@@ -79,9 +160,9 @@ impl SystemState {
     /// Stop an actor by setting its shutdown flag and calling `stop()`.
     pub fn stop_actor(&self, id: &str) {
         let guard = self.actors.read().unwrap_or_else(|e| e.into_inner());
-        if let Some((actor, shutdown)) = guard.get(id) {
-            shutdown.store(true, Ordering::Relaxed);
-            actor.stop();
+        if let Some(entry) = guard.get(id) {
+            entry.shutdown.set();
+            entry.actor.stop();
         }
     }
 
@@ -91,6 +172,172 @@ impl SystemState {
             .write()
             .unwrap_or_else(|e| e.into_inner())
             .remove(id)
-            .map(|(a, _)| a)
+            .map(|e| e.actor)
+    }
+
+    // ----- Supervision -----
+
+    /// Collect actors whose thread has ended although they were not stopped.
+    /// Each exit is reported once; the actor stays registered (and so listed)
+    /// until it is restarted or removed.
+    pub fn reap_actors(&self) -> Vec<ActorExit> {
+        let mut guard = self.actors.write().unwrap_or_else(|e| e.into_inner());
+        let mut exits = Vec::new();
+        for (id, entry) in guard.iter_mut() {
+            if entry.shutdown.is_set() {
+                continue;
+            }
+            let ended = match &entry.thread {
+                Some(Ok(handle)) => handle.is_finished(),
+                Some(Err(_)) => true,
+                None => false,
+            };
+            if !ended {
+                continue;
+            }
+            let (crashed, reason) = match entry.thread.take() {
+                Some(Ok(handle)) => match handle.join() {
+                    Ok(()) => (false, "thread returned without being stopped".to_string()),
+                    Err(payload) => (true, format!("panicked: {}", panic_message(&*payload))),
+                },
+                Some(Err(e)) => (true, format!("failed to spawn thread: {e}")),
+                None => continue,
+            };
+            exits.push(ActorExit {
+                id: id.clone(),
+                crashed,
+                reason,
+                uptime: entry.started.elapsed(),
+            });
+        }
+        exits
+    }
+
+    /// True if `id` is registered and its exit was reported by
+    /// [`reap_actors`](Self::reap_actors), i.e. it is down and not replaced.
+    pub fn is_dead(&self, id: &str) -> bool {
+        let guard = self.actors.read().unwrap_or_else(|e| e.into_inner());
+        guard
+            .get(id)
+            .is_some_and(|e| e.thread.is_none() && !e.shutdown.is_set())
+    }
+
+    /// Liveness of every registered actor, keyed by ID.
+    pub fn actor_liveness(&self) -> HashMap<String, ActorLiveness> {
+        let guard = self.actors.read().unwrap_or_else(|e| e.into_inner());
+        guard
+            .iter()
+            .map(|(id, e)| {
+                let dead = !e.shutdown.is_set()
+                    && match &e.thread {
+                        Some(Ok(handle)) => handle.is_finished(),
+                        Some(Err(_)) | None => true,
+                    };
+                let liveness = ActorLiveness {
+                    idle: e.heartbeat.elapsed(),
+                    uptime: e.started.elapsed(),
+                    dead,
+                };
+                (id.clone(), liveness)
+            })
+            .collect()
+    }
+
+    /// Bus events each registered actor has missed, keyed by ID.
+    pub fn actor_lag(&self) -> HashMap<String, LagCounts> {
+        let guard = self.actors.read().unwrap_or_else(|e| e.into_inner());
+        guard
+            .iter()
+            .map(|(id, e)| (id.clone(), e.lag.counts()))
+            .collect()
+    }
+
+    // ----- Lifecycle holds -----
+
+    /// Keep `id` stopped across config reloads.
+    pub fn hold_actor(&self, id: &str) {
+        self.held
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(id.to_string());
+    }
+
+    /// Clear a hold set by [`hold_actor`](Self::hold_actor).
+    pub fn release_actor(&self, id: &str) {
+        self.held
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(id);
+    }
+
+    /// True if `id` was stopped via the lifecycle API.
+    pub fn is_held(&self, id: &str) -> bool {
+        self.held
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(id)
+    }
+}
+
+/// The message a panic was raised with, if it was a string.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("non-string panic payload")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::{Bus, BusReceiver};
+
+    struct PanickingActor;
+
+    impl Actor for PanickingActor {
+        fn start(&self, _: Arc<SystemState>, _: BusSender, _: BusReceiver) -> ActorThread {
+            std::thread::Builder::new().spawn(|| panic!("device went away"))
+        }
+    }
+
+    fn wait_for_exit(state: &SystemState) -> Vec<ActorExit> {
+        for _ in 0..100 {
+            let exits = state.reap_actors();
+            if !exits.is_empty() {
+                return exits;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        panic!("actor thread did not end");
+    }
+
+    #[test]
+    fn panicked_actor_is_reaped_once_and_stays_listed() {
+        let dir = std::env::temp_dir().join(format!("flighthook-reap-{}", std::process::id()));
+        let (state, _writer) = SystemState::new(dir.join("config.toml"));
+        let state = Arc::new(state);
+        crate::actors::start_actor(
+            "mock_monitor.0".into(),
+            Box::new(PanickingActor),
+            &state,
+            &Bus::new(16),
+        );
+
+        let exits = wait_for_exit(&state);
+        assert_eq!(exits.len(), 1);
+        assert_eq!(exits[0].id, "mock_monitor.0");
+        assert!(exits[0].crashed);
+        assert!(exits[0].reason.contains("device went away"));
+        assert!(state.is_dead("mock_monitor.0"));
+        assert!(state.actor_liveness()["mock_monitor.0"].dead);
+        assert!(state.reap_actors().is_empty());
+        assert_eq!(state.actor_ids(), ["mock_monitor.0"]);
+
+        // A stopped actor is not dead, whatever its thread did.
+        state.stop_actor("mock_monitor.0");
+        assert!(!state.is_dead("mock_monitor.0"));
+        assert!(!state.actor_liveness()["mock_monitor.0"].dead);
+        let _ = std::fs::remove_dir_all(dir);
     }
 }
@@ -1,23 +1,70 @@
 //! REST API request/response types shared between the app and UI crates.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use serde::{Deserialize, Serialize};
 
-use crate::{ActorStatus, ShotDetectionMode};
+use crate::{
+    ActorStatus, BallFlight, Club, ClubData, ConfigIssue, FaceImpact, FlighthookConfig, ShotData,
+    ShotDetectionMode, ShotKey,
+};
 
 /// GET /api/status response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct StatusResponse {
     #[serde(default)]
     pub actors: HashMap<String, ActorStatusResponse>,
     #[serde(default)]
     pub mode: Option<ShotDetectionMode>,
+    /// Mode each configured launch monitor uses: `mode` through the
+    /// section's pin or mapping, keyed by actor ID.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub device_modes: HashMap<String, ShotDetectionMode>,
+}
+
+/// GET /api/health response, served with 200 when `ready` and 503 otherwise.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HealthResponse {
+    /// The system actor and every critical actor (`[health] critical`) are
+    /// running, not stalled, and connected.
+    pub ready: bool,
+    /// Why the app is not ready, one entry per problem.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub problems: Vec<String>,
+    /// Every running actor, plus critical actors that are not running.
+    #[serde(default)]
+    pub actors: HashMap<String, ActorHealth>,
+}
+
+/// Per-actor liveness within the health response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ActorHealth {
+    /// Last status the actor reported, if it reports one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ActorStatus>,
+    /// Registered and its thread alive.
+    pub running: bool,
+    /// Milliseconds since the actor last sent on or polled the bus.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idle_ms: Option<u64>,
+    /// Milliseconds since the actor was started.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uptime_ms: Option<u64>,
+    /// Running but idle longer than `[health] stall_after_ms`.
+    #[serde(default)]
+    pub stalled: bool,
+    /// Listed in `[health] critical` (always true for `system`).
+    #[serde(default)]
+    pub critical: bool,
 }
 
 /// Per-actor status within the status response. Also used as the cached
 /// per-actor state in the web layer and UI.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ActorStatusResponse {
     #[serde(default)]
     pub name: String,
@@ -26,17 +73,151 @@ pub struct ActorStatusResponse {
     pub telemetry: HashMap<String, String>,
 }
 
+/// One entry of the GET /api/shots history: the shot plus the context it
+/// was hit in. The shot fields are flattened, so a record also parses as a
+/// plain [`ShotData`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ShotRecord {
+    /// Shot ID from the lifecycle `ShotKey`; the pagination cursor.
+    pub shot_id: String,
+    /// When the shot finished, RFC 3339 UTC with milliseconds.
+    pub recorded_at: String,
+    /// Consecutive shots by the same player share a session number; it
+    /// goes up by one whenever the player changes.
+    pub session: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub player: Option<String>,
+    /// Club selected when the shot was recorded. (`club` is the club head
+    /// data of the flattened shot.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_club: Option<Club>,
+    /// Detection mode when the shot was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<ShotDetectionMode>,
+    /// Where the shot's time went. Omitted when nothing was measured.
+    #[serde(default, skip_serializing_if = "ShotLatency::is_empty")]
+    pub latency: ShotLatency,
+    /// Integrations that dropped this shot as a duplicate of the same swing
+    /// from another monitor, mapped to the shot ID each forwarded instead
+    /// (from `shot_suppressed` events).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub duplicate_of: BTreeMap<String, String>,
+    #[serde(flatten)]
+    pub shot: ShotData,
+}
+
+/// Per-shot latency in milliseconds, from the envelope `timestamp_ms`s.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ShotLatency {
+    /// `shot_trigger` to `shot_finished`, both stamped by the shot's source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_ms: Option<u64>,
+    /// `shot_finished` to hand-off, per integration that forwarded the shot
+    /// (e.g. `"gspro.0"`), from its `shot_delivered` events.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub delivered_ms: BTreeMap<String, u64>,
+}
+
+impl ShotLatency {
+    pub fn is_empty(&self) -> bool {
+        self.finished_ms.is_none() && self.delivered_ms.is_empty()
+    }
+}
+
 /// POST /api/mode request body.
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ModeRequest {
     pub mode: ShotDetectionMode,
 }
 
-/// POST /api/settings response.
+/// POST /api/shots request body — a hand-entered or synthetic shot.
+///
+/// The data fields match [`ShotData`](crate::ShotData); the server assigns
+/// the shot ID and number. At least one of them must be present.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PostShotRequest {
+    /// Attribute the shot to a configured launch monitor (e.g. `"mevo.0"`)
+    /// instead of the dedicated `manual` actor, e.g. to test routing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "Option<crate::schema::BallFlight>")
+    )]
+    pub ball: Option<BallFlight>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<crate::schema::ClubData>"))]
+    pub club: Option<ClubData>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "Option<crate::schema::FaceImpact>")
+    )]
+    pub impact: Option<FaceImpact>,
+}
+
+/// POST /api/shots response: who the shot was published as, and its key.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PostShotResponse {
+    pub actor: String,
+    #[serde(flatten)]
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::ShotKey"))]
+    pub key: ShotKey,
+}
+
+/// POST /api/settings response, also returned by the
+/// `POST /api/actors/{id}/{restart,stop,start}` lifecycle endpoints.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PostSettingsResponse {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub restarted: Vec<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub stopped: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub started: Vec<String>,
+    /// Validation warnings for the saved config (`POST /api/settings` only).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ConfigIssue>,
+}
+
+/// GET /api/profiles response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ProfilesResponse {
+    /// Name of the active profile.
+    pub active: String,
+    /// All profile names, the active one included, sorted.
+    pub profiles: Vec<String>,
+}
+
+/// One entry of GET /api/settings/history: the config as it was before a
+/// change was applied, and what the change did.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ConfigHistoryEntry {
+    /// Increasing ID; `POST /api/settings/history/{id}/rollback` restores
+    /// `previous`.
+    pub id: u64,
+    /// When the change was applied, RFC 3339 UTC with milliseconds.
+    pub recorded_at: String,
+    /// Actor that sent the `ConfigCommand` (`"web"`, `"ws.<name>"`, ...).
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// `ConfigAction` type of the change, e.g. `"replace_all"`.
+    pub action: String,
+    /// Global ID of the section changed; `None` for a full replace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// The full config before the change.
+    pub previous: FlighthookConfig,
+    /// Actors the change restarted, stopped and started.
+    pub outcome: PostSettingsResponse,
 }
@@ -0,0 +1,516 @@
+//! Async (tokio) WebSocket client with automatic reconnect.
+//!
+//! Requires the `async-client` feature flag. Unlike [`FlighthookClient`],
+//! which gives up on the first error, [`AsyncFlighthookClient`] owns a
+//! background task that reconnects with exponential backoff, redoes the FRP
+//! handshake, and resumes from the last sequence number it saw, so buffered
+//! shot events missed while disconnected are replayed by the server.
+//!
+//! ```no_run
+//! # async fn run() {
+//! use futures_util::StreamExt;
+//! use flighthook::AsyncFlighthookClient;
+//!
+//! let client = AsyncFlighthookClient::connect("ws://localhost:5880/frp", "my-service");
+//! let mut state = client.state();
+//! tokio::spawn(async move {
+//!     while state.changed().await.is_ok() {
+//!         println!("connection: {:?}", *state.borrow());
+//!     }
+//! });
+//!
+//! let mut shots = client.shots();
+//! while let Some(shot) = shots.next().await {
+//!     println!("shot #{} from {}", shot.shot_number, shot.actor);
+//! }
+//! # }
+//! ```
+//!
+//! [`FlighthookClient`]: crate::FlighthookClient
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_util::{SinkExt, Stream, StreamExt};
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::client::{parse_init_actor_id, start_message};
+use crate::{ClientError, ConnectOptions, FlighthookMessage, ShotAggregator, ShotData};
+
+/// Messages buffered between the connection task and the consumer. When
+/// full, the task stops reading the socket until the consumer catches up.
+const MESSAGE_BUFFER: usize = 1024;
+
+/// Reconnect delays: `initial`, doubling per failed attempt up to `max`.
+/// The delay resets once a handshake succeeds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    pub initial: Duration,
+    pub max: Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(500),
+            max: Duration::from_secs(30),
+        }
+    }
+}
+
+impl Backoff {
+    /// Delay before reconnect attempt `attempt` (0-based).
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.initial
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max)
+    }
+}
+
+/// Connection state of an [`AsyncFlighthookClient`], observed via
+/// [`state`](AsyncFlighthookClient::state).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// Opening the socket and waiting for the `init` handshake reply.
+    Connecting,
+    /// Handshake complete; messages are flowing.
+    Connected {
+        /// Actor ID assigned by the server for this session.
+        actor_id: String,
+    },
+    /// The connection failed or dropped; the next attempt starts after
+    /// `retry_in`.
+    Reconnecting {
+        /// Consecutive failed attempts so far.
+        attempt: u32,
+        retry_in: Duration,
+        error: String,
+    },
+    /// Closed with [`close`](AsyncFlighthookClient::close). Final.
+    Closed,
+}
+
+enum Command {
+    Send(String),
+    Close,
+}
+
+/// An async WebSocket client that stays connected to a flighthook server.
+///
+/// Implements [`Stream`] of [`FlighthookMessage`]; the stream only ends after
+/// [`close`](Self::close). Connection loss is not an error on the stream:
+/// it shows up in [`state`](Self::state) and the client reconnects on its
+/// own. Dropping the client stops the background task.
+pub struct AsyncFlighthookClient {
+    messages: mpsc::Receiver<FlighthookMessage>,
+    commands: mpsc::UnboundedSender<Command>,
+    state: watch::Receiver<ConnectionState>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl AsyncFlighthookClient {
+    /// Start connecting to `url` (e.g. `"ws://localhost:5880/frp"`) in the
+    /// background. `name` is sent in the handshake.
+    ///
+    /// Returns immediately; watch [`state`](Self::state) for the outcome.
+    /// Must be called from within a tokio runtime.
+    pub fn connect(url: &str, name: &str) -> Self {
+        Self::connect_with(url, name, &ConnectOptions::default())
+    }
+
+    /// Like [`connect`](Self::connect), with extra [`ConnectOptions`] (token,
+    /// TLS roots, [`Backoff`]).
+    pub fn connect_with(url: &str, name: &str, options: &ConnectOptions) -> Self {
+        let (msg_tx, messages) = mpsc::channel(MESSAGE_BUFFER);
+        let (commands, cmd_rx) = mpsc::unbounded_channel();
+        let (state_tx, state) = watch::channel(ConnectionState::Connecting);
+        let task = tokio::spawn(run(
+            url.to_string(),
+            name.to_string(),
+            options.clone(),
+            msg_tx,
+            cmd_rx,
+            state_tx,
+        ));
+        Self {
+            messages,
+            commands,
+            state,
+            task: Some(task),
+        }
+    }
+
+    /// A receiver for connection state changes. Independent of the client,
+    /// so it can be moved to another task before consuming the client with
+    /// [`shots`](Self::shots).
+    pub fn state(&self) -> watch::Receiver<ConnectionState> {
+        self.state.clone()
+    }
+
+    /// Send a [`FlighthookMessage`] to the server (see
+    /// [`FlighthookClient::send`](crate::FlighthookClient::send) for what the
+    /// server accepts).
+    ///
+    /// Fails with [`ClientError::Closed`] unless currently connected:
+    /// messages are not queued across reconnects.
+    pub fn send(&self, msg: &FlighthookMessage) -> Result<(), ClientError> {
+        if !matches!(*self.state.borrow(), ConnectionState::Connected { .. }) {
+            return Err(ClientError::Closed);
+        }
+        let json = serde_json::to_string(msg)?;
+        self.commands
+            .send(Command::Send(json))
+            .map_err(|_| ClientError::Closed)
+    }
+
+    /// Complete shots, assembled from the shot lifecycle events with a
+    /// [`ShotAggregator`]. Other messages are dropped.
+    pub fn shots(self) -> impl Stream<Item = ShotData> + Unpin {
+        let mut aggregator = ShotAggregator::new();
+        self.filter_map(move |msg| std::future::ready(aggregator.feed(&msg)))
+    }
+
+    /// Close the connection and stop reconnecting.
+    pub async fn close(mut self) {
+        let _ = self.commands.send(Command::Close);
+        if let Some(task) = self.task.take() {
+            let _ = task.await;
+        }
+    }
+}
+
+impl Stream for AsyncFlighthookClient {
+    type Item = FlighthookMessage;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.messages.poll_recv(cx)
+    }
+}
+
+impl Drop for AsyncFlighthookClient {
+    fn drop(&mut self) {
+        if let Some(task) = &self.task {
+            task.abort();
+        }
+    }
+}
+
+impl std::fmt::Debug for AsyncFlighthookClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncFlighthookClient")
+            .field("state", &*self.state.borrow())
+            .finish_non_exhaustive()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Connection task
+// ---------------------------------------------------------------------------
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// A streamed message with the server's top-level `seq` extension field.
+#[derive(serde::Deserialize)]
+struct Sequenced {
+    #[serde(default)]
+    seq: Option<u64>,
+    #[serde(flatten)]
+    msg: FlighthookMessage,
+}
+
+/// Why a session ended without an error.
+enum SessionEnd {
+    /// [`AsyncFlighthookClient::close`] was called.
+    Closed,
+    /// The client was dropped.
+    Dropped,
+}
+
+async fn run(
+    url: String,
+    name: String,
+    options: ConnectOptions,
+    msg_tx: mpsc::Sender<FlighthookMessage>,
+    mut cmd_rx: mpsc::UnboundedReceiver<Command>,
+    state_tx: watch::Sender<ConnectionState>,
+) {
+    // Sequence number of the last message seen, sent as `resume_from` on
+    // reconnect. Starts from the `init` reply of the first session.
+    let mut last_seq: Option<u64> = None;
+    let mut attempt = 0u32;
+
+    loop {
+        state_tx.send_replace(ConnectionState::Connecting);
+        let session = session(
+            &url,
+            &name,
+            &options,
+            &mut last_seq,
+            &mut attempt,
+            &msg_tx,
+            &mut cmd_rx,
+            &state_tx,
+        );
+        let error = match session.await {
+            Ok(SessionEnd::Closed) => {
+                state_tx.send_replace(ConnectionState::Closed);
+                return;
+            }
+            Ok(SessionEnd::Dropped) => return,
+            Err(e) => e.to_string(),
+        };
+
+        let retry_in = options.backoff.delay(attempt);
+        attempt = attempt.saturating_add(1);
+        state_tx.send_replace(ConnectionState::Reconnecting {
+            attempt,
+            retry_in,
+            error,
+        });
+
+        let sleep = tokio::time::sleep(retry_in);
+        tokio::pin!(sleep);
+        loop {
+            tokio::select! {
+                _ = &mut sleep => break,
+                cmd = cmd_rx.recv() => match cmd {
+                    // Nothing to send on; `send` already refuses while
+                    // disconnected, so this only drops a racing message.
+                    Some(Command::Send(_)) => {}
+                    Some(Command::Close) => {
+                        state_tx.send_replace(ConnectionState::Closed);
+                        return;
+                    }
+                    None => return,
+                },
+            }
+        }
+    }
+}
+
+/// One connection: open, handshake, then pump frames until it ends.
+#[allow(clippy::too_many_arguments)]
+async fn session(
+    url: &str,
+    name: &str,
+    options: &ConnectOptions,
+    last_seq: &mut Option<u64>,
+    attempt: &mut u32,
+    msg_tx: &mpsc::Sender<FlighthookMessage>,
+    cmd_rx: &mut mpsc::UnboundedReceiver<Command>,
+    state_tx: &watch::Sender<ConnectionState>,
+) -> Result<SessionEnd, ClientError> {
+    let mut ws = open(url, options).await?;
+    ws.send(Message::text(start_message(name, options, *last_seq)))
+        .await?;
+
+    // The server answers a refused handshake (bad token, no common FRP
+    // version) with a bare alert before closing.
+    let mut refusal = None;
+    let actor_id = loop {
+        match ws.next().await {
+            Some(Ok(Message::Text(text))) => {
+                if let Some(id) = parse_init_actor_id(&text) {
+                    track_seq(&text, last_seq);
+                    break id;
+                }
+                refusal = parse_refusal(&text).or(refusal);
+            }
+            Some(Ok(Message::Close(_))) | None => {
+                return Err(refusal.map_or(ClientError::Closed, ClientError::Refused));
+            }
+            Some(Ok(_)) => {}
+            Some(Err(e)) => return Err(e.into()),
+        }
+    };
+    *attempt = 0;
+    state_tx.send_replace(ConnectionState::Connected { actor_id });
+
+    loop {
+        tokio::select! {
+            frame = ws.next() => match frame {
+                Some(Ok(Message::Text(text))) => {
+                    // Frames that are not a `FlighthookMessage` are skipped
+                    // rather than ending the stream.
+                    let Ok(sequenced) = serde_json::from_str::<Sequenced>(&text) else {
+                        continue;
+                    };
+                    // Replays can run ahead of older live frames, so keep
+                    // the highest `seq` seen.
+                    *last_seq = (*last_seq).max(sequenced.seq);
+                    if msg_tx.send(sequenced.msg).await.is_err() {
+                        return Ok(SessionEnd::Dropped);
+                    }
+                }
+                Some(Ok(Message::Close(_))) | None => return Err(ClientError::Closed),
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(e.into()),
+            },
+            cmd = cmd_rx.recv() => match cmd {
+                Some(Command::Send(json)) => ws.send(Message::text(json)).await?,
+                Some(Command::Close) => {
+                    let _ = ws.close(None).await;
+                    return Ok(SessionEnd::Closed);
+                }
+                None => return Ok(SessionEnd::Dropped),
+            },
+        }
+    }
+}
+
+/// Open the WebSocket; extra root certificates need a custom rustls config,
+/// as in the synchronous client.
+async fn open(url: &str, options: &ConnectOptions) -> Result<Socket, ClientError> {
+    #[cfg(feature = "tls")]
+    if !options.root_certs.is_empty() {
+        let connector = tokio_tungstenite::Connector::Rustls(crate::client::rustls_config(
+            &options.root_certs,
+        )?);
+        let (ws, _response) =
+            tokio_tungstenite::connect_async_tls_with_config(url, None, false, Some(connector))
+                .await?;
+        return Ok(ws);
+    }
+    let _ = options;
+    let (ws, _response) = tokio_tungstenite::connect_async(url).await?;
+    Ok(ws)
+}
+
+fn track_seq(text: &str, last_seq: &mut Option<u64>) {
+    #[derive(serde::Deserialize)]
+    struct Seq {
+        seq: Option<u64>,
+    }
+    if let Ok(Seq { seq: Some(seq) }) = serde_json::from_str(text) {
+        *last_seq = Some(seq);
+    }
+}
+
+fn parse_refusal(text: &str) -> Option<String> {
+    #[derive(serde::Deserialize)]
+    struct Alert {
+        kind: String,
+        message: String,
+    }
+    let alert: Alert = serde_json::from_str(text).ok()?;
+    (alert.kind == "alert").then_some(alert.message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    fn shot_frames(seq: u64, shot_id: &str) -> Vec<String> {
+        let key = serde_json::json!({ "shot_id": shot_id, "shot_number": seq });
+        [
+            serde_json::json!({ "kind": "shot_trigger", "key": key }),
+            serde_json::json!({ "kind": "ball_flight", "key": key, "ball": { "launch_speed": "150mph" } }),
+            serde_json::json!({ "kind": "shot_finished", "key": key }),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, event)| {
+            serde_json::json!({ "actor": "mock_monitor.0", "seq": seq * 10 + i as u64, "event": event })
+                .to_string()
+        })
+        .collect()
+    }
+
+    /// Accept one connection, check its `start` message, reply `init`, send
+    /// `frames` and hang up. Returns the `start` message.
+    async fn serve_once(
+        listener: &TcpListener,
+        init_seq: u64,
+        frames: Vec<String>,
+    ) -> serde_json::Value {
+        let (tcp, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+        let start = match ws.next().await {
+            Some(Ok(Message::Text(text))) => serde_json::from_str(&text).unwrap(),
+            other => panic!("expected start, got {other:?}"),
+        };
+        let init = serde_json::json!({ "kind": "init", "version": crate::FRP_VERSION, "actor_id": "ws.test", "seq": init_seq });
+        ws.send(Message::text(init.to_string())).await.unwrap();
+        for frame in frames {
+            ws.send(Message::text(frame)).await.unwrap();
+        }
+        ws.close(None).await.ok();
+        start
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let b = Backoff {
+            initial: Duration::from_millis(100),
+            max: Duration::from_secs(1),
+        };
+        assert_eq!(b.delay(0), Duration::from_millis(100));
+        assert_eq!(b.delay(3), Duration::from_millis(800));
+        assert_eq!(b.delay(4), Duration::from_secs(1));
+        assert_eq!(b.delay(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn reconnects_and_resumes_from_last_seq() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/frp", listener.local_addr().unwrap());
+        let options = ConnectOptions::new().token("t0k").backoff(Backoff {
+            initial: Duration::from_millis(10),
+            max: Duration::from_millis(10),
+        });
+
+        let client = AsyncFlighthookClient::connect_with(&url, "test", &options);
+        let mut state = client.state();
+        let mut shots = client.shots();
+
+        let first = serve_once(&listener, 7, shot_frames(1, "a")).await;
+        assert_eq!(first["token"], "t0k");
+        assert!(first.get("resume_from").is_none());
+        assert_eq!(shots.next().await.unwrap().shot_number, 1);
+
+        // Server hangs up; the client comes back asking for what it missed.
+        let second = serve_once(&listener, 30, shot_frames(2, "b")).await;
+        assert_eq!(second["resume_from"], 12);
+        assert_eq!(shots.next().await.unwrap().shot_number, 2);
+
+        let seen_reconnecting = state
+            .wait_for(|s| matches!(s, ConnectionState::Reconnecting { .. }))
+            .await;
+        assert!(seen_reconnecting.is_ok());
+    }
+
+    #[tokio::test]
+    async fn refused_handshake_is_reported() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/frp", listener.local_addr().unwrap());
+        let client = AsyncFlighthookClient::connect(&url, "test");
+        let mut state = client.state();
+
+        let (tcp, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+        ws.next().await;
+        let alert = r#"{"kind":"alert","severity":"critical","message":"Unauthorized"}"#;
+        ws.send(Message::text(alert)).await.unwrap();
+        ws.close(None).await.ok();
+
+        let state = state
+            .wait_for(|s| matches!(s, ConnectionState::Reconnecting { .. }))
+            .await
+            .unwrap()
+            .clone();
+        match state {
+            ConnectionState::Reconnecting { attempt, error, .. } => {
+                assert_eq!(attempt, 1);
+                assert!(error.contains("Unauthorized"), "{error}");
+            }
+            other => panic!("unexpected state {other:?}"),
+        }
+        client.close().await;
+    }
+}
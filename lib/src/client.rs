@@ -34,6 +34,28 @@
 //!     // ... render frame, sleep, etc.
 //! }
 //! ```
+//!
+//! # TLS
+//!
+//! With the `tls` feature, `wss://` URLs are supported. Server certificates
+//! are checked against the bundled Mozilla roots; for a self-signed server
+//! certificate, trust it explicitly:
+//!
+//! ```no_run
+//! # #[cfg(feature = "tls")]
+//! # fn main() -> Result<(), flighthook::ClientError> {
+//! use flighthook::{ConnectOptions, FlighthookClient};
+//!
+//! let pem = std::fs::read("flighthook-cert.pem").unwrap();
+//! let options = ConnectOptions::new()
+//!     .token("s3cret")
+//!     .add_root_certificate_pem(&pem)?;
+//! let client = FlighthookClient::connect_with("wss://192.168.1.20:5880/frp", "my-app", &options)?;
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "tls"))]
+//! # fn main() {}
+//! ```
 
 use std::fmt;
 use std::net::TcpStream;
@@ -51,8 +73,13 @@ pub enum ClientError {
     WebSocket(Box<tungstenite::Error>),
     /// JSON serialization/deserialization error.
     Json(serde_json::Error),
+    /// Invalid TLS settings (e.g. an unparsable root certificate).
+    Tls(String),
     /// The connection was closed.
     Closed,
+    /// The server refused the handshake, with its reason (e.g. a missing
+    /// token or no common FRP version).
+    Refused(String),
 }
 
 impl fmt::Display for ClientError {
@@ -60,7 +87,9 @@ impl fmt::Display for ClientError {
         match self {
             ClientError::WebSocket(e) => write!(f, "websocket: {e}"),
             ClientError::Json(e) => write!(f, "json: {e}"),
+            ClientError::Tls(e) => write!(f, "tls: {e}"),
             ClientError::Closed => write!(f, "connection closed"),
+            ClientError::Refused(reason) => write!(f, "refused by server: {reason}"),
         }
     }
 }
@@ -70,7 +99,7 @@ impl std::error::Error for ClientError {
         match self {
             ClientError::WebSocket(e) => Some(e.as_ref()),
             ClientError::Json(e) => Some(e),
-            ClientError::Closed => None,
+            ClientError::Tls(_) | ClientError::Closed | ClientError::Refused(_) => None,
         }
     }
 }
@@ -87,6 +116,52 @@ impl From<serde_json::Error> for ClientError {
     }
 }
 
+/// Optional settings for [`FlighthookClient::connect_with`].
+#[derive(Debug, Clone, Default)]
+pub struct ConnectOptions {
+    pub(crate) token: Option<String>,
+    #[cfg(feature = "tls")]
+    pub(crate) root_certs: Vec<rustls::pki_types::CertificateDer<'static>>,
+    #[cfg(feature = "async-client")]
+    pub(crate) backoff: crate::Backoff,
+}
+
+impl ConnectOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// API token presented in the handshake.
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Reconnect delays for [`AsyncFlighthookClient`](crate::AsyncFlighthookClient).
+    /// Ignored by the synchronous client, which does not reconnect.
+    #[cfg(feature = "async-client")]
+    pub fn backoff(mut self, backoff: crate::Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Trust the certificate(s) in `pem` in addition to the bundled roots,
+    /// e.g. a server's self-signed certificate.
+    #[cfg(feature = "tls")]
+    pub fn add_root_certificate_pem(mut self, pem: &[u8]) -> Result<Self, ClientError> {
+        use rustls::pki_types::pem::PemObject;
+
+        let certs = rustls::pki_types::CertificateDer::pem_slice_iter(pem)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| ClientError::Tls(e.to_string()))?;
+        if certs.is_empty() {
+            return Err(ClientError::Tls("no certificate in PEM".into()));
+        }
+        self.root_certs.extend(certs);
+        Ok(self)
+    }
+}
+
 /// A synchronous WebSocket client connected to a flighthook server.
 ///
 /// After [`connect`](Self::connect), the handshake is complete and the client
@@ -104,12 +179,27 @@ impl FlighthookClient {
     ///
     /// `url` should be a WebSocket URL like `"ws://localhost:5880/frp"`.
     /// `name` is a human-readable client identifier sent during the handshake.
+    /// With the `tls` feature, `wss://` URLs are supported too.
     pub fn connect(url: &str, name: &str) -> Result<Self, ClientError> {
-        let (mut socket, _response) = tungstenite::connect(url)?;
+        Self::connect_with(url, name, &ConnectOptions::default())
+    }
+
+    /// Like [`connect`](Self::connect), presenting an API token in the
+    /// handshake. Needed when the server has `tokens` configured; a missing
+    /// or invalid token makes the server close the connection
+    /// ([`ClientError::Closed`]).
+    pub fn connect_with_token(url: &str, name: &str, token: &str) -> Result<Self, ClientError> {
+        Self::connect_with(url, name, &ConnectOptions::new().token(token))
+    }
 
-        let start =
-            serde_json::json!({ "kind": "start", "version": [crate::FRP_VERSION], "name": name });
-        socket.send(Message::text(start.to_string()))?;
+    /// Like [`connect`](Self::connect), with extra [`ConnectOptions`].
+    pub fn connect_with(
+        url: &str,
+        name: &str,
+        options: &ConnectOptions,
+    ) -> Result<Self, ClientError> {
+        let mut socket = open_socket(url, options)?;
+        socket.send(Message::text(start_message(name, options, None)))?;
 
         // Wait for init response (blocking — handshake always blocks)
         let actor_id = loop {
@@ -126,7 +216,8 @@ impl FlighthookClient {
         Ok(Self { socket, actor_id })
     }
 
-    /// The unique actor ID assigned by the server (e.g. `"ws.a1b2c3d4"`).
+    /// The actor ID assigned by the server, from the client name (e.g.
+    /// `"ws.my-dashboard"`).
     pub fn actor_id(&self) -> &str {
         &self.actor_id
     }
@@ -141,6 +232,11 @@ impl FlighthookClient {
             MaybeTlsStream::Plain(tcp) => tcp
                 .set_nonblocking(nonblocking)
                 .map_err(|e| ClientError::WebSocket(Box::new(tungstenite::Error::Io(e)))),
+            #[cfg(feature = "tls")]
+            MaybeTlsStream::Rustls(tls) => tls
+                .sock
+                .set_nonblocking(nonblocking)
+                .map_err(|e| ClientError::WebSocket(Box::new(tungstenite::Error::Io(e)))),
             _ => Ok(()),
         }
     }
@@ -181,6 +277,11 @@ impl FlighthookClient {
     }
 
     /// Send a [`FlighthookMessage`] to the server.
+    ///
+    /// The server re-stamps `actor` with this session's [`actor_id`](Self::actor_id)
+    /// and publishes the message on its bus. Only shot lifecycle, device
+    /// telemetry, alert, detection mode, player and club info events are
+    /// accepted; other kinds are silently dropped.
     pub fn send(&mut self, msg: &FlighthookMessage) -> Result<(), ClientError> {
         let json = serde_json::to_string(msg)?;
         self.socket.send(Message::text(json))?;
@@ -211,7 +312,102 @@ impl fmt::Debug for FlighthookClient {
     }
 }
 
-fn parse_init_actor_id(text: &str) -> Option<String> {
+/// Open the WebSocket. Extra root certificates need a custom rustls config;
+/// everything else goes through tungstenite's default connector.
+fn open_socket(
+    url: &str,
+    options: &ConnectOptions,
+) -> Result<WebSocket<MaybeTlsStream<TcpStream>>, ClientError> {
+    #[cfg(feature = "tls")]
+    if !options.root_certs.is_empty() {
+        return open_tls_socket(url, &options.root_certs);
+    }
+    let _ = options;
+    let (socket, _response) = tungstenite::connect(url)?;
+    Ok(socket)
+}
+
+/// rustls client config trusting the bundled Mozilla roots plus `extra_roots`.
+#[cfg(feature = "tls")]
+pub(crate) fn rustls_config(
+    extra_roots: &[rustls::pki_types::CertificateDer<'static>],
+) -> Result<std::sync::Arc<rustls::ClientConfig>, ClientError> {
+    use std::sync::Arc;
+
+    let mut roots = rustls::RootCertStore::empty();
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    for cert in extra_roots {
+        roots
+            .add(cert.clone())
+            .map_err(|e| ClientError::Tls(e.to_string()))?;
+    }
+    let config = rustls::ClientConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .map_err(|e| ClientError::Tls(e.to_string()))?
+    .with_root_certificates(roots)
+    .with_no_client_auth();
+    Ok(Arc::new(config))
+}
+
+#[cfg(feature = "tls")]
+fn open_tls_socket(
+    url: &str,
+    extra_roots: &[rustls::pki_types::CertificateDer<'static>],
+) -> Result<WebSocket<MaybeTlsStream<TcpStream>>, ClientError> {
+    use tungstenite::client::IntoClientRequest;
+
+    let config = rustls_config(extra_roots)?;
+    let request = url.into_client_request()?;
+    let host = request
+        .uri()
+        .host()
+        .ok_or(tungstenite::Error::Url(
+            tungstenite::error::UrlError::NoHostName,
+        ))?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+    let port = request
+        .uri()
+        .port_u16()
+        .unwrap_or(if request.uri().scheme_str() == Some("wss") {
+            443
+        } else {
+            80
+        });
+    let stream = TcpStream::connect((host.as_str(), port)).map_err(tungstenite::Error::Io)?;
+
+    let connector = tungstenite::Connector::Rustls(config);
+    match tungstenite::client_tls_with_config(request, stream, None, Some(connector)) {
+        Ok((socket, _response)) => Ok(socket),
+        Err(tungstenite::HandshakeError::Failure(e)) => Err(e.into()),
+        Err(tungstenite::HandshakeError::Interrupted(_)) => {
+            Err(tungstenite::Error::Io(std::io::ErrorKind::WouldBlock.into()).into())
+        }
+    }
+}
+
+/// The FRP `start` handshake message. `resume_from` asks the server to
+/// replay buffered shot events after that sequence number.
+pub(crate) fn start_message(
+    name: &str,
+    options: &ConnectOptions,
+    resume_from: Option<u64>,
+) -> String {
+    let mut start =
+        serde_json::json!({ "kind": "start", "version": [crate::FRP_VERSION], "name": name });
+    if let Some(token) = &options.token {
+        start["token"] = token.as_str().into();
+    }
+    if let Some(seq) = resume_from {
+        start["resume_from"] = seq.into();
+    }
+    start.to_string()
+}
+
+pub(crate) fn parse_init_actor_id(text: &str) -> Option<String> {
     #[derive(serde::Deserialize)]
     struct InitMsg {
         kind: String,
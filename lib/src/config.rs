@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use crate::game_state::Club;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 #[serde(rename_all = "snake_case")]
 pub enum ShotDetectionMode {
@@ -107,6 +108,7 @@ impl VelocityExt for Velocity {
 
 /// Unit system for display. Imperial = yards/feet/inches/mph, Metric = meters/m/s.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum UnitSystem {
     #[default]
@@ -135,6 +137,21 @@ pub fn default_putting_clubs() -> Vec<Club> {
     vec![Club::Putter]
 }
 
+// ---------------------------------------------------------------------------
+// Profiles
+// ---------------------------------------------------------------------------
+
+/// Name of the active profile in a config that never set one.
+pub const DEFAULT_PROFILE: &str = "default";
+
+fn default_profile() -> String {
+    DEFAULT_PROFILE.into()
+}
+
+fn is_default_profile(name: &str) -> bool {
+    name == DEFAULT_PROFILE
+}
+
 // ---------------------------------------------------------------------------
 // Persisted config types (shared between app and UI)
 // ---------------------------------------------------------------------------
@@ -142,6 +159,7 @@ pub fn default_putting_clubs() -> Vec<Club> {
 /// Top-level persisted config. All fields are in user-friendly units
 /// (inches, feet, 0-100 percent) so the TOML file is hand-editable.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FlighthookConfig {
     /// Default unit system for shot display (freedom units by default)
     #[serde(default)]
@@ -152,6 +170,25 @@ pub struct FlighthookConfig {
     /// Clubs that trigger Putting mode on selection.
     #[serde(default = "default_putting_clubs")]
     pub putting_clubs: Vec<Club>,
+    /// Name of the profile the device and integration sections belong to.
+    #[serde(
+        default = "default_profile",
+        skip_serializing_if = "is_default_profile"
+    )]
+    pub active_profile: String,
+    /// Restart policy for actors that crash.
+    #[serde(default, skip_serializing_if = "SupervisorSection::is_default")]
+    pub supervisor: SupervisorSection,
+    /// Critical actors and stall threshold for `GET /api/health`.
+    #[serde(default, skip_serializing_if = "HealthSection::is_default")]
+    pub health: HealthSection,
+    /// Named shot routing rule sets, picked by integrations with
+    /// `routing = "<name>"`.
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub routing: std::collections::HashMap<String, RoutingSection>,
+    /// Suppression of the same swing reported by several monitors.
+    #[serde(default, skip_serializing_if = "DedupSection::is_default")]
+    pub dedup: DedupSection,
     #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
     pub webserver: std::collections::HashMap<String, WebserverSection>,
     #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
@@ -168,13 +205,134 @@ pub struct FlighthookConfig {
     pub gspro: std::collections::HashMap<String, GsProSection>,
     #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
     pub random_club: std::collections::HashMap<String, RandomClubSection>,
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub plugin: std::collections::HashMap<String, PluginSection>,
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub script: std::collections::HashMap<String, ScriptSection>,
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub process: std::collections::HashMap<String, ProcessSection>,
+    /// Saved setups other than the active one, by name. Switching profiles
+    /// swaps one of these in for the live sections.
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub profiles: std::collections::HashMap<String, ConfigProfile>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// A named device and integration setup (`[profiles.<name>]`): every
+/// section type except webservers, which stay up across profile switches,
+/// plus the routing rule sets and dedup policy that go with those devices.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ConfigProfile {
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub mevo: std::collections::HashMap<String, MevoSection>,
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub r10: std::collections::HashMap<String, R10Section>,
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub square: std::collections::HashMap<String, SquareSection>,
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub mock_monitor: std::collections::HashMap<String, MockMonitorSection>,
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub openconnect_server: std::collections::HashMap<String, OpenConnectServerSection>,
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub gspro: std::collections::HashMap<String, GsProSection>,
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub random_club: std::collections::HashMap<String, RandomClubSection>,
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub plugin: std::collections::HashMap<String, PluginSection>,
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub script: std::collections::HashMap<String, ScriptSection>,
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub process: std::collections::HashMap<String, ProcessSection>,
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub routing: std::collections::HashMap<String, RoutingSection>,
+    #[serde(default, skip_serializing_if = "DedupSection::is_default")]
+    pub dedup: DedupSection,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct WebserverSection {
     #[serde(default)]
     pub name: String,
+    /// Keep the section but do not start its actor.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
     pub bind: String,
+    /// API tokens accepted by this server. Empty = no authentication.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tokens: Vec<ApiToken>,
+    /// PEM certificate (chain) file. Set with `tls_key` to serve HTTPS/WSS.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_cert: Option<String>,
+    /// PEM private key file for `tls_cert`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_key: Option<String>,
+    /// Generate a self-signed certificate at `tls_cert`/`tls_key` when the
+    /// files do not exist yet (default `false`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_self_signed: Option<bool>,
+}
+
+/// What an API token may do. Each scope includes the ones below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum TokenScope {
+    /// Read status, shots, and the WebSocket event stream.
+    Read,
+    /// Also change the detection mode and publish events over WebSocket.
+    Control,
+    /// Also read and write the config.
+    Admin,
+}
+
+impl fmt::Display for TokenScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read => write!(f, "read"),
+            Self::Control => write!(f, "control"),
+            Self::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+/// A bearer token for the REST/WebSocket API.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ApiToken {
+    /// Label for logs (e.g. "scoreboard").
+    #[serde(default)]
+    pub name: String,
+    pub token: String,
+    pub scope: TokenScope,
+}
+
+impl WebserverSection {
+    /// Certificate and key paths, if this server is configured for TLS.
+    pub fn tls_paths(&self) -> Option<(&str, &str)> {
+        Some((self.tls_cert.as_deref()?, self.tls_key.as_deref()?))
+    }
+
+    /// Scope granted to a presented token.
+    ///
+    /// With no tokens configured the API is open, so everything is `Admin`.
+    /// Returns `None` for a missing or unknown token.
+    pub fn granted_scope(&self, presented: Option<&str>) -> Option<TokenScope> {
+        if self.tokens.is_empty() {
+            return Some(TokenScope::Admin);
+        }
+        let presented = presented?;
+        self.tokens
+            .iter()
+            .filter(|t| constant_time_eq(t.token.as_bytes(), presented.as_bytes()))
+            .map(|t| t.scope)
+            .max()
+    }
+}
+
+/// Compare without short-circuiting on the first differing byte.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
 }
 
 /// Camera mode requested from the device at session start.
@@ -185,6 +343,7 @@ pub struct WebserverSection {
 /// whatever this is set to. The two Fusion variants are firmware-dependent —
 /// picking the wrong one yields no club data.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 #[serde(rename_all = "snake_case")]
 pub enum CameraMode {
@@ -233,13 +392,20 @@ impl CameraMode {
 
 /// A Mevo/Mevo+ device instance.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct MevoSection {
     #[serde(default)]
     pub name: String,
+    /// Keep the section but do not start its actor.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
     pub address: Option<String>,
     pub ball_type: Option<u8>,
+    #[cfg_attr(feature = "schema", schemars(with = "Option<crate::schema::Distance>"))]
     pub tee_height: Option<Distance>,
+    #[cfg_attr(feature = "schema", schemars(with = "Option<crate::schema::Distance>"))]
     pub range: Option<Distance>,
+    #[cfg_attr(feature = "schema", schemars(with = "Option<crate::schema::Distance>"))]
     pub surface_height: Option<Distance>,
     pub track_pct: Option<f64>,
     /// Whether to use estimated (E8 fallback) shots. Defaults to true when
@@ -251,13 +417,58 @@ pub struct MevoSection {
     /// Fusion modes additionally require the Pro Package on the device.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub camera_mode: Option<CameraMode>,
+    /// Detection mode this device uses instead of the global one.
+    #[serde(default, skip_serializing_if = "DeviceModeSection::is_default")]
+    pub mode: DeviceModeSection,
+}
+
+/// A launch monitor's own detection mode (`[<monitor>.<idx>.mode]`).
+///
+/// By default a device follows the global mode (`GameState.mode`). `pinned`
+/// fixes it to one mode; otherwise `full`, `chipping` and `putting` map each
+/// global mode to the mode the device uses, and unmapped modes pass through.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DeviceModeSection {
+    /// Always use this mode, whatever the global one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<ShotDetectionMode>,
+    /// Mode to use while the global mode is full swing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full: Option<ShotDetectionMode>,
+    /// Mode to use while the global mode is chipping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chipping: Option<ShotDetectionMode>,
+    /// Mode to use while the global mode is putting.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub putting: Option<ShotDetectionMode>,
+}
+
+impl DeviceModeSection {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The mode the device uses while the global mode is `global`.
+    pub fn effective(&self, global: ShotDetectionMode) -> ShotDetectionMode {
+        let mapped = match global {
+            ShotDetectionMode::Full => self.full,
+            ShotDetectionMode::Chipping => self.chipping,
+            ShotDetectionMode::Putting => self.putting,
+        };
+        self.pinned.or(mapped).unwrap_or(global)
+    }
 }
 
 /// A Garmin R10 BLE device instance.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct R10Section {
     #[serde(default)]
     pub name: String,
+    /// Keep the section but do not start its actor.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
     /// Distance from the device to the tee, sent to the R10 as `tee_range`
     /// once it wakes up. Garmin recommends placing the R10 6-8 ft behind the
     /// ball.
@@ -265,7 +476,11 @@ pub struct R10Section {
     /// When absent, no shot config is sent and the device keeps whatever tee
     /// distance was last set on it (e.g. by the Garmin Golf app).
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<crate::schema::Distance>"))]
     pub range: Option<Distance>,
+    /// Detection mode this device uses instead of the global one.
+    #[serde(default, skip_serializing_if = "DeviceModeSection::is_default")]
+    pub mode: DeviceModeSection,
 }
 
 /// A Square Golf Omni BLE device instance.
@@ -273,14 +488,18 @@ pub struct R10Section {
 /// The original Square / Square Home is not supported: it uses a different
 /// club-code scheme.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SquareSection {
     #[serde(default)]
     pub name: String,
+    /// Keep the section but do not start its actor.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
     /// BLE address to connect to. When absent, the first device advertising the
     /// `SquareGolf` name prefix is used.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub address: Option<String>,
-    /// Club selected on connect, e.g. `"7i"`, `"driver"`, `"putter"`.
+    /// Club selected on connect, as a club code, e.g. `"7I"`, `"DR"`, `"PT"`.
     ///
     /// The device is told which club is in play — it affects how the shot is
     /// classified. When GSPro reports a club change, the actor follows it and
@@ -304,13 +523,23 @@ pub struct SquareSection {
     /// Defaults to true when absent.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub discard_non_putting_zero_spin: Option<bool>,
+    /// Detection mode this device uses instead of the global one.
+    #[serde(default, skip_serializing_if = "DeviceModeSection::is_default")]
+    pub mode: DeviceModeSection,
 }
 
 /// A mock launch monitor instance.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct MockMonitorSection {
     #[serde(default)]
     pub name: String,
+    /// Keep the section but do not start its actor.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+    /// Detection mode this device uses instead of the global one.
+    #[serde(default, skip_serializing_if = "DeviceModeSection::is_default")]
+    pub mode: DeviceModeSection,
 }
 
 /// An OpenConnect server instance — a *launch monitor*, not an integration.
@@ -324,18 +553,30 @@ pub struct MockMonitorSection {
 /// `C:\GSPro\GSPC\GSPconnect.exe.config` to move GSPConnect to 922 and free
 /// 921 for this actor, so both can share one host.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct OpenConnectServerSection {
     #[serde(default)]
     pub name: String,
+    /// Keep the section but do not start its actor.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
     /// Bind address. Defaults to `0.0.0.0:921`.
     pub bind: Option<String>,
+    /// Detection mode the connected monitor's shots are routed and recorded
+    /// in. The client sets its own mode, so this only labels its shots.
+    #[serde(default, skip_serializing_if = "DeviceModeSection::is_default")]
+    pub mode: DeviceModeSection,
 }
 
 /// A GSPro integration instance.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct GsProSection {
     #[serde(default)]
     pub name: String,
+    /// Keep the section but do not start its actor.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
     pub address: Option<String>,
     /// Actor ID for full-swing shots (e.g. "mevo.0"). None = accept from any monitor.
     #[serde(default)]
@@ -346,13 +587,269 @@ pub struct GsProSection {
     /// Actor ID for putting shots. None = accept from any monitor.
     #[serde(default)]
     pub putting_monitor: Option<String>,
+    /// Name of a `[routing.<name>]` rule set. When set, it replaces the
+    /// three per-mode monitors above.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub routing: Option<String>,
 }
 
 /// A random club cycling integration instance.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct RandomClubSection {
     #[serde(default)]
     pub name: String,
+    /// Keep the section but do not start its actor.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+}
+
+/// A WebAssembly plugin instance: a custom shot processor or integration
+/// loaded from a module file.
+///
+/// The plugin runs sandboxed: it sees only the bus messages it is given and
+/// can only act by emitting messages back (see `docs/PLUGINS.md`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PluginSection {
+    #[serde(default)]
+    pub name: String,
+    /// Keep the section but do not start its actor.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+    /// Path to the module: binary `.wasm` or `.wat` text.
+    pub path: String,
+    /// Event kinds delivered to the plugin (e.g. `["shot_finished"]`).
+    /// Empty = every kind but `config_command`/`config_outcome`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kinds: Vec<String>,
+    /// Plugin-specific settings, handed to the module's `flighthook_init`
+    /// as a JSON object.
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub settings: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A Rhai script instance: bay rules that react to bus events and emit new
+/// ones, without a rebuild (see `docs/SCRIPTING.md`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ScriptSection {
+    #[serde(default)]
+    pub name: String,
+    /// Keep the section but do not start its actor.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+    /// Path to the `.rhai` script.
+    pub path: String,
+    /// Event kinds passed to the script's `on_message` (e.g.
+    /// `["shot_finished", "club_info"]`). Empty = every kind but
+    /// `config_command`/`config_outcome`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kinds: Vec<String>,
+}
+
+/// An external process instance: a device driver or integration written in
+/// any language, speaking NDJSON `FlighthookMessage`s over stdin/stdout
+/// (see `docs/PROCESSES.md`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ProcessSection {
+    #[serde(default)]
+    pub name: String,
+    /// Keep the section but do not start its actor.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+    /// Program to run, looked up on `PATH` when it has no directory part.
+    pub command: String,
+    /// Arguments passed to `command`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Event kinds written to the process's stdin (e.g.
+    /// `["shot_finished"]`). Empty = every kind but
+    /// `config_command`/`config_outcome`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kinds: Vec<String>,
+}
+
+/// Restart policy for actors whose thread dies (`[supervisor]`). All fields
+/// are optional; an empty section restarts crashed actors after 1s, doubling
+/// the delay with each consecutive crash up to 60s, with no limit.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SupervisorSection {
+    /// Restart crashed actors (default `true`). When `false` they stay
+    /// disconnected until restarted with `POST /api/actors/{id}/restart`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restart: Option<bool>,
+    /// Delay before the first restart, in milliseconds (default 1000).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backoff_initial_ms: Option<u64>,
+    /// Longest delay between restarts, in milliseconds (default 60000).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backoff_max_ms: Option<u64>,
+    /// Consecutive crashes after which an actor is left down (default: no
+    /// limit). A run of a minute or more resets the count.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_restarts: Option<u32>,
+}
+
+impl SupervisorSection {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Delay before restarting an actor after its `crashes`-th consecutive
+    /// crash (counting from 1), or `None` when the policy says to leave it
+    /// down.
+    pub fn restart_delay(&self, crashes: u32) -> Option<std::time::Duration> {
+        if self.restart == Some(false) || self.max_restarts.is_some_and(|max| crashes > max) {
+            return None;
+        }
+        let initial = self.backoff_initial_ms.unwrap_or(1000);
+        let max = self.backoff_max_ms.unwrap_or(60_000);
+        let factor = 1u64
+            .checked_shl(crashes.saturating_sub(1))
+            .unwrap_or(u64::MAX);
+        let delay = initial.saturating_mul(factor).min(max);
+        Some(std::time::Duration::from_millis(delay))
+    }
+}
+
+/// Readiness rules for `GET /api/health` (`[health]`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HealthSection {
+    /// Global IDs of actors the app is not ready without (e.g. `"mevo.0"`,
+    /// `"gspro.0"`). Health is 503 unless each is running and connected.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub critical: Vec<String>,
+    /// Time without bus activity after which an actor counts as stalled, in
+    /// milliseconds (default 10000).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stall_after_ms: Option<u64>,
+}
+
+impl HealthSection {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Time without bus activity after which an actor counts as stalled.
+    pub fn stall_after(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.stall_after_ms.unwrap_or(10_000))
+    }
+}
+
+/// An ordered set of shot routing rules (`[routing.<name>]`). For each
+/// finished shot the rules are tried in order and the first that matches
+/// lets it through; a shot no rule matches is dropped.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RoutingSection {
+    #[serde(default)]
+    pub rules: Vec<RoutingRule>,
+    /// How long a connected monitor may report `ready = false` before the
+    /// monitors after it take over, in milliseconds (default 30000).
+    /// Monitors disarm around every shot, so keep this well above one
+    /// shot cycle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_ready_grace_ms: Option<u64>,
+}
+
+impl RoutingSection {
+    /// How long a monitor may stay not ready before it is failed over.
+    pub fn not_ready_grace_ms(&self) -> u64 {
+        self.not_ready_grace_ms.unwrap_or(30_000)
+    }
+}
+
+/// One routing rule. Every condition that is set must hold; empty lists
+/// match anything.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RoutingRule {
+    /// Detection modes the rule applies to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modes: Vec<ShotDetectionMode>,
+    /// Selected clubs the rule applies to (GSPro codes, e.g. `"DR"`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub clubs: Vec<Club>,
+    /// Monitors by global ID, primary first. Only shots from the first one
+    /// that is available match; the rest are fallbacks for when it is
+    /// disconnected or has not been ready for `not_ready_grace_ms`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub monitors: Vec<String>,
+    /// Shot data the shot must carry.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<ShotPart>,
+}
+
+/// A part of a shot's data, for [`RoutingRule::requires`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ShotPart {
+    Ball,
+    Club,
+    Impact,
+}
+
+/// Duplicate shot suppression (`[dedup]`). With several monitors on one
+/// bay, one swing is reported once per monitor; integrations forward only
+/// the best of them. Off unless `window_ms` is set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DedupSection {
+    /// Shots from different actors triggered at most this many milliseconds
+    /// apart are one swing. A swing's first shot is held this long after it
+    /// finishes, waiting for its duplicates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_ms: Option<u64>,
+    /// How the shot to forward is picked: criteria in order, each later one
+    /// breaking ties (default `["measured", "complete", "priority"]`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prefer: Vec<DedupPreference>,
+    /// Actors by global ID, most preferred first, for the `priority`
+    /// criterion. Unlisted actors rank after listed ones.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub priority: Vec<String>,
+}
+
+impl DedupSection {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The grouping window, or `None` when suppression is off.
+    pub fn window_ms(&self) -> Option<u64> {
+        self.window_ms.filter(|ms| *ms > 0)
+    }
+
+    /// The preference criteria, in order.
+    pub fn preferences(&self) -> &[DedupPreference] {
+        if self.prefer.is_empty() {
+            &[
+                DedupPreference::Measured,
+                DedupPreference::Complete,
+                DedupPreference::Priority,
+            ]
+        } else {
+            &self.prefer
+        }
+    }
+}
+
+/// A criterion for picking among duplicate shots ([`DedupSection::prefer`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DedupPreference {
+    /// The actor listed first in `priority`.
+    Priority,
+    /// The shot with the most data fields.
+    Complete,
+    /// A measured ball flight over an estimated one.
+    Measured,
 }
 
 impl FlighthookConfig {
@@ -380,6 +877,124 @@ impl FlighthookConfig {
             || !self.openconnect_server.is_empty()
             || !self.gspro.is_empty()
             || !self.random_club.is_empty()
+            || !self.plugin.is_empty()
+            || !self.script.is_empty()
+            || !self.process.is_empty()
+    }
+
+    /// `disabled` flag of the section behind a global actor ID (`"mevo.0"`).
+    /// Returns `None` when no such section exists.
+    pub fn section_disabled(&self, id: &str) -> Option<bool> {
+        let (prefix, index) = id.split_once('.')?;
+        match prefix {
+            "webserver" => self.webserver.get(index).map(|s| s.disabled),
+            "mevo" => self.mevo.get(index).map(|s| s.disabled),
+            "r10" => self.r10.get(index).map(|s| s.disabled),
+            "square" => self.square.get(index).map(|s| s.disabled),
+            "openconnect_server" => self.openconnect_server.get(index).map(|s| s.disabled),
+            "gspro" => self.gspro.get(index).map(|s| s.disabled),
+            "mock_monitor" => self.mock_monitor.get(index).map(|s| s.disabled),
+            "random_club" => self.random_club.get(index).map(|s| s.disabled),
+            "plugin" => self.plugin.get(index).map(|s| s.disabled),
+            "script" => self.script.get(index).map(|s| s.disabled),
+            "process" => self.process.get(index).map(|s| s.disabled),
+            _ => None,
+        }
+    }
+
+    /// The mode launch monitor `id` (e.g. `"mevo.0"`) uses while the global
+    /// mode is `global`, or `None` for an actor without a mode setting.
+    pub fn device_mode(&self, id: &str, global: ShotDetectionMode) -> Option<ShotDetectionMode> {
+        let (prefix, index) = id.split_once('.')?;
+        let mode = match prefix {
+            "mevo" => self.mevo.get(index).map(|s| s.mode),
+            "r10" => self.r10.get(index).map(|s| s.mode),
+            "square" => self.square.get(index).map(|s| s.mode),
+            "mock_monitor" => self.mock_monitor.get(index).map(|s| s.mode),
+            "openconnect_server" => self.openconnect_server.get(index).map(|s| s.mode),
+            _ => None,
+        }?;
+        Some(mode.effective(global))
+    }
+
+    /// Mode settings of every launch monitor with one, keyed by global ID.
+    pub fn device_modes(&self) -> std::collections::HashMap<String, DeviceModeSection> {
+        let mevo = self.mevo.iter().map(|(i, s)| (format!("mevo.{i}"), s.mode));
+        let r10 = self.r10.iter().map(|(i, s)| (format!("r10.{i}"), s.mode));
+        let square = self
+            .square
+            .iter()
+            .map(|(i, s)| (format!("square.{i}"), s.mode));
+        let mock = self
+            .mock_monitor
+            .iter()
+            .map(|(i, s)| (format!("mock_monitor.{i}"), s.mode));
+        let openconnect = self
+            .openconnect_server
+            .iter()
+            .map(|(i, s)| (format!("openconnect_server.{i}"), s.mode));
+        mevo.chain(r10)
+            .chain(square)
+            .chain(mock)
+            .chain(openconnect)
+            .collect()
+    }
+
+    /// All profile names, active one included, sorted.
+    pub fn profile_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.profiles.keys().cloned().collect();
+        if !self.profiles.contains_key(&self.active_profile) {
+            names.push(self.active_profile.clone());
+        }
+        names.sort();
+        names
+    }
+
+    /// Copy of the live device and integration sections, routing rule sets
+    /// and dedup policy.
+    pub fn current_profile(&self) -> ConfigProfile {
+        ConfigProfile {
+            mevo: self.mevo.clone(),
+            r10: self.r10.clone(),
+            square: self.square.clone(),
+            mock_monitor: self.mock_monitor.clone(),
+            openconnect_server: self.openconnect_server.clone(),
+            gspro: self.gspro.clone(),
+            random_club: self.random_club.clone(),
+            plugin: self.plugin.clone(),
+            script: self.script.clone(),
+            process: self.process.clone(),
+            routing: self.routing.clone(),
+            dedup: self.dedup.clone(),
+        }
+    }
+
+    /// Make `name` the active profile: the live sections are saved under the
+    /// current profile's name and replaced by those of `name`. Returns
+    /// `false` (and changes nothing) when there is no such profile.
+    pub fn switch_profile(&mut self, name: &str) -> bool {
+        if name == self.active_profile {
+            return true;
+        }
+        let Some(next) = self.profiles.remove(name) else {
+            return false;
+        };
+        let current = self.current_profile();
+        let previous = std::mem::replace(&mut self.active_profile, name.to_string());
+        self.profiles.insert(previous, current);
+        self.mevo = next.mevo;
+        self.r10 = next.r10;
+        self.square = next.square;
+        self.mock_monitor = next.mock_monitor;
+        self.openconnect_server = next.openconnect_server;
+        self.gspro = next.gspro;
+        self.random_club = next.random_club;
+        self.plugin = next.plugin;
+        self.script = next.script;
+        self.process = next.process;
+        self.routing = next.routing;
+        self.dedup = next.dedup;
+        true
     }
 }
 
@@ -392,13 +1007,23 @@ impl Default for FlighthookConfig {
             "0".into(),
             WebserverSection {
                 name: "Web Server".into(),
+                disabled: false,
                 bind: "0.0.0.0:5880".into(),
+                tokens: Vec::new(),
+                tls_cert: None,
+                tls_key: None,
+                tls_self_signed: None,
             },
         );
         Self {
             default_units: UnitSystem::default(),
             chipping_clubs: default_chipping_clubs(),
             putting_clubs: default_putting_clubs(),
+            active_profile: default_profile(),
+            supervisor: SupervisorSection::default(),
+            health: HealthSection::default(),
+            routing: std::collections::HashMap::new(),
+            dedup: DedupSection::default(),
             webserver,
             mevo: std::collections::HashMap::new(),
             r10: std::collections::HashMap::new(),
@@ -407,6 +1032,10 @@ impl Default for FlighthookConfig {
             openconnect_server: std::collections::HashMap::new(),
             gspro: std::collections::HashMap::new(),
             random_club: std::collections::HashMap::new(),
+            plugin: std::collections::HashMap::new(),
+            script: std::collections::HashMap::new(),
+            process: std::collections::HashMap::new(),
+            profiles: std::collections::HashMap::new(),
         }
     }
 }
@@ -415,6 +1044,7 @@ impl Default for MevoSection {
     fn default() -> Self {
         Self {
             name: "Mevo WiFi".into(),
+            disabled: false,
             address: Some("192.168.2.1:5100".into()),
             ball_type: Some(0),
             tee_height: Some(Distance::Inches(1.5)),
@@ -423,6 +1053,7 @@ impl Default for MevoSection {
             track_pct: Some(80.0),
             use_estimated: None,
             camera_mode: None,
+            mode: DeviceModeSection::default(),
         }
     }
 }
@@ -431,8 +1062,10 @@ impl Default for R10Section {
     fn default() -> Self {
         Self {
             name: "Garmin R10".into(),
+            disabled: false,
             // Absent: leave the device's own tee distance untouched.
             range: None,
+            mode: DeviceModeSection::default(),
         }
     }
 }
@@ -441,11 +1074,13 @@ impl Default for SquareSection {
     fn default() -> Self {
         Self {
             name: "Square Golf Omni".into(),
+            disabled: false,
             // Auto-discover by name prefix.
             address: None,
             club: None,
             advanced_spin: None,
             discard_non_putting_zero_spin: Some(true),
+            mode: DeviceModeSection::default(),
         }
     }
 }
@@ -454,7 +1089,9 @@ impl Default for OpenConnectServerSection {
     fn default() -> Self {
         Self {
             name: "OpenConnect Server".into(),
+            disabled: false,
             bind: Some("0.0.0.0:921".into()),
+            mode: DeviceModeSection::default(),
         }
     }
 }
@@ -463,10 +1100,12 @@ impl Default for GsProSection {
     fn default() -> Self {
         Self {
             name: "Local GSPro".into(),
+            disabled: false,
             address: Some("127.0.0.1:921".into()),
             full_monitor: None,
             chipping_monitor: None,
             putting_monitor: None,
+            routing: None,
         }
     }
 }
@@ -506,3 +1145,192 @@ mod camera_mode_tests {
         assert!(CameraMode::RawFusion.is_fusion());
     }
 }
+
+#[cfg(test)]
+mod token_tests {
+    use super::*;
+
+    fn section(tokens: &[(&str, TokenScope)]) -> WebserverSection {
+        WebserverSection {
+            name: "Web Server".into(),
+            disabled: false,
+            bind: "0.0.0.0:5880".into(),
+            tokens: tokens
+                .iter()
+                .map(|(token, scope)| ApiToken {
+                    name: String::new(),
+                    token: (*token).into(),
+                    scope: *scope,
+                })
+                .collect(),
+            tls_cert: None,
+            tls_key: None,
+            tls_self_signed: None,
+        }
+    }
+
+    #[test]
+    fn no_tokens_means_open_api() {
+        let s = section(&[]);
+        assert_eq!(s.granted_scope(None), Some(TokenScope::Admin));
+        assert_eq!(s.granted_scope(Some("anything")), Some(TokenScope::Admin));
+    }
+
+    #[test]
+    fn tokens_grant_their_scope() {
+        let s = section(&[("r", TokenScope::Read), ("c", TokenScope::Control)]);
+        assert_eq!(s.granted_scope(None), None);
+        assert_eq!(s.granted_scope(Some("x")), None);
+        assert_eq!(s.granted_scope(Some("r")), Some(TokenScope::Read));
+        assert_eq!(s.granted_scope(Some("c")), Some(TokenScope::Control));
+        assert!(TokenScope::Admin > TokenScope::Control);
+        assert!(TokenScope::Control > TokenScope::Read);
+    }
+
+    #[test]
+    fn tokens_parse_from_toml_shape() {
+        let json =
+            r#"{"bind":"0.0.0.0:5880","tokens":[{"name":"tablet","token":"abc","scope":"read"}]}"#;
+        let s: WebserverSection = serde_json::from_str(json).expect("parse");
+        assert_eq!(s.tokens[0].scope, TokenScope::Read);
+        let without = serde_json::to_string(&section(&[])).expect("serialize");
+        assert!(!without.contains("tokens"), "{without}");
+    }
+}
+
+#[cfg(test)]
+mod disabled_tests {
+    use super::*;
+
+    #[test]
+    fn disabled_round_trips_and_is_omitted_when_false() {
+        let on: GsProSection = serde_json::from_str(r#"{"name":"GSPro"}"#).expect("parse");
+        assert!(!on.disabled);
+        let json = serde_json::to_value(&on).unwrap();
+        assert!(json.get("disabled").is_none());
+
+        let off: GsProSection =
+            serde_json::from_str(r#"{"name":"GSPro","disabled":true}"#).expect("parse");
+        assert!(off.disabled);
+        assert_eq!(serde_json::to_value(&off).unwrap()["disabled"], true);
+    }
+
+    #[test]
+    fn section_disabled_by_global_id() {
+        let mut config = FlighthookConfig::default();
+        config.gspro.insert(
+            "0".into(),
+            GsProSection {
+                disabled: true,
+                ..GsProSection::default()
+            },
+        );
+        assert_eq!(config.section_disabled("gspro.0"), Some(true));
+        assert_eq!(config.section_disabled("webserver.0"), Some(false));
+        assert_eq!(config.section_disabled("gspro.1"), None);
+        assert_eq!(config.section_disabled("system"), None);
+    }
+}
+
+#[cfg(test)]
+mod supervisor_tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn default_policy_backs_off_to_a_minute() {
+        let policy = SupervisorSection::default();
+        assert_eq!(policy.restart_delay(1), Some(Duration::from_secs(1)));
+        assert_eq!(policy.restart_delay(3), Some(Duration::from_secs(4)));
+        assert_eq!(policy.restart_delay(7), Some(Duration::from_secs(60)));
+        assert_eq!(policy.restart_delay(200), Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn policy_can_limit_or_disable_restarts() {
+        let limited = SupervisorSection {
+            max_restarts: Some(2),
+            ..Default::default()
+        };
+        assert!(limited.restart_delay(2).is_some());
+        assert_eq!(limited.restart_delay(3), None);
+
+        let off = SupervisorSection {
+            restart: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(off.restart_delay(1), None);
+    }
+}
+
+#[cfg(test)]
+mod device_mode_tests {
+    use super::*;
+
+    #[test]
+    fn pinned_wins_over_the_mapping_and_unmapped_modes_pass_through() {
+        let map: DeviceModeSection = serde_json::from_str(r#"{"chipping":"full"}"#).expect("parse");
+        assert_eq!(
+            map.effective(ShotDetectionMode::Chipping),
+            ShotDetectionMode::Full
+        );
+        assert_eq!(
+            map.effective(ShotDetectionMode::Putting),
+            ShotDetectionMode::Putting
+        );
+
+        let pinned = DeviceModeSection {
+            pinned: Some(ShotDetectionMode::Putting),
+            ..map
+        };
+        for global in [ShotDetectionMode::Full, ShotDetectionMode::Chipping] {
+            assert_eq!(pinned.effective(global), ShotDetectionMode::Putting);
+        }
+    }
+
+    #[test]
+    fn device_mode_by_global_id() {
+        let mut config = FlighthookConfig::default();
+        config.square.insert(
+            "0".into(),
+            SquareSection {
+                mode: DeviceModeSection {
+                    pinned: Some(ShotDetectionMode::Putting),
+                    ..Default::default()
+                },
+                ..SquareSection::default()
+            },
+        );
+        config.mevo.insert("0".into(), MevoSection::default());
+        let full = ShotDetectionMode::Full;
+        assert_eq!(
+            config.device_mode("square.0", full),
+            Some(ShotDetectionMode::Putting)
+        );
+        assert_eq!(config.device_mode("mevo.0", full), Some(full));
+        assert_eq!(config.device_mode("gspro.0", full), None);
+        assert!(
+            serde_json::to_value(&config.mevo["0"])
+                .unwrap()
+                .get("mode")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn openconnect_server_mode_is_reported_like_a_monitor() {
+        let mut config = FlighthookConfig::default();
+        let section: OpenConnectServerSection =
+            serde_json::from_str(r#"{"mode":{"pinned":"putting"}}"#).expect("parse");
+        config.openconnect_server.insert("0".into(), section);
+        let full = ShotDetectionMode::Full;
+        assert_eq!(
+            config.device_mode("openconnect_server.0", full),
+            Some(ShotDetectionMode::Putting)
+        );
+        assert_eq!(
+            config.device_modes()["openconnect_server.0"].pinned,
+            Some(ShotDetectionMode::Putting)
+        );
+    }
+}
@@ -0,0 +1,321 @@
+//! Duplicate shot suppression: one swing seen by several monitors.
+
+use std::cmp::Ordering;
+
+use crate::{DedupPreference, DedupSection, ShotData};
+
+/// How long a handed-out swing still takes duplicates that finish late: a
+/// slow monitor's shot can finish well after its window closed, though it
+/// was triggered inside it.
+pub const DEDUP_LATE_GRACE_MS: u64 = 10_000;
+
+/// Finished shots held until their swing's window closes.
+#[derive(Debug)]
+struct Swing<T> {
+    /// Trigger time of the first shot in the swing.
+    triggered_ms: u64,
+    /// When the swing is handed out.
+    due_ms: u64,
+    /// In arrival order.
+    shots: Vec<T>,
+    /// For late duplicates: the swing's shot, handed out earlier.
+    kept: Option<T>,
+}
+
+/// A swing already handed out, remembered for late duplicates.
+#[derive(Debug)]
+struct Released<T> {
+    triggered_ms: u64,
+    released_ms: u64,
+    /// Every actor with a shot in the swing.
+    actors: Vec<String>,
+    kept: T,
+}
+
+/// One swing: the shot to forward and the duplicates dropped in its favour.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DedupOutcome<T> {
+    pub kept: T,
+    pub dropped: Vec<T>,
+    /// `kept` was handed out in an earlier outcome; `dropped` are duplicates
+    /// of it that finished after its window closed. Only they need handling.
+    pub late: bool,
+}
+
+/// Applies a [`DedupSection`] to finished shots.
+///
+/// [`offer`](Self::offer) every shot an integration would forward, then
+/// collect swings whose window has closed with [`due`](Self::due). Shots from
+/// different actors triggered within `window_ms` of a swing's first shot join
+/// that swing; the best one by the configured preferences is kept. A shot
+/// whose swing was already handed out (within [`DEDUP_LATE_GRACE_MS`]) is
+/// still dropped, in a `late` outcome due at once. With suppression off,
+/// every shot is its own swing and is due at once.
+///
+/// `T` is whatever the integration needs to send the shot later.
+#[derive(Debug)]
+pub struct ShotDeduplicator<T> {
+    dedup: DedupSection,
+    swings: Vec<Swing<T>>,
+    released: Vec<Released<T>>,
+}
+
+impl<T: AsRef<ShotData> + Clone> ShotDeduplicator<T> {
+    pub fn new(dedup: DedupSection) -> Self {
+        Self {
+            dedup,
+            swings: Vec::new(),
+            released: Vec::new(),
+        }
+    }
+
+    /// Hold a finished shot that was triggered at `triggered_ms`.
+    pub fn offer(&mut self, shot: T, triggered_ms: u64, now_ms: u64) {
+        let Some(window) = self.dedup.window_ms() else {
+            self.swings.push(Swing {
+                triggered_ms,
+                due_ms: now_ms,
+                shots: vec![shot],
+                kept: None,
+            });
+            return;
+        };
+        let actor = shot.as_ref().actor.clone();
+        let swing = self.swings.iter_mut().find(|s| {
+            s.kept.is_none()
+                && s.triggered_ms.abs_diff(triggered_ms) <= window
+                && s.shots.iter().all(|other| other.as_ref().actor != actor)
+        });
+        if let Some(swing) = swing {
+            swing.shots.push(shot);
+            return;
+        }
+
+        self.released
+            .retain(|r| now_ms.saturating_sub(r.released_ms) <= DEDUP_LATE_GRACE_MS);
+        let released = self.released.iter_mut().find(|r| {
+            r.triggered_ms.abs_diff(triggered_ms) <= window && !r.actors.contains(&actor)
+        });
+        match released {
+            Some(released) => {
+                released.actors.push(actor);
+                self.swings.push(Swing {
+                    triggered_ms,
+                    due_ms: now_ms,
+                    shots: vec![shot],
+                    kept: Some(released.kept.clone()),
+                });
+            }
+            None => self.swings.push(Swing {
+                triggered_ms,
+                due_ms: now_ms + window,
+                shots: vec![shot],
+                kept: None,
+            }),
+        }
+    }
+
+    /// Swings whose window has closed by `now_ms`, oldest first.
+    pub fn due(&mut self, now_ms: u64) -> Vec<DedupOutcome<T>> {
+        let (due, held) = std::mem::take(&mut self.swings)
+            .into_iter()
+            .partition::<Vec<_>, _>(|s| s.due_ms <= now_ms);
+        self.swings = held;
+        due.into_iter()
+            .map(|s| match s.kept {
+                Some(kept) => DedupOutcome {
+                    kept,
+                    dropped: s.shots,
+                    late: true,
+                },
+                None => {
+                    let outcome = self.pick(s.shots);
+                    if self.dedup.window_ms().is_some() {
+                        let dropped = outcome.dropped.iter();
+                        self.released.push(Released {
+                            triggered_ms: s.triggered_ms,
+                            released_ms: now_ms,
+                            actors: std::iter::once(&outcome.kept)
+                                .chain(dropped)
+                                .map(|shot| shot.as_ref().actor.clone())
+                                .collect(),
+                            kept: outcome.kept.clone(),
+                        });
+                    }
+                    outcome
+                }
+            })
+            .collect()
+    }
+
+    /// When the next held swing is due, if any is held.
+    pub fn next_due_ms(&self) -> Option<u64> {
+        self.swings.iter().map(|s| s.due_ms).min()
+    }
+
+    /// Best shot first; ties keep arrival order.
+    fn pick(&self, mut shots: Vec<T>) -> DedupOutcome<T> {
+        shots.sort_by(|a, b| self.compare(a.as_ref(), b.as_ref()));
+        let mut shots = shots.into_iter();
+        let kept = shots.next().expect("a swing holds at least one shot");
+        DedupOutcome {
+            kept,
+            dropped: shots.collect(),
+            late: false,
+        }
+    }
+
+    /// `Less` when `a` is preferred.
+    fn compare(&self, a: &ShotData, b: &ShotData) -> Ordering {
+        self.dedup
+            .preferences()
+            .iter()
+            .map(|pref| match pref {
+                DedupPreference::Priority => self.rank(a).cmp(&self.rank(b)),
+                DedupPreference::Complete => field_count(b).cmp(&field_count(a)),
+                DedupPreference::Measured => a.estimated.cmp(&b.estimated),
+            })
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+
+    /// Position in `priority`; unlisted actors rank last.
+    fn rank(&self, shot: &ShotData) -> usize {
+        let priority = &self.dedup.priority;
+        priority
+            .iter()
+            .position(|id| *id == shot.actor)
+            .unwrap_or(priority.len())
+    }
+}
+
+/// Number of data fields present in the shot's ball, club and impact data.
+fn field_count(shot: &ShotData) -> usize {
+    fn count(value: &serde_json::Value) -> usize {
+        match value {
+            serde_json::Value::Null => 0,
+            serde_json::Value::Object(map) => map.values().map(count).sum(),
+            serde_json::Value::Array(items) => items.iter().map(count).sum(),
+            _ => 1,
+        }
+    }
+    [
+        serde_json::to_value(&shot.ball),
+        serde_json::to_value(&shot.club),
+        serde_json::to_value(&shot.impact),
+    ]
+    .iter()
+    .flatten()
+    .map(count)
+    .sum()
+}
+
+impl AsRef<ShotData> for ShotData {
+    fn as_ref(&self) -> &ShotData {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BallFlight, ClubData, Velocity};
+
+    fn shot(actor: &str, estimated: bool, club: bool) -> ShotData {
+        ShotData {
+            actor: actor.into(),
+            shot_number: 1,
+            ball: Some(BallFlight {
+                launch_speed: Some(Velocity::MetersPerSecond(60.0)),
+                ..Default::default()
+            }),
+            club: club.then(|| ClubData {
+                club_speed: Some(Velocity::MetersPerSecond(40.0)),
+                ..Default::default()
+            }),
+            impact: None,
+            estimated,
+        }
+    }
+
+    fn actors(outcome: &DedupOutcome<ShotData>) -> (&str, Vec<&str>) {
+        let dropped = outcome.dropped.iter().map(|s| s.actor.as_str()).collect();
+        (outcome.kept.actor.as_str(), dropped)
+    }
+
+    fn dedup(prefer: Vec<DedupPreference>) -> ShotDeduplicator<ShotData> {
+        ShotDeduplicator::new(DedupSection {
+            window_ms: Some(500),
+            prefer,
+            priority: vec!["r10.0".into(), "mevo.0".into()],
+        })
+    }
+
+    #[test]
+    fn shots_within_the_window_are_one_swing() {
+        let mut dedup = dedup(vec![DedupPreference::Priority]);
+        dedup.offer(shot("mevo.0", false, false), 1_000, 1_100);
+        dedup.offer(shot("r10.0", false, false), 1_200, 1_300);
+        // Same actor again: a new swing, even inside the window.
+        dedup.offer(shot("mevo.0", false, false), 1_300, 1_400);
+        assert_eq!(dedup.next_due_ms(), Some(1_600));
+        assert!(dedup.due(1_599).is_empty());
+
+        let due = dedup.due(1_600);
+        assert_eq!(due.len(), 1);
+        assert_eq!(actors(&due[0]), ("r10.0", vec!["mevo.0"]));
+        assert_eq!(dedup.due(1_900).len(), 1);
+        assert_eq!(dedup.next_due_ms(), None);
+    }
+
+    #[test]
+    fn preferences_break_ties_in_order() {
+        let offer = |dedup: &mut ShotDeduplicator<ShotData>| {
+            dedup.offer(shot("mevo.0", true, true), 0, 0);
+            dedup.offer(shot("r10.0", false, false), 0, 0);
+            dedup.offer(shot("square.0", false, true), 0, 0);
+            dedup.due(1_000).remove(0)
+        };
+
+        let mut measured = dedup(vec![DedupPreference::Measured, DedupPreference::Priority]);
+        assert_eq!(actors(&offer(&mut measured)).0, "r10.0");
+
+        let mut complete = dedup(vec![DedupPreference::Complete, DedupPreference::Priority]);
+        assert_eq!(actors(&offer(&mut complete)).0, "mevo.0");
+
+        // Default: measured, then complete, then priority.
+        let mut default = dedup(Vec::new());
+        assert_eq!(
+            actors(&offer(&mut default)),
+            ("square.0", vec!["r10.0", "mevo.0"])
+        );
+    }
+
+    #[test]
+    fn late_duplicate_of_a_released_swing_is_dropped() {
+        let mut dedup = dedup(vec![DedupPreference::Priority]);
+        dedup.offer(shot("mevo.0", false, false), 1_000, 1_100);
+        let due = dedup.due(1_600);
+        assert_eq!(actors(&due[0]), ("mevo.0", vec![]));
+
+        // The R10 triggered with the Mevo but only finishes after `due`.
+        dedup.offer(shot("r10.0", false, false), 1_050, 2_400);
+        let due = dedup.due(2_400);
+        assert_eq!(due.len(), 1);
+        assert!(due[0].late);
+        assert_eq!(actors(&due[0]), ("mevo.0", vec!["r10.0"]));
+
+        // A new swing from the R10 is not a duplicate.
+        dedup.offer(shot("r10.0", false, false), 5_000, 5_100);
+        assert!(dedup.due(5_100).is_empty());
+        assert!(!dedup.due(5_600)[0].late);
+    }
+
+    #[test]
+    fn without_a_window_every_shot_is_due_at_once() {
+        let mut dedup = ShotDeduplicator::new(DedupSection::default());
+        dedup.offer(shot("mevo.0", false, false), 0, 10);
+        dedup.offer(shot("r10.0", false, false), 0, 10);
+        assert_eq!(dedup.due(10).len(), 2);
+    }
+}
@@ -16,16 +16,29 @@ use crate::{ShotKey, UnitSystem};
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ShotData {
     #[serde(default)]
     pub actor: String,
     pub shot_number: u32,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "Option<crate::schema::BallFlight>")
+    )]
     pub ball: Option<BallFlight>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<crate::schema::ClubData>"))]
     pub club: Option<ClubData>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "Option<crate::schema::FaceImpact>")
+    )]
     pub impact: Option<FaceImpact>,
+    /// The ball flight was estimated by the device, not measured.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub estimated: bool,
 }
 
 impl ShotData {
@@ -71,6 +84,7 @@ impl ShotData {
             ball,
             club,
             impact: self.impact.clone(),
+            estimated: self.estimated,
         }
     }
 
@@ -110,6 +124,7 @@ impl ShotData {
             ball,
             club,
             impact: self.impact.clone(),
+            estimated: self.estimated,
         }
     }
 }
@@ -120,6 +135,7 @@ impl ShotData {
 
 /// Generic actor lifecycle status.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum ActorStatus {
     Starting,
@@ -152,9 +168,12 @@ impl std::fmt::Display for ActorStatus {
 pub struct ShotAccumulator {
     pub actor: String,
     pub key: ShotKey,
+    /// `timestamp_ms` of the `ShotTrigger`, if it carried one.
+    pub triggered_ms: Option<u64>,
     ball: Option<BallFlight>,
     club: Option<ClubData>,
     impact: Option<FaceImpact>,
+    estimated: bool,
 }
 
 impl ShotAccumulator {
@@ -163,17 +182,37 @@ impl ShotAccumulator {
         Self {
             actor,
             key,
+            triggered_ms: None,
             ball: None,
             club: None,
             impact: None,
+            estimated: false,
         }
     }
 
+    /// Record when the shot was triggered (the `ShotTrigger`'s `timestamp_ms`).
+    pub fn triggered_at(mut self, timestamp_ms: Option<u64>) -> Self {
+        self.triggered_ms = timestamp_ms;
+        self
+    }
+
+    /// Milliseconds from the trigger to `finished_ms` (the `ShotFinished`'s
+    /// `timestamp_ms`), when both are known.
+    pub fn latency_to(&self, finished_ms: Option<u64>) -> Option<u64> {
+        Some(finished_ms?.saturating_sub(self.triggered_ms?))
+    }
+
     /// Record ball flight data.
     pub fn set_ball(&mut self, ball: BallFlight) {
         self.ball = Some(ball);
     }
 
+    /// Mark the ball flight as estimated rather than measured (the
+    /// `BallFlight` event's `estimated` flag).
+    pub fn set_estimated(&mut self, estimated: bool) {
+        self.estimated = estimated;
+    }
+
     /// Record club path data.
     pub fn set_club(&mut self, club: ClubData) {
         self.club = Some(club);
@@ -195,6 +234,7 @@ impl ShotAccumulator {
             ball: self.ball,
             club: self.club,
             impact: self.impact,
+            estimated: self.estimated,
         })
     }
 }
@@ -240,9 +280,14 @@ impl ShotAggregator {
                 self.pending.insert((msg.actor.clone(), key.clone()), acc);
                 None
             }
-            crate::FlighthookEvent::BallFlight { key, ball } => {
+            crate::FlighthookEvent::BallFlight {
+                key,
+                ball,
+                estimated,
+            } => {
                 if let Some(acc) = self.pending.get_mut(&(msg.actor.clone(), key.clone())) {
                     acc.set_ball(*ball.clone());
+                    acc.set_estimated(*estimated);
                 }
                 None
             }
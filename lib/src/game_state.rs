@@ -12,6 +12,7 @@ use crate::{Handedness, ShotDetectionMode};
 /// `Display` returns the same code. `from_code()` parses case-insensitively.
 /// `mode()` maps to the appropriate `ShotDetectionMode`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Club {
     #[serde(rename = "DR")]
     Driver,
@@ -148,6 +149,7 @@ impl fmt::Display for Club {
 
 /// Player info (name).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PlayerInfo {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
@@ -155,6 +157,7 @@ pub struct PlayerInfo {
 
 /// Club selection.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ClubInfo {
     pub club: Club,
 }
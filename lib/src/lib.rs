@@ -1,18 +1,30 @@
 mod api;
+#[cfg(feature = "async-client")]
+mod async_client;
 #[cfg(feature = "client")]
 mod client;
 mod config;
+mod dedup;
 mod event;
 mod game_state;
 mod message;
+mod routing;
+#[cfg(feature = "schema")]
+pub mod schema;
+mod validation;
 
 pub use api::*;
+#[cfg(feature = "async-client")]
+pub use async_client::*;
 #[cfg(feature = "client")]
 pub use client::*;
 pub use config::*;
+pub use dedup::*;
 pub use event::*;
 pub use game_state::*;
 pub use message::*;
+pub use routing::*;
+pub use validation::*;
 
 // Re-export flightrelay types used throughout flighthook.
 pub use flightrelay::types::{BallFlight, ClubData, FaceImpact};
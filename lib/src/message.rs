@@ -1,8 +1,9 @@
 //! Unified `FlighthookMessage` bus types.
 //!
-//! All events flow through a single `broadcast<FlighthookMessage>` channel.
+//! All events flow through a single bus of `FlighthookMessage`s.
 //! Each message has an actor ID, optional device (FRP physical unit ID),
-//! optional raw payload (hex-first policy), and a typed event.
+//! origin timestamp and bus sequence number, optional raw payload (hex-first
+//! policy), and a typed event.
 //!
 //! ## Wire format (FRP envelope)
 //!
@@ -11,8 +12,9 @@
 //! { "actor": "mevo.0", "device": "FS-M2-XXXXXX", "event": { "kind": "ball_flight", ... } }
 //! ```
 //!
-//! `actor` is a flighthook extension (actor framework ID). `device` is the
-//! FRP device identifier (present on device events, absent on extensions).
+//! `actor`, `timestamp_ms` and `bus_seq` are flighthook extensions. `device`
+//! is the FRP device identifier (present on device events, absent on
+//! extensions).
 //! FRP consumers silently ignore unknown `kind` values and extra fields per spec.
 //!
 //! FRP-compliant events: `ShotTrigger`, `BallFlight`, `ClubPath`, `FaceImpact`,
@@ -30,7 +32,8 @@ use crate::{ActorStatus, BallFlight, ClubData, FaceImpact};
 use crate::{ClubInfo, PlayerInfo};
 use crate::{
     FlighthookConfig, GsProSection, MevoSection, MockMonitorSection, OpenConnectServerSection,
-    R10Section, RandomClubSection, SquareSection, WebserverSection,
+    PluginSection, ProcessSection, R10Section, RandomClubSection, ScriptSection, SquareSection,
+    WebserverSection,
 };
 
 // ---------------------------------------------------------------------------
@@ -42,6 +45,7 @@ use crate::{
 /// Uses the FRP envelope shape: `{ actor, device?, event: { kind, ... } }`.
 /// `actor` is a flighthook extension field. FRP consumers ignore unknown fields.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FlighthookMessage {
     /// Actor ID of the originator (e.g. "mevo.0", "gspro.0", "system").
     #[serde(default)]
@@ -51,19 +55,40 @@ pub struct FlighthookMessage {
     /// system/config/actor-status events.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub device: Option<String>,
+    /// Wall-clock time the message was created at its origin, in Unix epoch
+    /// milliseconds. Set by `new()`; the bus stamps messages that arrive
+    /// without one (e.g. from WebSocket clients that leave it out).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_ms: Option<u64>,
+    /// Position in the order messages were put on the bus, assigned by the
+    /// bus. Every subscriber sees one sender's messages in increasing
+    /// `bus_seq`, with gaps for messages it filtered out or missed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bus_seq: Option<u64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub raw_payload: Option<RawPayload>,
     pub event: FlighthookEvent,
 }
 
+/// Current wall-clock time in Unix epoch milliseconds, as used for
+/// `FlighthookMessage::timestamp_ms`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 impl FlighthookMessage {
-    /// Create a new message. Use `.actor()`, `.device()`, and
-    /// `.raw()` / `.raw_binary()` to attach metadata.
+    /// Create a new message stamped with the current time. Use `.actor()`,
+    /// `.device()`, and `.raw()` / `.raw_binary()` to attach metadata.
     pub fn new(event: impl Into<FlighthookEvent>) -> Self {
         Self {
             actor: String::new(),
             device: None,
+            timestamp_ms: Some(now_ms()),
+            bus_seq: None,
             raw_payload: None,
             event: event.into(),
         }
@@ -152,22 +177,45 @@ pub use flightrelay::ShotKey;
 /// Flighthook extensions: everything else. FRP-only consumers silently ignore
 /// unknown `kind` values per spec.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "kind", rename_all = "snake_case")]
 pub enum FlighthookEvent {
     // -- FRP: Shot lifecycle (correlated by ShotKey + device) --
     /// Ball strike detected. Emitted immediately — no data yet.
-    ShotTrigger { key: ShotKey },
+    ShotTrigger {
+        #[cfg_attr(feature = "schema", schemars(with = "crate::schema::ShotKey"))]
+        key: ShotKey,
+    },
     /// Ball flight data available.
-    BallFlight { key: ShotKey, ball: Box<BallFlight> },
+    BallFlight {
+        #[cfg_attr(feature = "schema", schemars(with = "crate::schema::ShotKey"))]
+        key: ShotKey,
+        #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BallFlight"))]
+        ball: Box<BallFlight>,
+        /// The device estimated the flight rather than measuring it (e.g.
+        /// the Mevo's E8 fallback).
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        estimated: bool,
+    },
     /// Club path data available.
-    ClubPath { key: ShotKey, club: Box<ClubData> },
+    ClubPath {
+        #[cfg_attr(feature = "schema", schemars(with = "crate::schema::ShotKey"))]
+        key: ShotKey,
+        #[cfg_attr(feature = "schema", schemars(with = "crate::schema::ClubData"))]
+        club: Box<ClubData>,
+    },
     /// Face impact location available.
     FaceImpact {
+        #[cfg_attr(feature = "schema", schemars(with = "crate::schema::ShotKey"))]
         key: ShotKey,
+        #[cfg_attr(feature = "schema", schemars(with = "crate::schema::FaceImpact"))]
         impact: Box<FaceImpact>,
     },
     /// Shot sequence complete. Accumulators should finalize.
-    ShotFinished { key: ShotKey },
+    ShotFinished {
+        #[cfg_attr(feature = "schema", schemars(with = "crate::schema::ShotKey"))]
+        key: ShotKey,
+    },
 
     // -- FRP: Device status --
     /// Device telemetry — emitted any time a device-reported value changes.
@@ -190,6 +238,7 @@ pub enum FlighthookEvent {
     },
     /// Alert for warn/error/critical conditions.
     Alert {
+        #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Severity"))]
         severity: flightrelay::Severity,
         message: String,
     },
@@ -204,6 +253,10 @@ pub enum FlighthookEvent {
         #[serde(default, skip_serializing_if = "Option::is_none")]
         mode: Option<ShotDetectionMode>,
         #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(
+            feature = "schema",
+            schemars(with = "Option<crate::schema::Handedness>")
+        )]
         handed: Option<Handedness>,
     },
 
@@ -230,6 +283,31 @@ pub enum FlighthookEvent {
         #[serde(default, skip_serializing_if = "Vec::is_empty")]
         started: Vec<String>,
     },
+    /// An integration handed a finished shot on (e.g. sent it to GSPro).
+    /// `actor` is the integration; `key` and `source` identify the shot.
+    ShotDelivered {
+        #[cfg_attr(feature = "schema", schemars(with = "crate::schema::ShotKey"))]
+        key: ShotKey,
+        /// Actor that produced the shot (e.g. `"mevo.0"`).
+        source: String,
+        /// Milliseconds from the shot's `shot_finished` `timestamp_ms` to the
+        /// hand-off.
+        latency_ms: u64,
+    },
+    /// An integration dropped a finished shot as a duplicate of another
+    /// monitor's report of the same swing (`[dedup]`). `actor` is the
+    /// integration; `key` and `source` identify the dropped shot.
+    ShotSuppressed {
+        #[cfg_attr(feature = "schema", schemars(with = "crate::schema::ShotKey"))]
+        key: ShotKey,
+        /// Actor that produced the dropped shot (e.g. `"r10.0"`).
+        source: String,
+        /// The shot forwarded instead.
+        #[cfg_attr(feature = "schema", schemars(with = "crate::schema::ShotKey"))]
+        kept: ShotKey,
+        /// Actor that produced the forwarded shot (e.g. `"mevo.0"`).
+        kept_source: String,
+    },
     /// Actor/connection lifecycle update.
     ///
     /// Carries actor-framework state: connection status enum and actor-specific
@@ -245,6 +323,27 @@ pub enum FlighthookEvent {
 }
 
 impl FlighthookEvent {
+    /// The serde `kind` tag of this event (e.g. `"ball_flight"`).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            FlighthookEvent::ShotTrigger { .. } => "shot_trigger",
+            FlighthookEvent::BallFlight { .. } => "ball_flight",
+            FlighthookEvent::ClubPath { .. } => "club_path",
+            FlighthookEvent::FaceImpact { .. } => "face_impact",
+            FlighthookEvent::ShotFinished { .. } => "shot_finished",
+            FlighthookEvent::DeviceTelemetry { .. } => "device_telemetry",
+            FlighthookEvent::Alert { .. } => "alert",
+            FlighthookEvent::SetDetectionMode { .. } => "set_detection_mode",
+            FlighthookEvent::PlayerInfo { .. } => "player_info",
+            FlighthookEvent::ClubInfo { .. } => "club_info",
+            FlighthookEvent::ConfigCommand { .. } => "config_command",
+            FlighthookEvent::ConfigOutcome { .. } => "config_outcome",
+            FlighthookEvent::ShotDelivered { .. } => "shot_delivered",
+            FlighthookEvent::ShotSuppressed { .. } => "shot_suppressed",
+            FlighthookEvent::ActorStatus { .. } => "actor_status",
+        }
+    }
+
     /// Returns true if this is an ActorStatus event containing telemetry
     /// (battery_pct key in state map). Used for heartbeat filtering in audit.
     pub fn is_actor_status_with_telemetry(&self) -> bool {
@@ -258,6 +357,7 @@ impl FlighthookEvent {
 
 /// The specific config mutation to apply.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ConfigAction {
     /// Replace the entire config. Used by POST /api/settings.
@@ -302,10 +402,211 @@ pub enum ConfigAction {
         index: String,
         section: RandomClubSection,
     },
+    UpsertPlugin {
+        index: String,
+        section: PluginSection,
+    },
+    UpsertScript {
+        index: String,
+        section: ScriptSection,
+    },
+    UpsertProcess {
+        index: String,
+        section: ProcessSection,
+    },
     /// Remove a section by global ID ("mevo.0", "gspro.1", "webserver.0").
     Remove {
         id: String,
     },
+    /// Profiles (see `FlighthookConfig::profiles`).
+    ///
+    /// Make a saved profile the active one, swapping in its sections.
+    SwitchProfile {
+        name: String,
+    },
+    /// Save a copy of the live sections as an inactive profile, replacing
+    /// any profile of that name.
+    SaveProfile {
+        name: String,
+    },
+    /// Delete an inactive profile.
+    DeleteProfile {
+        name: String,
+    },
+    /// Lifecycle control by global ID. These leave the config untouched and
+    /// only use the command/outcome round trip.
+    ///
+    /// Stop and recreate the actor from its current config section.
+    RestartActor {
+        id: String,
+    },
+    /// Stop the actor and keep it stopped across config reloads until
+    /// `StartActor` or the next app start. Use the section's `disabled` flag
+    /// to keep it off permanently.
+    StopActor {
+        id: String,
+    },
+    /// Start an actor previously stopped with `StopActor`.
+    StartActor {
+        id: String,
+    },
+}
+
+impl ConfigAction {
+    /// The serde `type` tag of this action (e.g. `"upsert_mevo"`).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ConfigAction::ReplaceAll { .. } => "replace_all",
+            ConfigAction::UpsertWebserver { .. } => "upsert_webserver",
+            ConfigAction::UpsertMevo { .. } => "upsert_mevo",
+            ConfigAction::UpsertR10 { .. } => "upsert_r10",
+            ConfigAction::UpsertSquare { .. } => "upsert_square",
+            ConfigAction::UpsertOpenConnectServer { .. } => "upsert_open_connect_server",
+            ConfigAction::UpsertGsPro { .. } => "upsert_gs_pro",
+            ConfigAction::UpsertMockMonitor { .. } => "upsert_mock_monitor",
+            ConfigAction::UpsertRandomClub { .. } => "upsert_random_club",
+            ConfigAction::UpsertPlugin { .. } => "upsert_plugin",
+            ConfigAction::UpsertScript { .. } => "upsert_script",
+            ConfigAction::UpsertProcess { .. } => "upsert_process",
+            ConfigAction::Remove { .. } => "remove",
+            ConfigAction::SwitchProfile { .. } => "switch_profile",
+            ConfigAction::SaveProfile { .. } => "save_profile",
+            ConfigAction::DeleteProfile { .. } => "delete_profile",
+            ConfigAction::RestartActor { .. } => "restart_actor",
+            ConfigAction::StopActor { .. } => "stop_actor",
+            ConfigAction::StartActor { .. } => "start_actor",
+        }
+    }
+
+    /// Global ID of the section this action targets (`"mevo.0"`); `None`
+    /// for `ReplaceAll` and the profile actions.
+    pub fn section_id(&self) -> Option<String> {
+        match self {
+            ConfigAction::ReplaceAll { .. }
+            | ConfigAction::SwitchProfile { .. }
+            | ConfigAction::SaveProfile { .. }
+            | ConfigAction::DeleteProfile { .. } => None,
+            ConfigAction::UpsertWebserver { index, .. } => Some(format!("webserver.{index}")),
+            ConfigAction::UpsertMevo { index, .. } => Some(format!("mevo.{index}")),
+            ConfigAction::UpsertR10 { index, .. } => Some(format!("r10.{index}")),
+            ConfigAction::UpsertSquare { index, .. } => Some(format!("square.{index}")),
+            ConfigAction::UpsertOpenConnectServer { index, .. } => {
+                Some(format!("openconnect_server.{index}"))
+            }
+            ConfigAction::UpsertGsPro { index, .. } => Some(format!("gspro.{index}")),
+            ConfigAction::UpsertMockMonitor { index, .. } => Some(format!("mock_monitor.{index}")),
+            ConfigAction::UpsertRandomClub { index, .. } => Some(format!("random_club.{index}")),
+            ConfigAction::UpsertPlugin { index, .. } => Some(format!("plugin.{index}")),
+            ConfigAction::UpsertScript { index, .. } => Some(format!("script.{index}")),
+            ConfigAction::UpsertProcess { index, .. } => Some(format!("process.{index}")),
+            ConfigAction::Remove { id }
+            | ConfigAction::RestartActor { id }
+            | ConfigAction::StopActor { id }
+            | ConfigAction::StartActor { id } => Some(id.clone()),
+        }
+    }
+
+    /// Apply this mutation to `config`. Lifecycle actions leave it untouched.
+    ///
+    /// Returns `false` for a `Remove` whose ID has no known type prefix, a
+    /// switch to or deletion of a profile that does not exist, deleting the
+    /// active profile, or saving over it.
+    pub fn apply(&self, config: &mut FlighthookConfig) -> bool {
+        match self {
+            ConfigAction::ReplaceAll { config: new } => *config = (**new).clone(),
+            ConfigAction::UpsertWebserver { index, section } => {
+                config.webserver.insert(index.clone(), section.clone());
+            }
+            ConfigAction::UpsertMevo { index, section } => {
+                config.mevo.insert(index.clone(), section.clone());
+            }
+            ConfigAction::UpsertR10 { index, section } => {
+                config.r10.insert(index.clone(), section.clone());
+            }
+            ConfigAction::UpsertSquare { index, section } => {
+                config.square.insert(index.clone(), section.clone());
+            }
+            ConfigAction::UpsertOpenConnectServer { index, section } => {
+                config
+                    .openconnect_server
+                    .insert(index.clone(), section.clone());
+            }
+            ConfigAction::UpsertGsPro { index, section } => {
+                config.gspro.insert(index.clone(), section.clone());
+            }
+            ConfigAction::UpsertMockMonitor { index, section } => {
+                config.mock_monitor.insert(index.clone(), section.clone());
+            }
+            ConfigAction::UpsertRandomClub { index, section } => {
+                config.random_club.insert(index.clone(), section.clone());
+            }
+            ConfigAction::UpsertPlugin { index, section } => {
+                config.plugin.insert(index.clone(), section.clone());
+            }
+            ConfigAction::UpsertScript { index, section } => {
+                config.script.insert(index.clone(), section.clone());
+            }
+            ConfigAction::UpsertProcess { index, section } => {
+                config.process.insert(index.clone(), section.clone());
+            }
+            ConfigAction::Remove { id } => {
+                let Some((prefix, index)) = id.split_once('.') else {
+                    return false;
+                };
+                match prefix {
+                    "webserver" => {
+                        config.webserver.remove(index);
+                    }
+                    "mevo" => {
+                        config.mevo.remove(index);
+                    }
+                    "r10" => drop(config.r10.remove(index)),
+                    "square" => {
+                        config.square.remove(index);
+                    }
+                    "openconnect_server" => {
+                        config.openconnect_server.remove(index);
+                    }
+                    "gspro" => {
+                        config.gspro.remove(index);
+                    }
+                    "mock_monitor" => {
+                        config.mock_monitor.remove(index);
+                    }
+                    "random_club" => {
+                        config.random_club.remove(index);
+                    }
+                    "plugin" => {
+                        config.plugin.remove(index);
+                    }
+                    "script" => {
+                        config.script.remove(index);
+                    }
+                    "process" => {
+                        config.process.remove(index);
+                    }
+                    _ => return false,
+                }
+            }
+            ConfigAction::SwitchProfile { name } => return config.switch_profile(name),
+            ConfigAction::SaveProfile { name } => {
+                if *name == config.active_profile {
+                    return false;
+                }
+                let profile = config.current_profile();
+                config.profiles.insert(name.clone(), profile);
+            }
+            ConfigAction::DeleteProfile { name } => {
+                if *name == config.active_profile || config.profiles.remove(name).is_none() {
+                    return false;
+                }
+            }
+            ConfigAction::RestartActor { .. }
+            | ConfigAction::StopActor { .. }
+            | ConfigAction::StartActor { .. } => {}
+        }
+        true
+    }
 }
 
 #[cfg(test)]
@@ -317,6 +618,8 @@ mod tests {
         let msg = FlighthookMessage {
             actor: "mevo.0".into(),
             device: Some("FS-M2-XXXXXX".into()),
+            timestamp_ms: None,
+            bus_seq: None,
             raw_payload: None,
             event: FlighthookEvent::DeviceTelemetry {
                 manufacturer: Some("FlightScope".into()),
@@ -336,6 +639,8 @@ mod tests {
         let msg = FlighthookMessage {
             actor: "webserver.0".into(),
             device: None,
+            timestamp_ms: None,
+            bus_seq: None,
             raw_payload: None,
             event: FlighthookEvent::ActorStatus {
                 status: crate::ActorStatus::Connected,
@@ -354,6 +659,8 @@ mod tests {
         let msg = FlighthookMessage {
             actor: "mevo.0".into(),
             device: Some("FS-M2-XXXXXX".into()),
+            timestamp_ms: None,
+            bus_seq: None,
             raw_payload: None,
             event: FlighthookEvent::ShotFinished {
                 key: ShotKey {
@@ -374,6 +681,8 @@ mod tests {
         let msg = FlighthookMessage {
             actor: "system".into(),
             device: None,
+            timestamp_ms: None,
+            bus_seq: None,
             raw_payload: None,
             event: FlighthookEvent::ActorStatus {
                 status: crate::ActorStatus::Connected,
@@ -392,4 +701,166 @@ mod tests {
             }
         ));
     }
+
+    #[test]
+    fn timestamp_and_bus_seq_are_optional_extensions() {
+        let msg = FlighthookMessage::new(FlighthookEvent::ShotFinished {
+            key: ShotKey {
+                shot_id: "abc".into(),
+                shot_number: 1,
+            },
+        });
+        assert!(msg.timestamp_ms.is_some_and(|t| t > 1_600_000_000_000));
+        let json = serde_json::to_value(&msg).unwrap();
+        assert_eq!(json["timestamp_ms"], msg.timestamp_ms.unwrap());
+        assert!(json.get("bus_seq").is_none());
+
+        // Plain FRP envelopes (no extensions) still parse.
+        let back: FlighthookMessage = serde_json::from_str(
+            r#"{"device":"X","event":{"kind":"shot_trigger","key":{"shot_id":"a","shot_number":2}}}"#,
+        )
+        .unwrap();
+        assert_eq!(back.timestamp_ms, None);
+        assert_eq!(back.bus_seq, None);
+    }
+
+    #[test]
+    fn kind_matches_serde_tag() {
+        let key = ShotKey {
+            shot_id: "550e8400-e29b-41d4-a716-446655440000".into(),
+            shot_number: 1,
+        };
+        let events = [
+            FlighthookEvent::ShotTrigger { key: key.clone() },
+            FlighthookEvent::ShotFinished { key: key.clone() },
+            FlighthookEvent::ShotDelivered {
+                key: key.clone(),
+                source: "mevo.0".into(),
+                latency_ms: 3,
+            },
+            FlighthookEvent::ShotSuppressed {
+                key: key.clone(),
+                source: "r10.0".into(),
+                kept: key,
+                kept_source: "mevo.0".into(),
+            },
+            FlighthookEvent::SetDetectionMode {
+                mode: None,
+                handed: None,
+            },
+            FlighthookEvent::ConfigOutcome {
+                request_id: None,
+                restarted: vec![],
+                stopped: vec![],
+                started: vec![],
+            },
+        ];
+        for event in events {
+            let json = serde_json::to_value(&event).unwrap();
+            assert_eq!(json["kind"], event.kind());
+        }
+    }
+
+    #[test]
+    fn config_action_apply_upserts_and_removes() {
+        let mut config = FlighthookConfig::default();
+        let upsert = ConfigAction::UpsertGsPro {
+            index: "1".into(),
+            section: GsProSection::default(),
+        };
+        assert!(upsert.apply(&mut config));
+        assert_eq!(upsert.section_id().as_deref(), Some("gspro.1"));
+        assert!(config.gspro.contains_key("1"));
+
+        assert!(
+            ConfigAction::Remove {
+                id: "gspro.1".into()
+            }
+            .apply(&mut config)
+        );
+        assert!(config.gspro.is_empty());
+        assert!(
+            !ConfigAction::Remove {
+                id: "bogus.1".into()
+            }
+            .apply(&mut config)
+        );
+        assert!(!ConfigAction::Remove { id: "gspro".into() }.apply(&mut config));
+    }
+
+    #[test]
+    fn config_action_kind_matches_serde_tag() {
+        let actions = [
+            ConfigAction::ReplaceAll {
+                config: Box::default(),
+            },
+            ConfigAction::UpsertOpenConnectServer {
+                index: "0".into(),
+                section: OpenConnectServerSection::default(),
+            },
+            ConfigAction::UpsertGsPro {
+                index: "0".into(),
+                section: GsProSection::default(),
+            },
+            ConfigAction::Remove {
+                id: "mevo.0".into(),
+            },
+            ConfigAction::RestartActor {
+                id: "mevo.0".into(),
+            },
+            ConfigAction::SwitchProfile {
+                name: "putting green".into(),
+            },
+        ];
+        for action in actions {
+            let json = serde_json::to_value(&action).unwrap();
+            assert_eq!(json["type"], action.kind());
+        }
+    }
+
+    #[test]
+    fn profiles_save_switch_and_delete() {
+        let mut config = FlighthookConfig::default();
+        config.mock_monitor.insert(
+            "0".into(),
+            MockMonitorSection {
+                name: "Mock".into(),
+                disabled: false,
+                mode: Default::default(),
+            },
+        );
+        config
+            .routing
+            .insert("bay".into(), crate::RoutingSection::default());
+        let full_swing = config.current_profile();
+
+        let save = |name: &str| ConfigAction::SaveProfile { name: name.into() };
+        let switch = |name: &str| ConfigAction::SwitchProfile { name: name.into() };
+        assert!(save("putting green").apply(&mut config));
+        assert!(!save(crate::DEFAULT_PROFILE).apply(&mut config));
+        assert!(!switch("nope").apply(&mut config));
+
+        // Edit the putting green setup while it is active.
+        assert!(switch("putting green").apply(&mut config));
+        config.mock_monitor.clear();
+        config.routing.clear();
+        assert_eq!(config.profiles[crate::DEFAULT_PROFILE], full_swing);
+        assert_eq!(
+            config.profile_names(),
+            [crate::DEFAULT_PROFILE, "putting green"]
+        );
+
+        // Switching back restores the default setup and keeps the edit.
+        assert!(switch(crate::DEFAULT_PROFILE).apply(&mut config));
+        assert_eq!(config.current_profile(), full_swing);
+        assert!(config.profiles["putting green"].mock_monitor.is_empty());
+        assert!(config.profiles["putting green"].routing.is_empty());
+        assert!(config.webserver.contains_key("0"));
+
+        let delete = |name: &str| ConfigAction::DeleteProfile { name: name.into() };
+        assert!(!delete(crate::DEFAULT_PROFILE).apply(&mut config));
+        assert!(delete("putting green").apply(&mut config));
+        assert!(!delete("putting green").apply(&mut config));
+        assert_eq!(config.profile_names(), [crate::DEFAULT_PROFILE]);
+    }
 }
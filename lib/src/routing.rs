@@ -0,0 +1,387 @@
+//! Shot routing with failover: which monitors' shots an integration takes.
+
+use std::collections::HashMap;
+
+use crate::{
+    ActorStatus, Club, DeviceModeSection, FlighthookEvent, FlighthookMessage, GsProSection,
+    RoutingRule, RoutingSection, ShotData, ShotDetectionMode, ShotPart,
+};
+
+/// What the router knows about one monitor.
+#[derive(Debug, Clone, Default)]
+struct MonitorState {
+    /// Last `ActorStatus` was starting, reconnecting or disconnected.
+    down: bool,
+    /// Last `ready` telemetry, if any.
+    ready: Option<bool>,
+    /// When `ready` went false (ms since the epoch), while it stays false.
+    not_ready_since: Option<u64>,
+}
+
+/// Applies a [`RoutingSection`] to shots.
+///
+/// Feed it every bus message with [`observe`](Self::observe) so it can follow
+/// the detection mode, the selected club, and each monitor's connection and
+/// readiness. Then ask [`route`](Self::route) about each finished shot.
+///
+/// A monitor with a `mode` pin or mapping (see
+/// [`with_device_modes`](Self::with_device_modes)) has its shots matched
+/// against its own effective mode rather than the global one.
+#[derive(Debug, Clone)]
+pub struct ShotRouter {
+    routing: RoutingSection,
+    mode: ShotDetectionMode,
+    club: Option<Club>,
+    monitors: HashMap<String, MonitorState>,
+    devices: HashMap<String, DeviceModeSection>,
+}
+
+impl ShotRouter {
+    /// Router for `routing`, starting in full-swing mode with no club.
+    pub fn new(routing: RoutingSection) -> Self {
+        Self {
+            routing,
+            mode: ShotDetectionMode::Full,
+            club: None,
+            monitors: HashMap::new(),
+            devices: HashMap::new(),
+        }
+    }
+
+    /// Per-monitor mode settings, keyed by global ID (see
+    /// [`FlighthookConfig::device_modes`](crate::FlighthookConfig::device_modes)).
+    pub fn with_device_modes(mut self, devices: HashMap<String, DeviceModeSection>) -> Self {
+        self.devices = devices;
+        self
+    }
+
+    pub fn rules(&self) -> &[RoutingRule] {
+        &self.routing.rules
+    }
+
+    pub fn mode(&self) -> ShotDetectionMode {
+        self.mode
+    }
+
+    /// The mode `actor`'s shots are taken in: the global mode through the
+    /// monitor's pin or mapping.
+    pub fn mode_of(&self, actor: &str) -> ShotDetectionMode {
+        self.devices
+            .get(actor)
+            .map_or(self.mode, |d| d.effective(self.mode))
+    }
+
+    /// Update mode, club and monitor state from a bus message. `now_ms`
+    /// stands in for a missing `timestamp_ms`.
+    pub fn observe(&mut self, msg: &FlighthookMessage, now_ms: u64) {
+        match &msg.event {
+            FlighthookEvent::SetDetectionMode {
+                mode: Some(mode), ..
+            } => self.mode = *mode,
+            FlighthookEvent::ClubInfo { club_info } => self.club = Some(club_info.club),
+            FlighthookEvent::ActorStatus { status, .. } => {
+                let down = matches!(
+                    status,
+                    ActorStatus::Starting | ActorStatus::Reconnecting | ActorStatus::Disconnected
+                );
+                self.monitors.entry(msg.actor.clone()).or_default().down = down;
+            }
+            FlighthookEvent::DeviceTelemetry {
+                telemetry: Some(telemetry),
+                ..
+            } => {
+                let Some(ready) = telemetry.get("ready").map(|v| v == "true") else {
+                    return;
+                };
+                let state = self.monitors.entry(msg.actor.clone()).or_default();
+                if ready {
+                    state.not_ready_since = None;
+                } else if state.ready != Some(false) {
+                    state.not_ready_since = Some(msg.timestamp_ms.unwrap_or(now_ms));
+                }
+                state.ready = Some(ready);
+            }
+            _ => {}
+        }
+    }
+
+    /// Index of the first rule that lets a finished shot from `actor`
+    /// through, or `None` if it should be dropped.
+    pub fn route(&self, actor: &str, shot: &ShotData, now_ms: u64) -> Option<usize> {
+        let mode = self.mode_of(actor);
+        self.routing.rules.iter().position(|rule| {
+            self.applies(rule, mode)
+                && rule.requires.iter().all(|part| has_part(shot, *part))
+                && self.selected(rule, now_ms).is_none_or(|id| id == actor)
+        })
+    }
+
+    /// Whether the monitor the current mode and club route to is ready, for
+    /// integrations that report readiness (GSPro's heartbeat). A rule
+    /// without monitors counts as ready when any monitor is.
+    pub fn ready(&self, now_ms: u64) -> bool {
+        let Some(rule) = self
+            .routing
+            .rules
+            .iter()
+            .find(|r| self.applies(r, self.mode))
+        else {
+            return false;
+        };
+        match self.selected(rule, now_ms) {
+            Some(id) => self
+                .monitors
+                .get(id)
+                .is_some_and(|s| !s.down && s.ready == Some(true)),
+            None => self
+                .monitors
+                .values()
+                .any(|s| !s.down && s.ready == Some(true)),
+        }
+    }
+
+    /// The monitor a rule takes shots from right now: the first available
+    /// one, or the primary when none is. `None` for a rule without monitors
+    /// (any monitor).
+    pub fn selected<'a>(&self, rule: &'a RoutingRule, now_ms: u64) -> Option<&'a str> {
+        let first = rule.monitors.first()?;
+        let available = rule.monitors.iter().find(|id| self.available(id, now_ms));
+        Some(available.unwrap_or(first))
+    }
+
+    /// Whether the rule's mode and club conditions hold in `mode`.
+    fn applies(&self, rule: &RoutingRule, mode: ShotDetectionMode) -> bool {
+        (rule.modes.is_empty() || rule.modes.contains(&mode))
+            && (rule.clubs.is_empty() || self.club.is_some_and(|c| rule.clubs.contains(&c)))
+    }
+
+    /// Connected, and not stuck at `ready = false` past the grace period.
+    /// A monitor the router has not heard from is not available.
+    fn available(&self, id: &str, now_ms: u64) -> bool {
+        let Some(state) = self.monitors.get(id) else {
+            return false;
+        };
+        let grace = self.routing.not_ready_grace_ms();
+        !state.down
+            && state
+                .not_ready_since
+                .is_none_or(|since| now_ms.saturating_sub(since) < grace)
+    }
+}
+
+fn has_part(shot: &ShotData, part: ShotPart) -> bool {
+    match part {
+        ShotPart::Ball => shot.ball.is_some(),
+        ShotPart::Club => shot.club.is_some(),
+        ShotPart::Impact => shot.impact.is_some(),
+    }
+}
+
+impl GsProSection {
+    /// The rules this bridge routes by: the `[routing.<name>]` set named by
+    /// `routing`, or else one rule per detection mode built from the
+    /// per-mode monitors (an unset monitor accepts any).
+    pub fn effective_routing(&self, sets: &HashMap<String, RoutingSection>) -> RoutingSection {
+        if let Some(set) = self.routing.as_ref().and_then(|name| sets.get(name)) {
+            return set.clone();
+        }
+        let rules = [
+            (ShotDetectionMode::Full, &self.full_monitor),
+            (ShotDetectionMode::Chipping, &self.chipping_monitor),
+            (ShotDetectionMode::Putting, &self.putting_monitor),
+        ]
+        .into_iter()
+        .map(|(mode, monitor)| RoutingRule {
+            modes: vec![mode],
+            monitors: monitor.iter().filter(|m| !m.is_empty()).cloned().collect(),
+            ..RoutingRule::default()
+        })
+        .collect();
+        RoutingSection {
+            rules,
+            not_ready_grace_ms: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClubData, ClubInfo};
+
+    fn from(actor: &str, at: u64, event: FlighthookEvent) -> FlighthookMessage {
+        let mut msg = FlighthookMessage::new(event).actor(actor);
+        msg.timestamp_ms = Some(at);
+        msg
+    }
+
+    fn status(actor: &str, status: ActorStatus, at: u64) -> FlighthookMessage {
+        let telemetry = HashMap::new();
+        from(
+            actor,
+            at,
+            FlighthookEvent::ActorStatus { status, telemetry },
+        )
+    }
+
+    fn ready(actor: &str, ready: bool, at: u64) -> FlighthookMessage {
+        let event = FlighthookEvent::DeviceTelemetry {
+            manufacturer: None,
+            model: None,
+            firmware: None,
+            telemetry: Some(HashMap::from([("ready".into(), ready.to_string())])),
+        };
+        from(actor, at, event)
+    }
+
+    fn shot() -> ShotData {
+        ShotData {
+            actor: String::new(),
+            shot_number: 1,
+            ball: None,
+            club: None,
+            impact: None,
+            estimated: false,
+        }
+    }
+
+    fn failover() -> ShotRouter {
+        let mut router = ShotRouter::new(RoutingSection {
+            rules: vec![RoutingRule {
+                monitors: vec!["mevo.0".into(), "r10.0".into()],
+                ..RoutingRule::default()
+            }],
+            not_ready_grace_ms: Some(10_000),
+        });
+        for (monitor, at) in [("mevo.0", 0), ("r10.0", 0)] {
+            router.observe(&status(monitor, ActorStatus::Connected, at), at);
+            router.observe(&ready(monitor, true, at), at);
+        }
+        router
+    }
+
+    #[test]
+    fn fallback_takes_over_while_the_primary_is_down() {
+        let mut router = failover();
+        assert_eq!(router.route("mevo.0", &shot(), 1_000), Some(0));
+        assert_eq!(router.route("r10.0", &shot(), 1_000), None);
+
+        router.observe(&status("mevo.0", ActorStatus::Reconnecting, 2_000), 2_000);
+        assert_eq!(router.route("mevo.0", &shot(), 2_500), None);
+        assert_eq!(router.route("r10.0", &shot(), 2_500), Some(0));
+        assert!(router.ready(2_500));
+
+        router.observe(&status("mevo.0", ActorStatus::Connected, 3_000), 3_000);
+        assert_eq!(router.route("mevo.0", &shot(), 3_500), Some(0));
+        assert_eq!(router.route("r10.0", &shot(), 3_500), None);
+    }
+
+    #[test]
+    fn primary_keeps_its_shots_while_briefly_not_ready() {
+        let mut router = failover();
+        // Disarmed around a shot: still the primary within the grace period.
+        router.observe(&ready("mevo.0", false, 5_000), 5_000);
+        assert_eq!(router.route("mevo.0", &shot(), 9_000), Some(0));
+        assert!(!router.ready(9_000));
+
+        // Not ready for longer than the grace period: the R10 takes over.
+        router.observe(&ready("mevo.0", false, 12_000), 12_000);
+        assert_eq!(router.route("mevo.0", &shot(), 15_000), None);
+        assert_eq!(router.route("r10.0", &shot(), 15_000), Some(0));
+    }
+
+    #[test]
+    fn rules_match_mode_club_and_data_in_order() {
+        let mut router = ShotRouter::new(RoutingSection {
+            rules: vec![
+                RoutingRule {
+                    modes: vec![ShotDetectionMode::Putting],
+                    monitors: vec!["square.0".into()],
+                    ..RoutingRule::default()
+                },
+                RoutingRule {
+                    clubs: vec![Club::Driver],
+                    monitors: vec!["mevo.0".into()],
+                    requires: vec![ShotPart::Club],
+                    ..RoutingRule::default()
+                },
+                RoutingRule {
+                    modes: vec![ShotDetectionMode::Full],
+                    monitors: vec!["r10.0".into()],
+                    ..RoutingRule::default()
+                },
+            ],
+            not_ready_grace_ms: None,
+        });
+        let with_club = ShotData {
+            club: Some(ClubData::default()),
+            ..shot()
+        };
+
+        // No club selected yet: only the full-swing rule applies.
+        assert_eq!(router.route("mevo.0", &with_club, 0), None);
+        assert_eq!(router.route("r10.0", &shot(), 0), Some(2));
+
+        let driver = FlighthookMessage::new(FlighthookEvent::ClubInfo {
+            club_info: ClubInfo { club: Club::Driver },
+        });
+        router.observe(&driver, 0);
+        assert_eq!(router.route("mevo.0", &with_club, 0), Some(1));
+        assert_eq!(router.route("mevo.0", &shot(), 0), None); // no club data
+
+        let putting = FlighthookMessage::new(FlighthookEvent::SetDetectionMode {
+            mode: Some(ShotDetectionMode::Putting),
+            handed: None,
+        });
+        router.observe(&putting, 0);
+        assert_eq!(router.route("square.0", &shot(), 0), Some(0));
+        assert_eq!(router.route("r10.0", &shot(), 0), None);
+    }
+
+    #[test]
+    fn pinned_monitor_routes_in_its_own_mode() {
+        let section = GsProSection {
+            full_monitor: Some("mevo.0".into()),
+            putting_monitor: Some("square.0".into()),
+            ..GsProSection::default()
+        };
+        let pinned = DeviceModeSection {
+            pinned: Some(ShotDetectionMode::Putting),
+            ..DeviceModeSection::default()
+        };
+        let router = ShotRouter::new(section.effective_routing(&HashMap::new()))
+            .with_device_modes(HashMap::from([("square.0".to_string(), pinned)]));
+
+        // Global mode is full swing, but the Square's putts still go through
+        // the putting rule, and the Mevo keeps the full-swing one.
+        assert_eq!(router.mode_of("square.0"), ShotDetectionMode::Putting);
+        assert_eq!(router.mode_of("mevo.0"), ShotDetectionMode::Full);
+        assert_eq!(router.route("square.0", &shot(), 0), Some(2));
+        assert_eq!(router.route("mevo.0", &shot(), 0), Some(0));
+    }
+
+    #[test]
+    fn gspro_per_mode_monitors_become_rules() {
+        let section = GsProSection {
+            full_monitor: Some("mevo.0".into()),
+            ..GsProSection::default()
+        };
+        let mut router = ShotRouter::new(section.effective_routing(&HashMap::new()));
+        assert_eq!(router.route("mevo.0", &shot(), 0), Some(0));
+        assert_eq!(router.route("r10.0", &shot(), 0), None);
+
+        let chipping = FlighthookMessage::new(FlighthookEvent::SetDetectionMode {
+            mode: Some(ShotDetectionMode::Chipping),
+            handed: None,
+        });
+        router.observe(&chipping, 0);
+        assert_eq!(router.route("r10.0", &shot(), 0), Some(1));
+
+        let sets = HashMap::from([("bay".to_string(), RoutingSection::default())]);
+        let named = GsProSection {
+            routing: Some("bay".into()),
+            ..section
+        };
+        assert_eq!(named.effective_routing(&sets), RoutingSection::default());
+    }
+}
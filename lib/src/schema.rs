@@ -0,0 +1,301 @@
+//! JSON Schema generation for the wire types.
+//!
+//! Requires the `schema` feature flag. Types defined in this crate derive
+//! [`JsonSchema`]; the shot data and unit types come from `flightrelay`, so
+//! they are described by the mirror types below (same serde shape, matched in
+//! the tests) and referenced with `#[schemars(with = ...)]`.
+
+use std::borrow::Cow;
+
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+
+use crate::{FlighthookMessage, RawPayload};
+
+/// JSON Schema (draft 2020-12) of a [`FlighthookMessage`] as sent over the
+/// `/frp` WebSocket.
+pub fn message_schema() -> serde_json::Value {
+    let schema = schemars::generate::SchemaSettings::draft2020_12()
+        .into_generator()
+        .into_root_schema_for::<FlighthookMessage>();
+    schema.to_value()
+}
+
+/// Binary payloads go out as lowercase hex, text payloads as-is; both are
+/// plain strings on the wire.
+impl JsonSchema for RawPayload {
+    fn schema_name() -> Cow<'static, str> {
+        "RawPayload".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "Raw device payload: lowercase hex for binary protocols, verbatim for text protocols.",
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// flightrelay mirrors
+//
+// Schema-only stand-ins for the foreign types; never constructed.
+// ---------------------------------------------------------------------------
+
+/// Unit-tagged distance string.
+pub(crate) struct Distance;
+
+impl JsonSchema for Distance {
+    fn schema_name() -> Cow<'static, str> {
+        "Distance".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "Number immediately followed by a unit: ft, in, m, cm, yd or mm (e.g. \"180.5m\").",
+            "pattern": "^-?[0-9]+(\\.[0-9]+)?(ft|in|m|cm|yd|mm)$",
+        })
+    }
+}
+
+/// Unit-tagged velocity string.
+pub(crate) struct Velocity;
+
+impl JsonSchema for Velocity {
+    fn schema_name() -> Cow<'static, str> {
+        "Velocity".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "Number immediately followed by a unit: mph, mps, kph or fps (e.g. \"67.2mps\").",
+            "pattern": "^-?[0-9]+(\\.[0-9]+)?(mph|mps|kph|fps)$",
+        })
+    }
+}
+
+/// Correlates shot lifecycle events.
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+pub(crate) struct ShotKey {
+    /// Unique shot ID (UUID v4 string).
+    pub shot_id: String,
+    /// Monotonic counter from the device, for human display.
+    pub shot_number: u32,
+}
+
+/// Ball flight measurement data. All fields optional.
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+pub(crate) struct BallFlight {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub launch_speed: Option<Velocity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub launch_azimuth: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub launch_elevation: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub carry_distance: Option<Distance>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_distance: Option<Distance>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roll_distance: Option<Distance>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_height: Option<Distance>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flight_time: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backspin_rpm: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sidespin_rpm: Option<i32>,
+}
+
+/// Club head measurement data. All fields optional.
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+pub(crate) struct ClubData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub club_speed: Option<Velocity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub club_speed_post: Option<Velocity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attack_angle: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub face_angle: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dynamic_loft: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smash_factor: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub swing_plane_horizontal: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub swing_plane_vertical: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub club_offset: Option<Distance>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub club_height: Option<Distance>,
+}
+
+/// Face impact location — where on the club face the ball was struck.
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+pub(crate) struct FaceImpact {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lateral: Option<Distance>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vertical: Option<Distance>,
+}
+
+/// Alert severity level.
+#[derive(JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[allow(dead_code)]
+pub(crate) enum Severity {
+    Warn,
+    Error,
+    Critical,
+}
+
+/// Player handedness.
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+pub(crate) enum Handedness {
+    /// Right-handed.
+    #[serde(rename = "rh")]
+    Right,
+    /// Left-handed.
+    #[serde(rename = "lh")]
+    Left,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Property names of the generated schema for `T`.
+    fn schema_properties<T: JsonSchema>() -> Vec<String> {
+        let schema = schemars::schema_for!(T).to_value();
+        let mut keys: Vec<String> = schema["properties"]
+            .as_object()
+            .expect("object schema")
+            .keys()
+            .cloned()
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    /// Keys `value` serializes to, with every field populated.
+    fn serialized_keys(value: impl serde::Serialize) -> Vec<String> {
+        let mut keys: Vec<String> = serde_json::to_value(value)
+            .unwrap()
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn mirrors_match_flightrelay_fields() {
+        let d = Some(crate::Distance::Feet(1.0));
+        let v = Some(crate::Velocity::MilesPerHour(1.0));
+        let ball = crate::BallFlight {
+            launch_speed: v,
+            launch_azimuth: Some(0.0),
+            launch_elevation: Some(0.0),
+            carry_distance: d,
+            total_distance: d,
+            roll_distance: d,
+            max_height: d,
+            flight_time: Some(0.0),
+            backspin_rpm: Some(0),
+            sidespin_rpm: Some(0),
+        };
+        let club = crate::ClubData {
+            club_speed: v,
+            club_speed_post: v,
+            path: Some(0.0),
+            attack_angle: Some(0.0),
+            face_angle: Some(0.0),
+            dynamic_loft: Some(0.0),
+            smash_factor: Some(0.0),
+            swing_plane_horizontal: Some(0.0),
+            swing_plane_vertical: Some(0.0),
+            club_offset: d,
+            club_height: d,
+        };
+        let impact = crate::FaceImpact {
+            lateral: d,
+            vertical: d,
+        };
+        let key = crate::ShotKey::new("id", 1);
+
+        assert_eq!(serialized_keys(&ball), schema_properties::<BallFlight>());
+        assert_eq!(serialized_keys(&club), schema_properties::<ClubData>());
+        assert_eq!(serialized_keys(&impact), schema_properties::<FaceImpact>());
+        assert_eq!(serialized_keys(&key), schema_properties::<ShotKey>());
+    }
+
+    #[test]
+    fn mirror_enums_match_flightrelay_values() {
+        let severity = schemars::schema_for!(Severity).to_value();
+        for s in [
+            crate::Severity::Warn,
+            crate::Severity::Error,
+            crate::Severity::Critical,
+        ] {
+            let wire = serde_json::to_value(s).unwrap();
+            assert!(
+                severity["enum"].as_array().unwrap().contains(&wire),
+                "{wire}"
+            );
+        }
+        let handed = serde_json::to_string(&schemars::schema_for!(Handedness)).unwrap();
+        for h in [crate::Handedness::Right, crate::Handedness::Left] {
+            assert!(handed.contains(&serde_json::to_string(&h).unwrap()));
+        }
+    }
+
+    #[test]
+    fn unit_patterns_accept_wire_strings() {
+        let distance = Distance::json_schema(&mut SchemaGenerator::default()).to_value();
+        let velocity = Velocity::json_schema(&mut SchemaGenerator::default()).to_value();
+        // No regex engine here; check the unit alternations cover every suffix.
+        for unit in ["ft", "in", "m", "cm", "yd", "mm"] {
+            assert!(distance["pattern"].as_str().unwrap().contains(unit));
+        }
+        for unit in ["mph", "mps", "kph", "fps"] {
+            assert!(velocity["pattern"].as_str().unwrap().contains(unit));
+        }
+    }
+
+    #[test]
+    fn message_schema_covers_every_event_kind() {
+        let schema = serde_json::to_string(&message_schema()).unwrap();
+        for kind in [
+            "shot_trigger",
+            "ball_flight",
+            "club_path",
+            "face_impact",
+            "shot_finished",
+            "device_telemetry",
+            "alert",
+            "set_detection_mode",
+            "player_info",
+            "club_info",
+            "config_command",
+            "config_outcome",
+            "shot_delivered",
+            "shot_suppressed",
+            "actor_status",
+        ] {
+            assert!(schema.contains(&format!("\"{kind}\"")), "{kind}");
+        }
+    }
+}
@@ -0,0 +1,759 @@
+//! Config validation — structured, per-section errors and warnings.
+//!
+//! [`FlighthookConfig::validate`] checks what serde cannot: addresses that
+//! must parse, routing targets that must exist, listeners that must not
+//! collide. The app runs it before saving and when loading the config file;
+//! the UI runs it on the settings form to show problems inline.
+
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Club, FlighthookConfig};
+
+/// Section types that produce shots, i.e. valid GSPro routing targets.
+/// Plugins, scripts and processes count: a filter re-emits the shots it
+/// passes, and a process may be a device driver. WebSocket clients are
+/// targets too (see [`is_ws_client`]).
+const MONITOR_TYPES: [&str; 8] = [
+    "mevo",
+    "r10",
+    "square",
+    "openconnect_server",
+    "mock_monitor",
+    "plugin",
+    "script",
+    "process",
+];
+
+/// Whether `id` is a WebSocket client's actor ID (`ws.<name>`). Those are
+/// not config sections; a client takes one when it connects, so a route to
+/// one is accepted without a section to check against.
+fn is_ws_client(id: &str) -> bool {
+    id.strip_prefix("ws.").is_some_and(|name| !name.is_empty())
+}
+
+/// Longest `[dedup] window_ms`: a shot is held that long before it is
+/// forwarded.
+const MAX_DEDUP_WINDOW_MS: u64 = 10_000;
+
+/// One validation finding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ConfigIssue {
+    /// Stable machine-readable kind, e.g. `"invalid_address"`,
+    /// `"unknown_route"`, `"bind_conflict"`.
+    pub code: String,
+    /// Global ID of the section (`"gspro.0"`); `None` for top-level settings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+    /// Field the issue is about (`"address"`), if it is about one field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    pub message: String,
+}
+
+/// Result of [`FlighthookConfig::validate`]. A config with errors is
+/// rejected by `POST /api/settings`; warnings are reported but accepted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ConfigValidation {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<ConfigIssue>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ConfigIssue>,
+}
+
+impl ConfigValidation {
+    /// True when there are no errors.
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Errors and warnings for one section (`None` = top-level), each paired
+    /// with `true` for an error.
+    pub fn for_section<'a>(
+        &'a self,
+        section: Option<&'a str>,
+    ) -> impl Iterator<Item = (bool, &'a ConfigIssue)> {
+        let errors = self.errors.iter().map(|i| (true, i));
+        let warnings = self.warnings.iter().map(|i| (false, i));
+        errors
+            .chain(warnings)
+            .filter(move |(_, i)| i.section.as_deref() == section)
+    }
+
+    fn error(&mut self, code: &str, section: Option<&str>, field: Option<&str>, message: String) {
+        self.errors.push(issue(code, section, field, message));
+    }
+
+    fn warning(&mut self, code: &str, section: Option<&str>, field: Option<&str>, message: String) {
+        self.warnings.push(issue(code, section, field, message));
+    }
+}
+
+fn issue(code: &str, section: Option<&str>, field: Option<&str>, message: String) -> ConfigIssue {
+    ConfigIssue {
+        code: code.to_owned(),
+        section: section.map(str::to_owned),
+        field: field.map(str::to_owned),
+        message,
+    }
+}
+
+/// Sections in ID order, so results do not depend on `HashMap` order.
+fn sorted<T>(map: &std::collections::HashMap<String, T>) -> BTreeMap<&str, &T> {
+    map.iter().map(|(k, v)| (k.as_str(), v)).collect()
+}
+
+/// Parse `value` as a socket address, recording an error on failure.
+fn check_addr(
+    out: &mut ConfigValidation,
+    id: &str,
+    field: &str,
+    value: &str,
+) -> Option<SocketAddr> {
+    match value.parse::<SocketAddr>() {
+        Ok(addr) => Some(addr),
+        Err(_) => {
+            out.error(
+                "invalid_address",
+                Some(id),
+                Some(field),
+                format!("'{value}' is not an ip:port address"),
+            );
+            None
+        }
+    }
+}
+
+/// Whether two listeners would fight over the same port.
+fn binds_overlap(a: SocketAddr, b: SocketAddr) -> bool {
+    a.port() == b.port() && (a.ip() == b.ip() || a.ip().is_unspecified() || b.ip().is_unspecified())
+}
+
+/// Whether a string looks like a BLE address (`AA:BB:CC:DD:EE:FF`).
+fn is_ble_address(s: &str) -> bool {
+    s.len() == 17
+        && s.split(':').count() == 6
+        && s.split(':')
+            .all(|o| o.len() == 2 && o.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+impl FlighthookConfig {
+    /// Check the config for problems the actors would otherwise only log
+    /// (or silently work around) at startup.
+    pub fn validate(&self) -> ConfigValidation {
+        let mut out = ConfigValidation::default();
+        // Enabled listeners, for the duplicate-bind check.
+        let mut listeners: Vec<(String, SocketAddr)> = Vec::new();
+
+        for (index, ws) in sorted(&self.webserver) {
+            let id = format!("webserver.{index}");
+            if let Some(addr) = check_addr(&mut out, &id, "bind", &ws.bind)
+                && !ws.disabled
+            {
+                listeners.push((id.clone(), addr));
+            }
+            match (&ws.tls_cert, &ws.tls_key) {
+                (Some(_), None) => out.error(
+                    "tls_incomplete",
+                    Some(&id),
+                    Some("tls_key"),
+                    "tls_cert is set without tls_key".into(),
+                ),
+                (None, Some(_)) => out.error(
+                    "tls_incomplete",
+                    Some(&id),
+                    Some("tls_cert"),
+                    "tls_key is set without tls_cert".into(),
+                ),
+                _ => {}
+            }
+            for (i, token) in ws.tokens.iter().enumerate() {
+                if token.token.is_empty() {
+                    out.error(
+                        "empty_token",
+                        Some(&id),
+                        Some("tokens"),
+                        format!("token {i} is empty"),
+                    );
+                } else if ws.tokens[..i].iter().any(|t| t.token == token.token) {
+                    out.warning(
+                        "duplicate_token",
+                        Some(&id),
+                        Some("tokens"),
+                        format!("token {i} repeats an earlier token; the widest scope applies"),
+                    );
+                }
+            }
+        }
+
+        for (index, section) in sorted(&self.mevo) {
+            let id = format!("mevo.{index}");
+            if let Some(addr) = &section.address {
+                check_addr(&mut out, &id, "address", addr);
+            }
+            if let Some(pct) = section.track_pct
+                && !(0.0..=100.0).contains(&pct)
+            {
+                out.error(
+                    "out_of_range",
+                    Some(&id),
+                    Some("track_pct"),
+                    format!("{pct} is outside 0-100"),
+                );
+            }
+        }
+
+        for (index, section) in sorted(&self.square) {
+            let id = format!("square.{index}");
+            if let Some(addr) = section.address.as_deref().map(str::trim)
+                && !addr.is_empty()
+                && !is_ble_address(addr)
+            {
+                out.error(
+                    "invalid_address",
+                    Some(&id),
+                    Some("address"),
+                    format!("'{addr}' is not a BLE address (AA:BB:CC:DD:EE:FF)"),
+                );
+            }
+            if let Some(club) = &section.club
+                && Club::from_code(club).is_none()
+            {
+                out.error(
+                    "unknown_club",
+                    Some(&id),
+                    Some("club"),
+                    format!("unknown club code '{club}' (expected e.g. DR, 7I, PW, PT)"),
+                );
+            }
+        }
+
+        for (index, section) in sorted(&self.openconnect_server) {
+            let id = format!("openconnect_server.{index}");
+            let bind = section.bind.as_deref().unwrap_or("0.0.0.0:921");
+            if let Some(addr) = check_addr(&mut out, &id, "bind", bind)
+                && !section.disabled
+            {
+                listeners.push((id, addr));
+            }
+        }
+
+        for (index, section) in sorted(&self.plugin) {
+            if section.path.trim().is_empty() {
+                out.error(
+                    "missing_path",
+                    Some(&format!("plugin.{index}")),
+                    Some("path"),
+                    "no module path".into(),
+                );
+            }
+        }
+
+        for (index, section) in sorted(&self.script) {
+            if section.path.trim().is_empty() {
+                out.error(
+                    "missing_path",
+                    Some(&format!("script.{index}")),
+                    Some("path"),
+                    "no script path".into(),
+                );
+            }
+        }
+
+        for (index, section) in sorted(&self.process) {
+            if section.command.trim().is_empty() {
+                out.error(
+                    "missing_command",
+                    Some(&format!("process.{index}")),
+                    Some("command"),
+                    "no command to run".into(),
+                );
+            }
+        }
+
+        for (index, section) in sorted(&self.gspro) {
+            let id = format!("gspro.{index}");
+            let address = section.address.as_deref().unwrap_or("127.0.0.1:921");
+            if let Some(addr) = check_addr(&mut out, &id, "address", address)
+                && !section.disabled
+                && addr.ip().is_loopback()
+                && let Some((server, _)) = listeners
+                    .iter()
+                    .filter(|(l, _)| l.starts_with("openconnect_server."))
+                    .find(|(_, bind)| bind.port() == addr.port())
+            {
+                out.warning(
+                    "routes_to_self",
+                    Some(&id),
+                    Some("address"),
+                    format!("'{address}' is this app's own OpenConnect listener ({server})"),
+                );
+            }
+            for (field, target) in [
+                ("full_monitor", &section.full_monitor),
+                ("chipping_monitor", &section.chipping_monitor),
+                ("putting_monitor", &section.putting_monitor),
+            ] {
+                let Some(target) = target.as_deref().filter(|t| !t.is_empty()) else {
+                    continue;
+                };
+                self.check_route(&mut out, &id, field, target, section.disabled);
+            }
+            if let Some(name) = &section.routing
+                && !self.routing.contains_key(name)
+            {
+                out.error(
+                    "unknown_routing",
+                    Some(&id),
+                    Some("routing"),
+                    format!("no [routing.{name}] rule set"),
+                );
+            }
+        }
+
+        for (name, set) in sorted(&self.routing) {
+            let id = format!("routing.{name}");
+            if set.rules.is_empty() {
+                out.warning(
+                    "no_rules",
+                    Some(&id),
+                    Some("rules"),
+                    "no rules; every shot is dropped".into(),
+                );
+            }
+            for rule in &set.rules {
+                for target in &rule.monitors {
+                    self.check_route(&mut out, &id, "rules", target, false);
+                }
+            }
+        }
+
+        for (i, (id, addr)) in listeners.iter().enumerate() {
+            for (other, other_addr) in listeners.iter().take(i) {
+                if binds_overlap(*addr, *other_addr) {
+                    out.error(
+                        "bind_conflict",
+                        Some(id),
+                        Some("bind"),
+                        format!("{addr} conflicts with {other} ({other_addr})"),
+                    );
+                    out.error(
+                        "bind_conflict",
+                        Some(other),
+                        Some("bind"),
+                        format!("{other_addr} conflicts with {id} ({addr})"),
+                    );
+                }
+            }
+        }
+
+        let both: Vec<String> = self
+            .chipping_clubs
+            .iter()
+            .filter(|c| self.putting_clubs.contains(c))
+            .map(ToString::to_string)
+            .collect();
+        if !both.is_empty() {
+            out.warning(
+                "club_overlap",
+                None,
+                Some("chipping_clubs"),
+                format!(
+                    "{} in both chipping and putting clubs; putting wins",
+                    both.join(", ")
+                ),
+            );
+        }
+        if let Some(ms) = self.dedup.window_ms
+            && ms > MAX_DEDUP_WINDOW_MS
+        {
+            out.error(
+                "out_of_range",
+                None,
+                Some("dedup"),
+                format!(
+                    "window_ms {ms} is over {MAX_DEDUP_WINDOW_MS}; every shot is held that long"
+                ),
+            );
+        }
+        for id in self.dedup.priority.iter().filter(|id| !is_ws_client(id)) {
+            let is_monitor = id
+                .split_once('.')
+                .is_some_and(|(prefix, _)| MONITOR_TYPES.contains(&prefix));
+            if !is_monitor || self.section_disabled(id).is_none() {
+                out.warning(
+                    "unknown_priority",
+                    None,
+                    Some("dedup"),
+                    format!("priority '{id}' is not a configured launch monitor"),
+                );
+            }
+        }
+        // The system actor always counts toward health.
+        for id in self.health.critical.iter().filter(|id| *id != "system") {
+            let problem = match self.section_disabled(id) {
+                None => "is not configured",
+                Some(true) => "is disabled",
+                Some(false) => continue,
+            };
+            out.warning(
+                "critical_unavailable",
+                None,
+                Some("health"),
+                format!("critical actor '{id}' {problem}; health will report not ready"),
+            );
+        }
+        if self.webserver.values().all(|ws| ws.disabled) {
+            out.warning(
+                "no_webserver",
+                None,
+                Some("webserver"),
+                "no enabled webserver; the REST API and browser UI are unreachable".into(),
+            );
+        }
+
+        out
+    }
+
+    /// Validate this config as a replacement for `current`.
+    ///
+    /// Only errors the change introduces count: a config that is already
+    /// broken (say, hand-edited) can still be saved piecemeal while it is
+    /// being fixed. Warnings are those of this config.
+    pub fn validate_from(&self, current: &FlighthookConfig) -> ConfigValidation {
+        let existing = current.validate().errors;
+        let mut result = self.validate();
+        result.errors.retain(|e| !existing.contains(e));
+        result
+    }
+
+    /// Check that `target`, a shot source named by section `id`, is a
+    /// configured launch monitor, and warn if it is disabled while the
+    /// section is not.
+    fn check_route(
+        &self,
+        out: &mut ConfigValidation,
+        id: &str,
+        field: &str,
+        target: &str,
+        section_disabled: bool,
+    ) {
+        if is_ws_client(target) {
+            return;
+        }
+        let is_monitor = target
+            .split_once('.')
+            .is_some_and(|(prefix, _)| MONITOR_TYPES.contains(&prefix));
+        match self.section_disabled(target) {
+            Some(disabled) if is_monitor => {
+                if disabled && !section_disabled {
+                    out.warning(
+                        "route_disabled",
+                        Some(id),
+                        Some(field),
+                        format!("'{target}' is disabled; no shots will be routed"),
+                    );
+                }
+            }
+            _ => out.error(
+                "unknown_route",
+                Some(id),
+                Some(field),
+                format!("'{target}' is not a configured launch monitor"),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        GsProSection, MevoSection, OpenConnectServerSection, SquareSection, WebserverSection,
+    };
+
+    fn fields(issues: &[ConfigIssue]) -> Vec<(Option<&str>, Option<&str>)> {
+        issues
+            .iter()
+            .map(|i| (i.section.as_deref(), i.field.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn default_config_is_clean() {
+        assert_eq!(
+            FlighthookConfig::default().validate(),
+            ConfigValidation::default()
+        );
+    }
+
+    #[test]
+    fn bad_addresses_and_club_codes_are_errors() {
+        let mut config = FlighthookConfig::default();
+        config.gspro.insert(
+            "0".into(),
+            GsProSection {
+                address: Some("gspro.local".into()),
+                ..GsProSection::default()
+            },
+        );
+        config.square.insert(
+            "0".into(),
+            SquareSection {
+                club: Some("driver".into()),
+                ..SquareSection::default()
+            },
+        );
+        let result = config.validate();
+        assert!(!result.is_valid());
+        assert_eq!(
+            fields(&result.errors),
+            [
+                (Some("square.0"), Some("club")),
+                (Some("gspro.0"), Some("address")),
+            ]
+        );
+    }
+
+    #[test]
+    fn routing_targets_must_be_configured_monitors() {
+        let mut config = FlighthookConfig::default();
+        config.mock_monitor.insert(
+            "0".into(),
+            crate::MockMonitorSection {
+                name: "Mock".into(),
+                disabled: true,
+                mode: Default::default(),
+            },
+        );
+        config.gspro.insert(
+            "0".into(),
+            GsProSection {
+                full_monitor: Some("mevo.0".into()),
+                chipping_monitor: Some("webserver.0".into()),
+                putting_monitor: Some("mock_monitor.0".into()),
+                ..GsProSection::default()
+            },
+        );
+        let result = config.validate();
+        assert_eq!(
+            fields(&result.errors),
+            [
+                (Some("gspro.0"), Some("full_monitor")),
+                (Some("gspro.0"), Some("chipping_monitor")),
+            ]
+        );
+        assert_eq!(
+            fields(&result.warnings),
+            [(Some("gspro.0"), Some("putting_monitor"))]
+        );
+    }
+
+    #[test]
+    fn plugins_and_scripts_need_a_path_and_are_routable() {
+        let mut config = FlighthookConfig::default();
+        config
+            .plugin
+            .insert("0".into(), crate::PluginSection::default());
+        config.gspro.insert(
+            "0".into(),
+            GsProSection {
+                full_monitor: Some("plugin.0".into()),
+                ..GsProSection::default()
+            },
+        );
+        let result = config.validate();
+        assert_eq!(fields(&result.errors), [(Some("plugin.0"), Some("path"))]);
+
+        config.plugin.get_mut("0").unwrap().path = "filters/driver.wasm".into();
+        assert!(config.validate().is_valid());
+
+        config
+            .script
+            .insert("0".into(), crate::ScriptSection::default());
+        assert_eq!(
+            fields(&config.validate().errors),
+            [(Some("script.0"), Some("path"))]
+        );
+    }
+
+    #[test]
+    fn routing_rule_sets_must_exist_and_name_monitors() {
+        let mut config = FlighthookConfig::default();
+        config.mevo.insert("0".into(), MevoSection::default());
+        config.gspro.insert(
+            "0".into(),
+            GsProSection {
+                routing: Some("bay".into()),
+                ..GsProSection::default()
+            },
+        );
+        assert_eq!(
+            fields(&config.validate().errors),
+            [(Some("gspro.0"), Some("routing"))]
+        );
+
+        config.routing.insert(
+            "bay".into(),
+            crate::RoutingSection {
+                rules: vec![crate::RoutingRule {
+                    monitors: vec!["mevo.0".into(), "r10.0".into()],
+                    ..Default::default()
+                }],
+                not_ready_grace_ms: None,
+            },
+        );
+        let result = config.validate();
+        assert_eq!(
+            fields(&result.errors),
+            [(Some("routing.bay"), Some("rules"))]
+        );
+        assert!(result.errors[0].message.contains("r10.0"));
+
+        config.routing.get_mut("bay").unwrap().rules.clear();
+        let result = config.validate();
+        assert!(result.is_valid());
+        assert_eq!(
+            fields(&result.warnings),
+            [(Some("routing.bay"), Some("rules"))]
+        );
+    }
+
+    #[test]
+    fn dedup_window_is_bounded_and_priorities_are_monitors() {
+        let mut config = FlighthookConfig::default();
+        config.mevo.insert("0".into(), MevoSection::default());
+        config.dedup = crate::DedupSection {
+            window_ms: Some(60_000),
+            priority: vec!["mevo.0".into(), "gspro.0".into()],
+            ..Default::default()
+        };
+        let result = config.validate();
+        assert_eq!(fields(&result.errors), [(None, Some("dedup"))]);
+        assert_eq!(result.errors[0].code, "out_of_range");
+        assert_eq!(fields(&result.warnings), [(None, Some("dedup"))]);
+        assert!(result.warnings[0].message.contains("gspro.0"));
+
+        config.dedup.window_ms = Some(1_500);
+        config.dedup.priority.pop();
+        assert_eq!(config.validate(), ConfigValidation::default());
+    }
+
+    #[test]
+    fn processes_need_a_command_and_are_routable() {
+        let mut config = FlighthookConfig::default();
+        config
+            .process
+            .insert("0".into(), crate::ProcessSection::default());
+        config.gspro.insert(
+            "0".into(),
+            GsProSection {
+                full_monitor: Some("process.0".into()),
+                ..GsProSection::default()
+            },
+        );
+        let result = config.validate();
+        assert_eq!(
+            fields(&result.errors),
+            [(Some("process.0"), Some("command"))]
+        );
+
+        config.process.get_mut("0").unwrap().command = "./drivers/uneekor.py".into();
+        assert!(config.validate().is_valid());
+    }
+
+    #[test]
+    fn websocket_clients_are_routable() {
+        let mut config = FlighthookConfig::default();
+        config.gspro.insert(
+            "0".into(),
+            GsProSection {
+                full_monitor: Some("ws.uneekor-bridge".into()),
+                putting_monitor: Some("ws.".into()),
+                ..GsProSection::default()
+            },
+        );
+        config.dedup.priority = vec!["ws.uneekor-bridge".into()];
+        let result = config.validate();
+        assert_eq!(
+            fields(&result.errors),
+            [(Some("gspro.0"), Some("putting_monitor"))]
+        );
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn duplicate_binds_are_errors_on_both_sections() {
+        let mut config = FlighthookConfig::default();
+        config.webserver.insert(
+            "1".into(),
+            WebserverSection {
+                bind: "127.0.0.1:921".into(),
+                ..WebserverSection::default()
+            },
+        );
+        config
+            .openconnect_server
+            .insert("0".into(), OpenConnectServerSection::default());
+        let result = config.validate();
+        assert_eq!(
+            fields(&result.errors),
+            [
+                (Some("openconnect_server.0"), Some("bind")),
+                (Some("webserver.1"), Some("bind")),
+            ]
+        );
+
+        // A disabled section does not listen.
+        config.webserver.get_mut("1").unwrap().disabled = true;
+        assert!(config.validate().is_valid());
+    }
+
+    #[test]
+    fn critical_actors_must_be_enabled_sections() {
+        let mut config = FlighthookConfig::default();
+        config.mock_monitor.insert(
+            "0".into(),
+            crate::MockMonitorSection {
+                name: "Mock".into(),
+                disabled: true,
+                mode: Default::default(),
+            },
+        );
+        config.health.critical = vec![
+            "system".into(),
+            "webserver.0".into(),
+            "mock_monitor.0".into(),
+            "gspro.0".into(),
+        ];
+        let result = config.validate();
+        assert!(result.is_valid());
+        let codes: Vec<&str> = result.warnings.iter().map(|w| w.code.as_str()).collect();
+        assert_eq!(codes, ["critical_unavailable", "critical_unavailable"]);
+    }
+
+    #[test]
+    fn for_section_filters_and_flags_errors() {
+        let mut config = FlighthookConfig::default();
+        config.putting_clubs.push(Club::LobWedge);
+        config.gspro.insert(
+            "0".into(),
+            GsProSection {
+                address: Some("nope".into()),
+                ..GsProSection::default()
+            },
+        );
+        let result = config.validate();
+        let top: Vec<bool> = result.for_section(None).map(|(e, _)| e).collect();
+        assert_eq!(top, [false]);
+        let gspro: Vec<bool> = result
+            .for_section(Some("gspro.0"))
+            .map(|(e, _)| e)
+            .collect();
+        assert_eq!(gspro, [true]);
+    }
+}
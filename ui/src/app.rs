@@ -9,7 +9,7 @@ use crate::types::{
     ActorStatus, ActorStatusResponse, FlighthookEvent, FlighthookMessage, GsProSection, LogEntry,
     MevoSection, OpenConnectServerSection, R10Section, ShotRow, SquareSection, UnitSystem,
 };
-use chrono::{SecondsFormat, Utc};
+use chrono::{DateTime, SecondsFormat, Utc};
 
 const API_DOCS_MD: &str = include_str!("../../docs/API.md");
 
@@ -154,7 +154,16 @@ impl FlighthookApp {
             }
         }
 
+        if let Some(rejected) = p.settings_rejected.take() {
+            // Nothing was saved; keep the edits and show the errors inline.
+            self.wizard_saving = false;
+            self.settings.saving = false;
+            self.settings.save_target = None;
+            self.settings.rejected = Some(rejected);
+        }
+
         if let Some(_resp) = p.settings_save.take() {
+            self.settings.rejected = None;
             // Dismiss wizard on successful save and reload settings
             if self.wizard_saving {
                 self.wizard_saving = false;
@@ -210,7 +219,7 @@ impl FlighthookApp {
                     }
                 }
                 WsPollEvent::Init { .. } => {}
-                WsPollEvent::Message(msg) => self.handle_bus_event(msg),
+                WsPollEvent::Message(msg) => self.handle_bus_event(*msg),
                 WsPollEvent::Error(_) => {
                     ws_disconnected = true;
                 }
@@ -243,8 +252,13 @@ impl FlighthookApp {
                 .as_ref()
                 .map(|r| r.to_string())
                 .unwrap_or_else(|| serde_json::to_string(&msg.event).unwrap_or_default());
+            // When the event happened at its source, if it says.
+            let timestamp = msg
+                .timestamp_ms
+                .and_then(|ms| DateTime::from_timestamp_millis(i64::try_from(ms).ok()?))
+                .unwrap_or_else(Utc::now);
             self.log_entries.push(LogEntry {
-                timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+                timestamp: timestamp.to_rfc3339_opts(SecondsFormat::Millis, true),
                 actor_name,
                 actor_id: msg.actor.clone(),
                 message_type: message_type(&msg.event).to_string(),
@@ -291,7 +305,7 @@ impl FlighthookApp {
                     club: None,
                 });
             }
-            FlighthookEvent::BallFlight { key, ball } => {
+            FlighthookEvent::BallFlight { key, ball, .. } => {
                 if let Some(row) = self
                     .shots
                     .iter_mut()
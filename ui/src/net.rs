@@ -7,7 +7,8 @@
 use std::sync::{Arc, Mutex};
 
 use crate::types::{
-    self, FlighthookConfig, FlighthookMessage, PostSettingsResponse, ShotData, StatusResponse,
+    self, ConfigValidation, FlighthookConfig, FlighthookMessage, PostSettingsResponse, ShotData,
+    StatusResponse,
 };
 
 // ---------------------------------------------------------------------------
@@ -21,6 +22,8 @@ pub struct Pending {
     pub shots: Option<Vec<ShotData>>,
     pub settings: Option<FlighthookConfig>,
     pub settings_save: Option<PostSettingsResponse>,
+    /// Errors of a save the server rejected (`422`).
+    pub settings_rejected: Option<ConfigValidation>,
 }
 
 pub type PendingHandle = Arc<Mutex<Pending>>;
@@ -84,6 +87,46 @@ fn ws_url() -> String {
     format!("{ws_base}/frp")
 }
 
+// ---------------------------------------------------------------------------
+// API token — sent on every REST request and in the WS `start` message
+// ---------------------------------------------------------------------------
+
+/// Browser builds read the token from the page URL (`/?token=...`).
+#[cfg(target_arch = "wasm32")]
+fn api_token() -> Option<String> {
+    let search = web_sys::window()?.location().search().ok()?;
+    search
+        .trim_start_matches('?')
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("token="))
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+static NATIVE_API_TOKEN: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+
+/// Set the API token for native builds. Must be called before the app is
+/// created; without one, requests are sent unauthenticated.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn set_api_token(token: String) {
+    NATIVE_API_TOKEN.set(token).ok();
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn api_token() -> Option<String> {
+    NATIVE_API_TOKEN.get().cloned()
+}
+
+/// Attach the API token (if any) as a bearer header.
+fn authorized(mut req: ehttp::Request) -> ehttp::Request {
+    if let Some(token) = api_token() {
+        req.headers
+            .insert("Authorization".to_string(), format!("Bearer {token}"));
+    }
+    req
+}
+
 // ---------------------------------------------------------------------------
 // REST fetches — results go into the Pending queue
 // ---------------------------------------------------------------------------
@@ -92,7 +135,7 @@ pub fn fetch_status(ctx: &egui::Context, pending: &PendingHandle) {
     let ctx = ctx.clone();
     let pending = Arc::clone(pending);
     let url = format!("{}/api/status", api_base());
-    ehttp::fetch(ehttp::Request::get(&url), move |result| {
+    ehttp::fetch(authorized(ehttp::Request::get(&url)), move |result| {
         if let Ok(resp) = result
             && let Ok(status) = serde_json::from_slice::<StatusResponse>(&resp.bytes)
             && let Ok(mut p) = pending.lock()
@@ -107,7 +150,7 @@ pub fn fetch_shots(ctx: &egui::Context, pending: &PendingHandle) {
     let ctx = ctx.clone();
     let pending = Arc::clone(pending);
     let url = format!("{}/api/shots?limit=100", api_base());
-    ehttp::fetch(ehttp::Request::get(&url), move |result| {
+    ehttp::fetch(authorized(ehttp::Request::get(&url)), move |result| {
         if let Ok(resp) = result
             && let Ok(shots) = serde_json::from_slice::<Vec<ShotData>>(&resp.bytes)
             && let Ok(mut p) = pending.lock()
@@ -122,7 +165,7 @@ pub fn fetch_settings(ctx: &egui::Context, pending: &PendingHandle) {
     let ctx = ctx.clone();
     let pending = Arc::clone(pending);
     let url = format!("{}/api/settings", api_base());
-    ehttp::fetch(ehttp::Request::get(&url), move |result| {
+    ehttp::fetch(authorized(ehttp::Request::get(&url)), move |result| {
         if let Ok(resp) = result
             && let Ok(settings) = serde_json::from_slice::<FlighthookConfig>(&resp.bytes)
             && let Ok(mut p) = pending.lock()
@@ -151,14 +194,17 @@ pub fn post_settings(
     http_req
         .headers
         .insert("Content-Type".to_string(), "application/json".to_string());
-    ehttp::fetch(http_req, move |result| {
-        if let Ok(resp) = result
-            && let Ok(save_resp) = serde_json::from_slice::<PostSettingsResponse>(&resp.bytes)
-            && let Ok(mut p) = pending.lock()
-        {
+    ehttp::fetch(authorized(http_req), move |result| {
+        let Ok(resp) = result else { return };
+        let Ok(mut p) = pending.lock() else { return };
+        if resp.status == 422 {
+            p.settings_rejected = serde_json::from_slice::<ConfigValidation>(&resp.bytes).ok();
+        } else if let Ok(save_resp) = serde_json::from_slice::<PostSettingsResponse>(&resp.bytes) {
             p.settings_save = Some(save_resp);
-            ctx.request_repaint();
+        } else {
+            return;
         }
+        ctx.request_repaint();
     });
 }
 
@@ -169,7 +215,7 @@ pub fn post_mode(ctx: &egui::Context, mode: &str) {
     let mut req = ehttp::Request::post(&url, body);
     req.headers
         .insert("Content-Type".to_string(), "application/json".to_string());
-    ehttp::fetch(req, move |_| {
+    ehttp::fetch(authorized(req), move |_| {
         ctx.request_repaint();
     });
 }
@@ -195,9 +241,15 @@ pub fn connect_ws() -> Option<(ewebsock::WsSender, ewebsock::WsReceiver)> {
 
 /// Send the init handshake after the WebSocket is confirmed open.
 pub fn send_ws_start(tx: &mut ewebsock::WsSender) {
-    tx.send(ewebsock::WsMessage::Text(
-        r#"{"kind":"start","version":["0.1.0"],"name":"Flighthook Dashboard"}"#.into(),
-    ));
+    let mut start = serde_json::json!({
+        "kind": "start",
+        "version": ["0.1.0"],
+        "name": "Flighthook Dashboard",
+    });
+    if let Some(token) = api_token() {
+        start["token"] = token.into();
+    }
+    tx.send(ewebsock::WsMessage::Text(start.to_string()));
 }
 
 /// Result from polling the WebSocket.
@@ -207,7 +259,7 @@ pub enum WsPollEvent {
     /// Init handshake completed — server assigned a actor_id.
     Init { actor_id: String },
     /// A bus event from the server.
-    Message(FlighthookMessage),
+    Message(Box<FlighthookMessage>),
     /// WebSocket error.
     Error(String),
     /// WebSocket connection lost.
@@ -229,7 +281,7 @@ pub fn poll_ws(rx: &mut ewebsock::WsReceiver) -> (Vec<WsPollEvent>, u64) {
                 if let Some(init) = parse_init_response(&text) {
                     events.push(init);
                 } else if let Some(msg) = types::parse_ws_message(&text) {
-                    events.push(WsPollEvent::Message(msg));
+                    events.push(WsPollEvent::Message(Box::new(msg)));
                 }
             }
             ewebsock::WsEvent::Error(e) => {
@@ -13,6 +13,8 @@ pub(crate) const MESSAGE_TYPES: &[&str] = &[
     "club_path",
     "face_impact",
     "shot_finished",
+    "shot_delivered",
+    "shot_suppressed",
     "device_telemetry",
     "actor_status",
     "player_info",
@@ -35,7 +37,16 @@ const FILTER_GROUPS: &[(&str, &[&str])] = &[
             "device_telemetry",
         ],
     ),
-    ("Game", &["player_info", "club_info", "set_detection_mode"]),
+    (
+        "Game",
+        &[
+            "player_info",
+            "club_info",
+            "set_detection_mode",
+            "shot_delivered",
+            "shot_suppressed",
+        ],
+    ),
     (
         "System",
         &["actor_status", "config_command", "config_outcome"],
@@ -58,6 +69,8 @@ pub(crate) fn message_type(event: &FlighthookEvent) -> &'static str {
         FlighthookEvent::ActorStatus { .. } => "actor_status",
         FlighthookEvent::ConfigCommand { .. } => "config_command",
         FlighthookEvent::ConfigOutcome { .. } => "config_outcome",
+        FlighthookEvent::ShotDelivered { .. } => "shot_delivered",
+        FlighthookEvent::ShotSuppressed { .. } => "shot_suppressed",
         FlighthookEvent::Alert { severity, .. } => match severity {
             Severity::Critical => "alert_critical",
             Severity::Error => "alert_error",
@@ -119,6 +132,18 @@ pub(crate) fn event_debug(event: &FlighthookEvent) -> String {
             Some(rid) => format!("outcome({rid})"),
             None => "outcome".into(),
         },
+        FlighthookEvent::ShotDelivered {
+            key, latency_ms, ..
+        } => format!("delivered #{} ({latency_ms}ms)", key.shot_number),
+        FlighthookEvent::ShotSuppressed {
+            key,
+            kept,
+            kept_source,
+            ..
+        } => format!(
+            "suppressed #{} (duplicate of #{} from {kept_source})",
+            key.shot_number, kept.shot_number
+        ),
         FlighthookEvent::Alert { severity, message } => format!("[{severity}] {message}"),
     }
 }
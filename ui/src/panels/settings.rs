@@ -1,8 +1,9 @@
 use crate::app::FlighthookApp;
 use crate::net;
 use crate::types::{
-    CameraMode, Club, Distance, DistanceExt, FlighthookConfig, GsProSection, MevoSection,
-    MockMonitorSection, R10Section, RandomClubSection, UnitSystem, WebserverSection,
+    CameraMode, Club, ConfigIssue, ConfigValidation, DeviceModeSection, Distance, DistanceExt,
+    FlighthookConfig, GsProSection, MevoSection, MockMonitorSection, R10Section, RandomClubSection,
+    ShotDetectionMode, UnitSystem, WebserverSection,
 };
 
 const DISTANCE_UNITS: &[(&str, &str)] = &[
@@ -68,6 +69,11 @@ pub(crate) struct DeviceFormEntry {
     /// saving settings does not wipe them from the config file.
     pub(crate) square_club: Option<String>,
     pub(crate) square_advanced_spin: Option<bool>,
+    /// Detection mode override. Only `pinned` is edited here; the per-mode
+    /// mapping is carried verbatim.
+    pub(crate) device_mode: DeviceModeSection,
+    /// Section kept in the config, but its actor is not started.
+    pub(crate) disabled: bool,
     pub(crate) dirty: bool,
 }
 
@@ -106,6 +112,8 @@ impl DeviceFormEntry {
             discard_zero_spin: true,
             square_club: None,
             square_advanced_spin: None,
+            device_mode: s.mode,
+            disabled: s.disabled,
             dirty: false,
         }
     }
@@ -133,6 +141,8 @@ impl DeviceFormEntry {
             discard_zero_spin: true,
             square_club: None,
             square_advanced_spin: None,
+            device_mode: s.mode,
+            disabled: s.disabled,
             dirty: false,
         }
     }
@@ -146,6 +156,7 @@ impl DeviceFormEntry {
             discard_zero_spin: s.discard_non_putting_zero_spin.unwrap_or(true),
             square_club: s.club.clone(),
             square_advanced_spin: s.advanced_spin,
+            device_mode: s.mode,
             ball_type: 0,
             tee_height_val: "1.5".into(),
             tee_height_unit: "inches".into(),
@@ -156,6 +167,7 @@ impl DeviceFormEntry {
             track_pct: "80".into(),
             use_estimated: true,
             camera_mode: CameraMode::default(),
+            disabled: s.disabled,
             dirty: false,
         }
     }
@@ -179,6 +191,8 @@ impl DeviceFormEntry {
             discard_zero_spin: true,
             square_club: None,
             square_advanced_spin: None,
+            device_mode: s.mode,
+            disabled: s.disabled,
             dirty: false,
         }
     }
@@ -202,6 +216,8 @@ impl DeviceFormEntry {
             discard_zero_spin: true,
             square_club: None,
             square_advanced_spin: None,
+            device_mode: s.mode,
+            disabled: s.disabled,
             dirty: false,
         }
     }
@@ -229,6 +245,15 @@ impl DeviceFormEntry {
         self.is_square()
     }
 
+    /// Whether the device has a detection mode override (`mode` on its
+    /// section).
+    pub(crate) fn has_mode_setting(&self) -> bool {
+        matches!(
+            self.monitor_type.as_str(),
+            "mevo" | "r10" | "square" | "mock_monitor" | "openconnect_server"
+        )
+    }
+
     /// Whether the Mevo tuning block applies — ball type, tee height, range,
     /// surface height, track percentage, estimated shots. These are all
     /// FlightScope radar settings with no meaning on any other device.
@@ -251,6 +276,11 @@ pub(crate) struct IntegrationFormEntry {
     pub(crate) chipping_monitor: String,
     /// Routing: actor ID for putting monitor, or empty = "Any".
     pub(crate) putting_monitor: String,
+    /// Named `[routing.<name>]` rule set that replaces the per-mode
+    /// monitors. Not edited here; carried through unchanged.
+    pub(crate) routing: Option<String>,
+    /// Section kept in the config, but its actor is not started.
+    pub(crate) disabled: bool,
     pub(crate) dirty: bool,
 }
 
@@ -262,6 +292,23 @@ pub(crate) enum ActorFormEntry {
 }
 
 impl ActorFormEntry {
+    /// Global section ID, e.g. `"mevo.0"`.
+    pub(crate) fn global_id(&self) -> String {
+        match self {
+            ActorFormEntry::Device(d) => format!("{}.{}", d.monitor_type, d.id),
+            ActorFormEntry::Integration(i) => format!("{}.{}", i.integration_type, i.id),
+        }
+    }
+
+    /// Whether the form flags a malformed address next to the field itself,
+    /// so `invalid_address` issues need not be repeated under the header.
+    fn flags_address_inline(&self) -> bool {
+        match self {
+            ActorFormEntry::Device(d) => d.has_network_address() || d.has_ble_address(),
+            ActorFormEntry::Integration(i) => i.integration_type != "random_club",
+        }
+    }
+
     pub(crate) fn name(&self) -> &str {
         match self {
             ActorFormEntry::Device(d) => &d.name,
@@ -362,6 +409,9 @@ pub(crate) struct SettingsForm {
     pub(crate) save_target: Option<SaveTarget>,
     /// Snapshot of the config at last load/save — used to build scoped requests.
     original_config: Option<FlighthookConfig>,
+    /// Errors from the last save, which the server rejected. Cleared by the
+    /// next successful save.
+    pub(crate) rejected: Option<ConfigValidation>,
 }
 
 impl Default for SettingsForm {
@@ -377,6 +427,7 @@ impl Default for SettingsForm {
             saving: false,
             save_target: None,
             original_config: None,
+            rejected: None,
         }
     }
 }
@@ -430,6 +481,8 @@ impl SettingsForm {
                     full_monitor: section.full_monitor.clone().unwrap_or_default(),
                     chipping_monitor: section.chipping_monitor.clone().unwrap_or_default(),
                     putting_monitor: section.putting_monitor.clone().unwrap_or_default(),
+                    routing: section.routing.clone(),
+                    disabled: section.disabled,
                     dirty: false,
                 }));
         }
@@ -443,9 +496,10 @@ impl SettingsForm {
                     full_monitor: String::new(),
                     chipping_monitor: String::new(),
                     putting_monitor: String::new(),
+                    routing: None,
+                    disabled: section.disabled,
                     dirty: false,
                 }));
-            let _ = section;
         }
         for (id, section) in &s.webserver {
             self.actors
@@ -457,6 +511,8 @@ impl SettingsForm {
                     full_monitor: String::new(),
                     chipping_monitor: String::new(),
                     putting_monitor: String::new(),
+                    routing: None,
+                    disabled: section.disabled,
                     dirty: false,
                 }));
         }
@@ -466,6 +522,21 @@ impl SettingsForm {
         self.dirty = false;
     }
 
+    /// Validation issues to show inline: those of the form as it stands,
+    /// plus errors from a rejected save that the local check does not
+    /// reproduce.
+    pub(crate) fn issues(&self) -> ConfigValidation {
+        let mut issues = self.to_request().validate();
+        if let Some(rejected) = &self.rejected {
+            for e in &rejected.errors {
+                if !issues.errors.contains(e) {
+                    issues.errors.push(e.clone());
+                }
+            }
+        }
+        issues
+    }
+
     pub(crate) fn is_valid(&self) -> bool {
         for actor in &self.actors {
             match actor {
@@ -519,6 +590,7 @@ impl SettingsForm {
                             dev.id.clone(),
                             MevoSection {
                                 name: dev.name.clone(),
+                                disabled: dev.disabled,
                                 address: if dev.address.is_empty() {
                                     None
                                 } else {
@@ -539,6 +611,7 @@ impl SettingsForm {
                                 track_pct: dev.track_pct.parse().ok(),
                                 use_estimated: Some(dev.use_estimated),
                                 camera_mode: Some(dev.camera_mode),
+                                mode: dev.device_mode,
                             },
                         );
                     }
@@ -547,6 +620,7 @@ impl SettingsForm {
                             dev.id.clone(),
                             flighthook::SquareSection {
                                 name: dev.name.clone(),
+                                disabled: dev.disabled,
                                 address: if dev.address.trim().is_empty() {
                                     None
                                 } else {
@@ -557,6 +631,7 @@ impl SettingsForm {
                                 club: dev.square_club.clone(),
                                 advanced_spin: dev.square_advanced_spin,
                                 discard_non_putting_zero_spin: Some(dev.discard_zero_spin),
+                                mode: dev.device_mode,
                             },
                         );
                     }
@@ -565,11 +640,13 @@ impl SettingsForm {
                             dev.id.clone(),
                             R10Section {
                                 name: dev.name.clone(),
+                                disabled: dev.disabled,
                                 range: dev
                                     .range_val
                                     .parse::<f64>()
                                     .ok()
                                     .map(|v| Distance::from_value_and_unit(v, &dev.range_unit)),
+                                mode: dev.device_mode,
                             },
                         );
                     }
@@ -578,11 +655,13 @@ impl SettingsForm {
                             dev.id.clone(),
                             flighthook::OpenConnectServerSection {
                                 name: dev.name.clone(),
+                                disabled: dev.disabled,
                                 bind: if dev.address.is_empty() {
                                     None
                                 } else {
                                     Some(dev.address.clone())
                                 },
+                                mode: dev.device_mode,
                             },
                         );
                     }
@@ -591,6 +670,8 @@ impl SettingsForm {
                             dev.id.clone(),
                             MockMonitorSection {
                                 name: dev.name.clone(),
+                                disabled: dev.disabled,
+                                mode: dev.device_mode,
                             },
                         );
                     }
@@ -602,6 +683,7 @@ impl SettingsForm {
                             entry.id.clone(),
                             GsProSection {
                                 name: entry.name.clone(),
+                                disabled: entry.disabled,
                                 address: if entry.address.is_empty() {
                                     None
                                 } else {
@@ -622,6 +704,7 @@ impl SettingsForm {
                                 } else {
                                     Some(entry.putting_monitor.clone())
                                 },
+                                routing: entry.routing.clone(),
                             },
                         );
                     }
@@ -630,15 +713,25 @@ impl SettingsForm {
                             entry.id.clone(),
                             RandomClubSection {
                                 name: entry.name.clone(),
+                                disabled: entry.disabled,
                             },
                         );
                     }
                     "webserver" => {
+                        // Tokens and TLS are not editable in the form; keep the loaded ones.
+                        let existing = self
+                            .original_config
+                            .as_ref()
+                            .and_then(|c| c.webserver.get(&entry.id))
+                            .cloned()
+                            .unwrap_or_default();
                         webserver.insert(
                             entry.id.clone(),
                             WebserverSection {
                                 name: entry.name.clone(),
+                                disabled: entry.disabled,
                                 bind: entry.address.clone(),
+                                ..existing
                             },
                         );
                     }
@@ -647,6 +740,11 @@ impl SettingsForm {
             }
         }
 
+        // Profiles, plugins, scripts, processes, routing rule sets, and the
+        // supervisor, health and dedup policies are not edited here; carry
+        // them through unchanged.
+        let base = self.original_config.clone().unwrap_or_default();
+
         FlighthookConfig {
             default_units: self.default_units,
             chipping_clubs: self.chipping_clubs.clone(),
@@ -659,6 +757,7 @@ impl SettingsForm {
             openconnect_server,
             gspro,
             random_club,
+            ..base
         }
     }
 
@@ -717,6 +816,34 @@ impl SettingsForm {
     }
 }
 
+/// Validation errors (red) and warnings (amber) for one section, under its
+/// header. `None` is the global settings block.
+fn show_issues(
+    ui: &mut egui::Ui,
+    issues: &ConfigValidation,
+    section: Option<&str>,
+    skip: impl Fn(&ConfigIssue) -> bool,
+) {
+    for (is_error, issue) in issues.for_section(section) {
+        if skip(issue) {
+            continue;
+        }
+        let color = if is_error {
+            egui::Color32::from_rgb(255, 80, 80)
+        } else {
+            egui::Color32::from_rgb(230, 180, 60)
+        };
+        let text = match &issue.field {
+            Some(field) => format!("{field}: {}", issue.message),
+            None => issue.message.clone(),
+        };
+        ui.horizontal(|ui| {
+            ui.add_space(16.0);
+            ui.label(egui::RichText::new(text).color(color).size(11.0));
+        });
+    }
+}
+
 /// Apply a single actor form entry's data into a FlighthookConfig.
 /// Returns the actor's global ID (e.g. "mevo.0").
 fn apply_actor_to_config(config: &mut FlighthookConfig, actor: &ActorFormEntry) -> String {
@@ -729,6 +856,7 @@ fn apply_actor_to_config(config: &mut FlighthookConfig, actor: &ActorFormEntry)
                         dev.id.clone(),
                         MevoSection {
                             name: dev.name.clone(),
+                            disabled: dev.disabled,
                             address: if dev.address.is_empty() {
                                 None
                             } else {
@@ -751,6 +879,7 @@ fn apply_actor_to_config(config: &mut FlighthookConfig, actor: &ActorFormEntry)
                             track_pct: dev.track_pct.parse().ok(),
                             use_estimated: Some(dev.use_estimated),
                             camera_mode: Some(dev.camera_mode),
+                            mode: dev.device_mode,
                         },
                     );
                 }
@@ -759,6 +888,7 @@ fn apply_actor_to_config(config: &mut FlighthookConfig, actor: &ActorFormEntry)
                         dev.id.clone(),
                         flighthook::SquareSection {
                             name: dev.name.clone(),
+                            disabled: dev.disabled,
                             address: if dev.address.trim().is_empty() {
                                 None
                             } else {
@@ -767,6 +897,7 @@ fn apply_actor_to_config(config: &mut FlighthookConfig, actor: &ActorFormEntry)
                             club: dev.square_club.clone(),
                             advanced_spin: dev.square_advanced_spin,
                             discard_non_putting_zero_spin: Some(dev.discard_zero_spin),
+                            mode: dev.device_mode,
                         },
                     );
                 }
@@ -775,11 +906,13 @@ fn apply_actor_to_config(config: &mut FlighthookConfig, actor: &ActorFormEntry)
                         dev.id.clone(),
                         R10Section {
                             name: dev.name.clone(),
+                            disabled: dev.disabled,
                             range: dev
                                 .range_val
                                 .parse::<f64>()
                                 .ok()
                                 .map(|v| Distance::from_value_and_unit(v, &dev.range_unit)),
+                            mode: dev.device_mode,
                         },
                     );
                 }
@@ -788,11 +921,13 @@ fn apply_actor_to_config(config: &mut FlighthookConfig, actor: &ActorFormEntry)
                         dev.id.clone(),
                         flighthook::OpenConnectServerSection {
                             name: dev.name.clone(),
+                            disabled: dev.disabled,
                             bind: if dev.address.is_empty() {
                                 None
                             } else {
                                 Some(dev.address.clone())
                             },
+                            mode: dev.device_mode,
                         },
                     );
                 }
@@ -801,6 +936,8 @@ fn apply_actor_to_config(config: &mut FlighthookConfig, actor: &ActorFormEntry)
                         dev.id.clone(),
                         MockMonitorSection {
                             name: dev.name.clone(),
+                            disabled: dev.disabled,
+                            mode: dev.device_mode,
                         },
                     );
                 }
@@ -816,6 +953,7 @@ fn apply_actor_to_config(config: &mut FlighthookConfig, actor: &ActorFormEntry)
                         entry.id.clone(),
                         GsProSection {
                             name: entry.name.clone(),
+                            disabled: entry.disabled,
                             address: if entry.address.is_empty() {
                                 None
                             } else {
@@ -836,6 +974,7 @@ fn apply_actor_to_config(config: &mut FlighthookConfig, actor: &ActorFormEntry)
                             } else {
                                 Some(entry.putting_monitor.clone())
                             },
+                            routing: entry.routing.clone(),
                         },
                     );
                 }
@@ -844,15 +983,20 @@ fn apply_actor_to_config(config: &mut FlighthookConfig, actor: &ActorFormEntry)
                         entry.id.clone(),
                         RandomClubSection {
                             name: entry.name.clone(),
+                            disabled: entry.disabled,
                         },
                     );
                 }
                 "webserver" => {
+                    // Tokens and TLS are not editable in the form; keep the loaded ones.
+                    let existing = config.webserver.get(&entry.id).cloned().unwrap_or_default();
                     config.webserver.insert(
                         entry.id.clone(),
                         WebserverSection {
                             name: entry.name.clone(),
+                            disabled: entry.disabled,
                             bind: entry.address.clone(),
+                            ..existing
                         },
                     );
                 }
@@ -900,6 +1044,8 @@ impl FlighthookApp {
                         self.show_api_docs = true;
                     }
                 });
+                let issues = self.settings.issues();
+                show_issues(ui, &issues, None, |_| false);
                 ui.add_space(4.0);
                 ui.horizontal(|ui| {
                     ui.label("Default Units:").on_hover_text("Default unit system for shot display. Can be toggled per-session in the Shots tab.");
@@ -1033,6 +1179,10 @@ impl FlighthookApp {
                             save_idx = Some(idx);
                         }
                     });
+                    let address_inline = actor.flags_address_inline();
+                    show_issues(ui, &issues, Some(&actor.global_id()), |i| {
+                        address_inline && i.code == "invalid_address"
+                    });
 
                     // Type-specific fields
                     match actor {
@@ -1049,6 +1199,18 @@ impl FlighthookApp {
                                 }
                             });
 
+                            // Disabled — section kept, actor not started
+                            ui.horizontal(|ui| {
+                                ui.add_space(16.0);
+                                if ui
+                                    .checkbox(&mut dev.disabled, "Disabled")
+                                    .on_hover_text("Keep this device in the config but do not run it.")
+                                    .changed()
+                                {
+                                    dev.dirty = true;
+                                }
+                            });
+
                             if dev.has_network_address() {
                                 // Address
                                 ui.horizontal(|ui| {
@@ -1094,6 +1256,39 @@ impl FlighthookApp {
                                 });
                             }
 
+                            if dev.has_mode_setting() {
+                                // Detection mode pin; unpinned follows the global mode.
+                                ui.horizontal(|ui| {
+                                    ui.add_space(16.0);
+                                    ui.label("Detection Mode:").on_hover_text(
+                                        "Pin this device to one detection mode so it ignores\nmode changes meant for other monitors on the bay.",
+                                    );
+                                    let label = |mode: Option<ShotDetectionMode>| match mode {
+                                        Some(mode) => mode.to_string(),
+                                        None => "follow global".into(),
+                                    };
+                                    egui::ComboBox::from_id_salt(format!("device_mode_{}_{}", dev.monitor_type, dev.id))
+                                        .selected_text(label(dev.device_mode.pinned))
+                                        .width(field_width)
+                                        .show_ui(ui, |ui| {
+                                            for mode in [
+                                                None,
+                                                Some(ShotDetectionMode::Full),
+                                                Some(ShotDetectionMode::Chipping),
+                                                Some(ShotDetectionMode::Putting),
+                                            ] {
+                                                if ui
+                                                    .selectable_label(dev.device_mode.pinned == mode, label(mode))
+                                                    .clicked()
+                                                {
+                                                    dev.device_mode.pinned = mode;
+                                                    dev.dirty = true;
+                                                }
+                                            }
+                                        });
+                                });
+                            }
+
                             if dev.is_square() {
                                 // Zero-spin rejection (putts always exempt).
                                 ui.horizontal(|ui| {
@@ -1299,6 +1494,18 @@ impl FlighthookApp {
                                 }
                             });
 
+                            // Disabled — section kept, actor not started
+                            ui.horizontal(|ui| {
+                                ui.add_space(16.0);
+                                if ui
+                                    .checkbox(&mut entry.disabled, "Disabled")
+                                    .on_hover_text("Keep this integration in the config but do not run it.")
+                                    .changed()
+                                {
+                                    entry.dirty = true;
+                                }
+                            });
+
                             // Address field (skip for mock)
                             if entry.integration_type != "random_club" {
                                 ui.horizontal(|ui| {
@@ -1322,7 +1529,14 @@ impl FlighthookApp {
                             }
 
                             // Routing dropdowns (GSPro only)
-                            if entry.integration_type == "gspro" {
+                            if let Some(set) = entry.routing.as_deref().filter(|_| entry.integration_type == "gspro") {
+                                ui.horizontal(|ui| {
+                                    ui.add_space(16.0);
+                                    ui.label(format!("Routing: rule set '{set}'")).on_hover_text(
+                                        "Shots are routed by the [routing] rule set named in the config file, not per mode."
+                                    );
+                                });
+                            } else if entry.integration_type == "gspro" {
                                 for (field_label, field_val, salt) in [
                                     ("Full Monitor:", &mut entry.full_monitor, "full"),
                                     ("Chipping Monitor:", &mut entry.chipping_monitor, "chipping"),
@@ -1407,6 +1621,8 @@ impl FlighthookApp {
                                     discard_zero_spin: true,
                                     square_club: None,
                                     square_advanced_spin: None,
+                                    device_mode: DeviceModeSection::default(),
+                                    disabled: false,
                                     dirty: true,
                                 }));
                                 self.settings.dirty = true;
@@ -1438,6 +1654,8 @@ impl FlighthookApp {
                                     discard_zero_spin: true,
                                     square_club: None,
                                     square_advanced_spin: None,
+                                    device_mode: DeviceModeSection::default(),
+                                    disabled: false,
                                     dirty: true,
                                 }));
                                 self.settings.dirty = true;
@@ -1468,6 +1686,8 @@ impl FlighthookApp {
                                     discard_zero_spin: true,
                                     square_club: None,
                                     square_advanced_spin: None,
+                                    device_mode: DeviceModeSection::default(),
+                                    disabled: false,
                                     dirty: true,
                                 }));
                                 self.settings.dirty = true;
@@ -1498,6 +1718,8 @@ impl FlighthookApp {
                                     discard_zero_spin: true,
                                     square_club: None,
                                     square_advanced_spin: None,
+                                    device_mode: DeviceModeSection::default(),
+                                    disabled: false,
                                     dirty: true,
                                 }));
                                 self.settings.dirty = true;
@@ -1518,6 +1740,8 @@ impl FlighthookApp {
                                     full_monitor: String::new(),
                                     chipping_monitor: String::new(),
                                     putting_monitor: String::new(),
+                                    routing: None,
+                                    disabled: false,
                                     dirty: true,
                                 }));
                                 self.settings.dirty = true;
@@ -1538,6 +1762,8 @@ impl FlighthookApp {
                                     full_monitor: String::new(),
                                     chipping_monitor: String::new(),
                                     putting_monitor: String::new(),
+                                    routing: None,
+                                    disabled: false,
                                     dirty: true,
                                 }));
                                 self.settings.dirty = true;
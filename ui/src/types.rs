@@ -18,6 +18,9 @@ pub use flighthook::{
     Club,
     ClubData,
     ClubInfo,
+    ConfigIssue,
+    ConfigValidation,
+    DeviceModeSection,
     // Config types
     Distance,
     DistanceExt,
@@ -70,6 +73,7 @@ impl ShotRow {
             ball: self.ball.clone(),
             club: self.club.clone(),
             impact: None,
+            estimated: false,
         };
         let converted = tmp.to_unit_system(system);
         ShotRow {